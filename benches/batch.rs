@@ -0,0 +1,69 @@
+//! Compares computing the impulses of a [`SpringBatch`] one lane at a time
+//! against [`SpringBatch::impulses`].
+//!
+//! `cargo bench --bench batch` measures both through [`Spring::impulse`],
+//! `cargo bench --bench batch --features simd` measures the `wide` vectors
+//! against it.
+use bevy::prelude::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use springy::batch::{SpringBatch, LANES};
+use springy::{prelude::*, SpringInstant};
+
+const SPRINGS: usize = 10_000;
+const TIMESTEP: f32 = 1.0 / 60.0;
+
+fn batches() -> Vec<SpringBatch> {
+    let presets: [Spring; LANES] = [
+        Spring::snappy(),
+        Spring::bouncy().with_damp_mode(DampMode::Axial),
+        Spring::stiff(),
+        Spring::sluggish(),
+    ];
+
+    (0..SPRINGS / LANES)
+        .map(|index| {
+            let mut batch = SpringBatch::default();
+            for (lane, preset) in presets.iter().enumerate() {
+                let seed = (index * LANES + lane) as f32;
+                let instant = SpringInstant {
+                    reduced_inertia: Vec3::splat(1.0 + seed % 3.0),
+                    displacement: Vec3::new(seed.sin(), seed.cos(), (seed * 0.5).sin()),
+                    velocity: Vec3::new((seed * 0.3).cos(), (seed * 0.7).sin(), 0.1),
+                };
+                batch.set(lane, *preset, &instant);
+            }
+            batch
+        })
+        .collect()
+}
+
+fn impulses(c: &mut Criterion) {
+    let batches = batches();
+    let mut group = c.benchmark_group("10k spring impulses");
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for batch in &batches {
+                black_box(batch.scalar_impulses(black_box(TIMESTEP)));
+            }
+        })
+    });
+
+    let name = if cfg!(feature = "simd") {
+        "batch simd"
+    } else {
+        "batch"
+    };
+    group.bench_function(name, |b| {
+        b.iter(|| {
+            for batch in &batches {
+                black_box(batch.impulses(black_box(TIMESTEP)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, impulses);
+criterion_main!(benches);
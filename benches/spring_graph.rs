@@ -0,0 +1,81 @@
+//! Compares the island solver of `spring_impulse` against the
+//! [`SpringGraphPlugin`] on 10k springs.
+//!
+//! `cargo bench --bench spring_graph`, add `--features simd` to solve the
+//! graph with SIMD batches.
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use springy::prelude::*;
+
+const CHAINS: usize = 100;
+const LINKS: usize = 100;
+
+fn app(graph: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(SpringPlugin::new(Update).with_solver_iterations(4))
+        .insert_resource(SpringTimestep::from_seconds(1.0 / 60.0))
+        .add_systems(Startup, setup_chains);
+
+    if graph {
+        app.add_plugins(SpringGraphPlugin);
+    }
+
+    // Let the chains settle into their islands before measuring.
+    for _ in 0..10 {
+        app.update();
+    }
+    app
+}
+
+fn setup_chains(mut commands: Commands) {
+    for chain in 0..CHAINS {
+        let x = chain as f32;
+        let mut previous = commands
+            .spawn((
+                TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                ParticleBundle {
+                    inertia: Inertia::INFINITY,
+                    ..default()
+                },
+            ))
+            .id();
+
+        for link in 1..=LINKS {
+            previous = commands
+                .spawn((
+                    TransformBundle::from_transform(Transform::from_xyz(
+                        x,
+                        -(link as f32) * 0.1,
+                        0.0,
+                    )),
+                    ParticleBundle::default(),
+                    SpringBundle {
+                        joint: SpringJoint::linear(previous).with_rest_length(0.1),
+                        ..SpringBundle::new(previous, Spring::snappy())
+                    },
+                ))
+                .id();
+        }
+    }
+}
+
+fn solvers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("10k springs");
+
+    let mut islands = app(false);
+    group.bench_function("spring_impulse", |b| b.iter(|| islands.update()));
+
+    let mut graph = app(true);
+    let name = if cfg!(feature = "simd") {
+        "spring_graph simd"
+    } else {
+        "spring_graph"
+    };
+    group.bench_function(name, |b| b.iter(|| graph.update()));
+
+    group.finish();
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
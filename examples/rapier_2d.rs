@@ -1,8 +1,7 @@
-use bevy::math::Vec3Swizzles;
-use bevy::{color::palettes::css, prelude::*, window::PresentMode};
+use bevy::{color::palettes::css, prelude::*};
 use bevy_rapier2d::prelude::*;
-
-const TICK_RATE: f32 = 1.0 / 100.0;
+use springy::joint::spring_impulse;
+use springy::prelude::{Spring, SpringBundle, SpringPlugin, SpringSchedule, SpringSet};
 
 fn main() {
     App::new()
@@ -18,19 +17,18 @@ fn main() {
         .add_plugins(DefaultPlugins)
         //.add_plugin(bevy_editor_pls::EditorPlugin::new())
         .add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::default())
-        .insert_resource(bevy_framepace::FramepaceSettings {
-            limiter: bevy_framepace::Limiter::Manual(std::time::Duration::from_secs_f64(
-                TICK_RATE as f64,
-            )),
-            ..default()
-        })
+        .insert_resource(Time::<Fixed>::from_hz(100.0))
         .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(10.0))
         .add_plugins(RapierDebugRenderPlugin::default())
         .add_systems(Startup, setup_graphics)
         .add_systems(Startup, setup_translation)
         //.add_systems(Startup, setup_rotation)
-        .add_systems(Update, spring_impulse)
-        .register_type::<SpringSettings>()
+        .add_plugins(SpringPlugin::default())
+        .add_systems(
+            SpringSchedule,
+            spring_impulse::<Velocity, ReadMassProperties, ExternalImpulse>
+                .in_set(SpringSet::Solve),
+        )
         .run();
 }
 
@@ -47,145 +45,6 @@ fn setup_graphics(mut commands: Commands) {
         .insert(Name::new("Camera"));
 }
 
-#[derive(Debug, Copy, Clone, Component)]
-pub struct Spring {
-    pub containing: Entity,
-}
-
-#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
-#[reflect(Component)]
-pub struct SpringSettings(springy::Spring);
-
-/*
-pub fn spring_impulse(
-    time: Res<Time>,
-    mut impulses: Query<&mut Impulse>,
-    mut springs: Query<(
-        Entity,
-        &GlobalTransform,
-        &Velocity,
-        &Inertia,
-        &SpringSettings,
-        &Spring,
-        &mut PreviousUnitVector,
-    )>,
-    particle: Query<(&GlobalTransform, &Velocity, &Inertia)>,
-) {
-    if time.delta_seconds() == 0.0 {
-        return;
-    }
-
-    let timestep = TICK_RATE as f32;
-
-    for (
-        spring_entity,
-        spring_transform,
-        spring_velocity,
-        spring_mass,
-        spring_settings,
-        spring,
-        mut previous_unit_vector,
-    ) in &mut springs
-    {
-        let particle_entity = spring.containing;
-        let (particle_transform, particle_velocity, particle_mass) =
-            particle.get(particle_entity).unwrap();
-
-        if particle_entity == spring_entity {
-            continue;
-        }
-
-        let (_, spring_rotation, spring_translation) =
-            spring_transform.to_scale_rotation_translation();
-        let particle_a = springy::TranslationParticle2 {
-            mass: spring_mass.linear,
-            translation: spring_translation.xy(),
-            velocity: spring_velocity.linear,
-        };
-
-        let unit_vector = spring_rotation * Vec3::X;
-        let angular_particle_a = springy::AngularParticle2 {
-            inertia: spring_mass.angular,
-            rotation: unit_vector.y.atan2(unit_vector.x),
-            velocity: spring_velocity.angular,
-        };
-
-        let (_, particle_rotation, particle_translation) =
-            particle_transform.to_scale_rotation_translation();
-        let particle_b = springy::TranslationParticle2 {
-            mass: particle_mass.linear,
-            translation: particle_translation.xy(),
-            velocity: particle_velocity.linear,
-        };
-
-        let unit_vector_b = particle_rotation * Vec3::X;
-        let angular_particle_b = springy::AngularParticle2 {
-            inertia: particle_mass.angular,
-            rotation: unit_vector_b.y.atan2(unit_vector_b.x),
-            velocity: particle_velocity.angular,
-        };
-
-        let instant = particle_a.instant(&particle_b);
-        let impulse = spring_settings.0.impulse(timestep, instant);
-
-        let angular_instant = angular_particle_a.instant(&angular_particle_b);
-        let angular_impulse = spring_settings.0.impulse(timestep, angular_instant);
-
-        let [mut spring_impulse, mut particle_impulse] = impulses
-            .get_many_mut([spring_entity, particle_entity])
-            .unwrap();
-
-        spring_impulse.linear += impulse;
-        spring_impulse.angular += angular_impulse;
-        particle_impulse.linear -= impulse;
-        particle_impulse.angular -= angular_impulse;
-    }
-}
-*/
-
-pub fn spring_impulse(
-    time: Res<Time>,
-    mut impulses: Query<&mut ExternalImpulse>,
-    mut springs: Query<(Entity, &mut SpringSettings, &Spring)>,
-    particle: Query<springy::RapierParticleQuery>,
-) {
-    if time.delta_seconds() == 0.0 {
-        return;
-    }
-
-    let timestep = TICK_RATE;
-
-    for (spring_entity, mut spring_settings, spring) in &mut springs {
-        let particle_entity = spring.containing;
-        let particle_a = particle.get(spring_entity).unwrap();
-        let particle_b = particle.get(particle_entity).unwrap();
-
-        if particle_entity == spring_entity {
-            continue;
-        }
-
-        let translate_particle_a = particle_a.translation();
-        let angular_particle_a = particle_a.angular();
-        let translate_particle_b = particle_b.translation();
-        let angular_particle_b = particle_b.angular();
-
-        let instant = translate_particle_a.instant(&translate_particle_b);
-        let impulse = spring_settings.0.impulse(timestep, instant);
-
-        let angular_instant = angular_particle_a.instant(&angular_particle_b);
-        let angular_impulse = spring_settings.0.impulse(timestep, angular_instant);
-        //let angular_impulse = angular_impulse * 0.001;
-        let [mut spring_impulse, mut particle_impulse] = impulses
-            .get_many_mut([spring_entity, particle_entity])
-            .unwrap();
-
-        spring_impulse.impulse += impulse;
-        spring_impulse.torque_impulse += angular_impulse;
-        particle_impulse.impulse -= impulse;
-        particle_impulse.torque_impulse -= angular_impulse;
-    }
-}
-
 /*
 pub fn setup_physics(mut commands: Commands) {
     /*
@@ -246,19 +105,14 @@ pub fn setup_physics(mut commands: Commands) {
             ..default()
         })
         .insert(TransformBundle::from(Transform::from_xyz(50.0, 50.0, 0.0)))
-        .insert(Spring { containing: cube_1 })
-        .insert(SpringSettings(springy::Spring {
-            strength: 1.0,
-            damp_ratio: 1.0,
-        }))
+        .insert(SpringBundle::new(slotted_cube, Spring::new(1.0, 1.0)).with_name("Cube Slot"))
         .insert((
             //RigidBody::Dynamic,
             Velocity::default(),
             ExternalImpulse::default(),
             ReadMassProperties::default(),
             //Collider::cuboid(size, size),
-        ))
-        .insert(Name::new("Cube Slot"));
+        ));
 
     commands
         .spawn(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 0.0)))
@@ -275,15 +129,6 @@ pub fn setup_physics(mut commands: Commands) {
 
 pub fn setup_translation(mut commands: Commands) {
     let size = 20.0;
-    let sprite = Sprite {
-        color: css::BLUE.into(),
-        flip_x: false,
-        flip_y: false,
-        custom_size: Some(Vec2::new(size, size)),
-        rect: None,
-        anchor: Default::default(),
-    };
-
     let slot = Sprite {
         color: css::RED.into(),
         flip_x: false,
@@ -328,7 +173,7 @@ pub fn setup_translation(mut commands: Commands) {
             .id();
 
         let damp_ratio = iteration as f32 / iterations as f32;
-        let critical_slot = commands
+        commands
             .spawn(SpriteBundle {
                 sprite: slot.clone(),
                 ..default()
@@ -336,38 +181,22 @@ pub fn setup_translation(mut commands: Commands) {
             .insert(TransformBundle::from(Transform::from_xyz(
                 100.0, height, 0.0,
             )))
-            .insert(Spring {
-                containing: damped_cube,
-            })
-            .insert(SpringSettings(springy::Spring {
-                strength: 0.05,
-                damp_ratio: damp_ratio,
-            }))
+            .insert(
+                SpringBundle::new(damped_cube, Spring::new(0.05, damp_ratio))
+                    .with_name(format!("Slot {} (ratio {})", iteration, damp_ratio)),
+            )
             .insert((
                 //RigidBody::Dynamic,
                 Velocity::default(),
                 ExternalImpulse::default(),
                 ReadMassProperties::default(),
                 //Collider::cuboid(size, size),
-            ))
-            .insert(Name::new(format!(
-                "Slot {} (ratio {})",
-                iteration, damp_ratio
-            )));
+            ));
     }
 }
 
 pub fn setup_rotation(mut commands: Commands) {
     let size = 20.0;
-    let sprite = Sprite {
-        color: css::BLUE.into(),
-        flip_x: false,
-        flip_y: false,
-        custom_size: Some(Vec2::new(size, size)),
-        rect: None,
-        anchor: Default::default(),
-    };
-
     let slot = Sprite {
         color: css::RED.into(),
         flip_x: false,
@@ -414,7 +243,7 @@ pub fn setup_rotation(mut commands: Commands) {
             .id();
 
         let damp_ratio = iteration as f32 / iterations as f32;
-        let critical_slot = commands
+        commands
             .spawn(SpriteBundle {
                 sprite: slot.clone(),
                 ..default()
@@ -422,23 +251,16 @@ pub fn setup_rotation(mut commands: Commands) {
             .insert(TransformBundle::from(Transform::from_xyz(
                 -200.0, height, 0.0,
             )))
-            .insert(Spring {
-                containing: damped_cube,
-            })
-            .insert(SpringSettings(springy::Spring {
-                strength: 0.7,
-                damp_ratio: damp_ratio,
-            }))
+            .insert(
+                SpringBundle::new(damped_cube, Spring::new(0.7, damp_ratio))
+                    .with_name(format!("Rot Slot {} (ratio {})", iteration, damp_ratio)),
+            )
             .insert((
                 //RigidBody::Dynamic,
                 Velocity::default(),
                 ExternalImpulse::default(),
                 ReadMassProperties::default(),
                 //Collider::cuboid(size, size),
-            ))
-            .insert(Name::new(format!(
-                "Rot Slot {} (ratio {})",
-                iteration, damp_ratio
-            )));
+            ));
     }
 }
@@ -0,0 +1,75 @@
+//! Stress test of the solver with 10k springs, comparing the island solver
+//! against the [`SpringGraphPlugin`].
+//!
+//! `cargo run --release --example spring_graph` for the island solver,
+//! `cargo run --release --example spring_graph -- --graph` for the graph.
+//! Add `--features simd` to solve the graph with SIMD batches.
+//!
+//! See `benches/spring_graph.rs` for measuring the two against each other.
+use bevy::{app::AppExit, diagnostic::LogDiagnosticsPlugin, prelude::*};
+use springy::prelude::*;
+
+const CHAINS: usize = 100;
+const LINKS: usize = 100;
+const FRAMES: u32 = 600;
+
+fn main() {
+    let graph = std::env::args().any(|arg| arg == "--graph");
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, LogDiagnosticsPlugin::default()))
+        .add_plugins(SpringPlugin::new(Update).with_solver_iterations(4))
+        .add_plugins(SpringDiagnosticsPlugin)
+        .add_systems(Startup, setup_chains)
+        .add_systems(Last, exit_after_frames);
+
+    if graph {
+        app.add_plugins(SpringGraphPlugin);
+    }
+
+    info!(
+        "solving {} springs with the {} solver",
+        CHAINS * LINKS,
+        if graph { "graph" } else { "island" }
+    );
+    app.run();
+}
+
+fn setup_chains(mut commands: Commands) {
+    for chain in 0..CHAINS {
+        let x = chain as f32;
+        let mut previous = commands
+            .spawn((
+                TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                ParticleBundle {
+                    inertia: Inertia::INFINITY,
+                    ..default()
+                },
+            ))
+            .id();
+
+        for link in 1..=LINKS {
+            previous = commands
+                .spawn((
+                    TransformBundle::from_transform(Transform::from_xyz(
+                        x,
+                        -(link as f32) * 0.1,
+                        0.0,
+                    )),
+                    ParticleBundle::default(),
+                    SpringBundle {
+                        joint: SpringJoint::linear(previous).with_rest_length(0.1),
+                        ..SpringBundle::new(previous, Spring::snappy())
+                    },
+                ))
+                .id();
+        }
+    }
+}
+
+fn exit_after_frames(mut frames: Local<u32>, mut exit: EventWriter<AppExit>) {
+    *frames += 1;
+    if *frames >= FRAMES {
+        exit.send(AppExit::Success);
+    }
+}
@@ -0,0 +1,72 @@
+//! Traits the spring solver reads particles through, so it can drive
+//! components of other crates or of the game instead of the ones in
+//! [`integration`](crate::integration).
+//!
+//! Solve with [`spring_impulse`](crate::joint::spring_impulse) over the
+//! components of choice, e.g. with `bevy_rapier3d`:
+//!
+//! ```ignore
+//! app.add_systems(
+//!     SpringSchedule,
+//!     spring_impulse::<Velocity, ReadMassProperties, ExternalImpulse>.in_set(SpringSet::Solve),
+//! );
+//! ```
+//!
+//! The fast path of [`SpringConfig::skip_unchanged`](crate::plugin::SpringConfig)
+//! only sees changes of the crate's own [`Velocity`], disable it when the
+//! velocity comes from another component.
+use bevy::{
+    ecs::query::{QueryItem, ReadOnlyQueryData},
+    prelude::*,
+};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+
+/// Component holding the velocity of a particle.
+pub trait ReadVelocity: Component {
+    fn velocity(&self) -> Velocity;
+}
+
+/// Component holding the mass and angular inertia of a particle, an inertia
+/// of 0 or infinity is immovable.
+///
+/// The inertia is read from the `Data` of the particle, so it can depend on
+/// other components of the particle as well, e.g. the body type.
+pub trait ReadInertia: Component {
+    type Data: ReadOnlyQueryData;
+
+    fn inertia(data: QueryItem<'_, Self::Data>) -> Inertia;
+}
+
+/// Component the impulses of the springs accumulate into before they are
+/// applied to the velocity.
+pub trait AccumulateImpulse: Component {
+    /// Impulse accumulated so far this step.
+    fn impulse(&self) -> Impulse;
+    fn accumulate(&mut self, linear: Vec3, angular: Vec3);
+}
+
+impl ReadVelocity for Velocity {
+    fn velocity(&self) -> Velocity {
+        *self
+    }
+}
+
+impl ReadInertia for Inertia {
+    type Data = &'static Inertia;
+
+    fn inertia(inertia: QueryItem<'_, Self::Data>) -> Inertia {
+        *inertia
+    }
+}
+
+impl AccumulateImpulse for Impulse {
+    fn impulse(&self) -> Impulse {
+        *self
+    }
+
+    fn accumulate(&mut self, linear: Vec3, angular: Vec3) {
+        self.linear += linear;
+        self.angular += angular;
+    }
+}
@@ -0,0 +1,179 @@
+//! Exact solutions of the continuous damped oscillator a [`Spring`] stands
+//! in for, to validate tuning and check the solver against.
+//!
+//! A spring with `strength` stepped every `timestep` seconds behaves like a
+//! continuous spring with a natural frequency of `sqrt(strength) / timestep`
+//! and the same damping ratio. The solver clamps the damping per step to at
+//! most the whole velocity, so very over-damped springs drift from the
+//! analytic solution.
+
+use crate::*;
+
+/// State of an oscillator at one point in time.
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct OscillatorState {
+    pub displacement: f32,
+    pub velocity: f32,
+}
+
+/// Natural frequency in radians per second of a spring with `strength`
+/// stepped every `timestep` seconds.
+pub fn natural_frequency(strength: f32, timestep: f32) -> f32 {
+    strength.clamp(0.0, 1.0).sqrt() / timestep
+}
+
+/// Exact displacement and velocity after `t` seconds of a spring with
+/// `strength` and `damp_ratio` stepped every `timestep` seconds, starting at
+/// displacement `x0` with velocity `v0`.
+pub fn damped_oscillator(
+    x0: f32,
+    v0: f32,
+    strength: f32,
+    damp_ratio: f32,
+    timestep: f32,
+    t: f32,
+) -> OscillatorState {
+    let omega = natural_frequency(strength, timestep);
+    let zeta = damp_ratio.max(0.0);
+
+    if omega == 0.0 {
+        return OscillatorState {
+            displacement: x0 + v0 * t,
+            velocity: v0,
+        };
+    }
+
+    if (zeta - 1.0).abs() < 1e-4 {
+        // Critically damped.
+        let decay = (-omega * t).exp();
+        let b = v0 + omega * x0;
+        return OscillatorState {
+            displacement: (x0 + b * t) * decay,
+            velocity: (v0 - omega * b * t) * decay,
+        };
+    }
+
+    if zeta < 1.0 {
+        // Under-damped.
+        let damped = omega * (1.0 - zeta * zeta).sqrt();
+        let decay = (-zeta * omega * t).exp();
+        let (sin, cos) = (damped * t).sin_cos();
+        return OscillatorState {
+            displacement: decay * (x0 * cos + (v0 + zeta * omega * x0) / damped * sin),
+            velocity: decay * (v0 * cos - (omega * omega * x0 + zeta * omega * v0) / damped * sin),
+        };
+    }
+
+    // Over-damped.
+    let root = (zeta * zeta - 1.0).sqrt();
+    let (r1, r2) = (-omega * (zeta - root), -omega * (zeta + root));
+    let a = (v0 - r2 * x0) / (r1 - r2);
+    let b = x0 - a;
+    let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+    OscillatorState {
+        displacement: a * e1 + b * e2,
+        velocity: r1 * a * e1 + r2 * b * e2,
+    }
+}
+
+/// [`damped_oscillator`] for the settings of a [`Spring`].
+pub fn spring_oscillator(
+    spring: &Spring,
+    x0: f32,
+    v0: f32,
+    timestep: f32,
+    t: f32,
+) -> OscillatorState {
+    damped_oscillator(x0, v0, spring.strength(), spring.damp_ratio(), timestep, t)
+}
+
+/// Step a particle on `spring` towards a fixed anchor with [`Spring::simulate`],
+/// returning the state after each of the `steps`.
+pub fn simulate(
+    spring: &Spring,
+    x0: f32,
+    v0: f32,
+    timestep: f32,
+    steps: usize,
+) -> Vec<OscillatorState> {
+    let particle = Particle1 {
+        inertia: 1.0,
+        position: x0,
+        velocity: v0,
+    };
+    let anchor = Particle1 {
+        inertia: f32::INFINITY,
+        position: 0.0,
+        velocity: 0.0,
+    };
+
+    // Symplectic Euler moves by the new velocity, so it follows from the
+    // change in displacement.
+    let mut previous = x0;
+    spring
+        .simulate(particle.instant(&anchor), timestep, steps)
+        .into_iter()
+        .map(|displacement| {
+            let velocity = (displacement - previous) / timestep;
+            previous = displacement;
+            OscillatorState {
+                displacement,
+                velocity,
+            }
+        })
+        .collect()
+}
+
+/// Largest difference in displacement between [`simulate`] and the analytic
+/// solution over `steps`, e.g. to assert in a test that a change to the
+/// solver keeps it within tolerance.
+pub fn max_displacement_error(
+    spring: &Spring,
+    x0: f32,
+    v0: f32,
+    timestep: f32,
+    steps: usize,
+) -> f32 {
+    simulate(spring, x0, v0, timestep, steps)
+        .iter()
+        .enumerate()
+        .map(|(step, simulated)| {
+            let t = (step + 1) as f32 * timestep;
+            let exact = spring_oscillator(spring, x0, v0, timestep, t);
+            (simulated.displacement - exact.displacement).abs()
+        })
+        .fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMESTEP: f32 = 1.0 / 60.0;
+    const STEPS: usize = 600;
+
+    // Symplectic Euler lags the exact solution the most in the first
+    // oscillation, so stronger springs get a looser tolerance.
+    fn assert_tracks_analytic(spring: Spring, tolerance: f32) {
+        let error = max_displacement_error(&spring, 1.0, 0.0, TIMESTEP, STEPS);
+        assert!(
+            error <= tolerance,
+            "{spring:?} drifted {error} from the analytic solution, more than {tolerance}"
+        );
+    }
+
+    #[test]
+    fn under_damped() {
+        assert_tracks_analytic(Spring::bouncy(), 0.2);
+    }
+
+    #[test]
+    fn critically_damped() {
+        assert_tracks_analytic(Spring::loose_follow(), 0.06);
+    }
+
+    #[test]
+    fn over_damped() {
+        assert_tracks_analytic(Spring::sluggish(), 0.03);
+    }
+}
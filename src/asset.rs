@@ -0,0 +1,128 @@
+//! Spring settings authored in `.spring.ron` asset files.
+//!
+//! Enable bevy's `file_watcher` feature to hot reload them while tuning.
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    reflect::{Reflect, TypePath},
+};
+
+use crate::joint::SpringPair;
+use crate::*;
+
+/// Spring settings loaded from a `.spring.ron` file, e.g.
+///
+/// ```ron
+/// (
+///     linear: (strength: 0.2, damp_ratio: 0.7),
+///     angular: Some((strength: 0.1, damp_ratio: 1.0)),
+/// )
+/// ```
+#[derive(Debug, Copy, Clone, Asset, TypePath, serde::Serialize, serde::Deserialize)]
+pub struct SpringAsset {
+    pub linear: Spring,
+    /// Angular spring, the linear spring is used for both when `None`.
+    #[serde(default)]
+    pub angular: Option<Spring>,
+}
+
+impl SpringAsset {
+    pub fn pair(&self) -> SpringPair {
+        SpringPair::new(self.linear, self.angular.unwrap_or(self.linear))
+    }
+}
+
+/// Inserts the [`SpringPair`] of the referenced [`SpringAsset`] on this entity,
+/// and again whenever the asset is reloaded.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct SpringSettingsHandle(pub Handle<SpringAsset>);
+
+#[derive(Debug)]
+pub enum SpringAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for SpringAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "could not read spring asset: {error}"),
+            Self::Ron(error) => write!(f, "could not parse spring asset: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SpringAssetError {}
+
+impl From<std::io::Error> for SpringAssetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for SpringAssetError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+#[derive(Default)]
+pub struct SpringAssetLoader;
+
+impl AssetLoader for SpringAssetLoader {
+    type Asset = SpringAsset;
+    type Settings = ();
+    type Error = SpringAssetError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<SpringAsset, SpringAssetError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["spring.ron"]
+    }
+}
+
+pub struct SpringAssetPlugin;
+
+impl Plugin for SpringAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SpringAsset>()
+            .init_asset_loader::<SpringAssetLoader>()
+            .register_type::<SpringSettingsHandle>()
+            .add_systems(Update, apply_spring_assets);
+    }
+}
+
+pub fn apply_spring_assets(
+    mut commands: Commands,
+    assets: Res<Assets<SpringAsset>>,
+    mut asset_events: EventReader<AssetEvent<SpringAsset>>,
+    handles: Query<(Entity, Ref<SpringSettingsHandle>)>,
+) {
+    let modified = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, handle) in &handles {
+        if !handle.is_changed() && !modified.contains(&handle.0.id()) {
+            continue;
+        }
+
+        if let Some(asset) = assets.get(&handle.0) {
+            commands.entity(entity).insert(asset.pair());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use wide::CmpGt;
+
+use crate::*;
+
+/// Number of springs in a [`SpringBatch`].
+pub const LANES: usize = 4;
+
+/// Up to [`LANES`] springs and their instants in a struct of arrays layout,
+/// so their impulses can be computed at once.
+///
+/// With the `simd` feature the impulses are computed with `wide` vectors,
+/// otherwise every lane goes through [`Spring::impulse`]. Unused lanes hold
+/// a default spring and produce no impulse.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct SpringBatch {
+    pub springs: [Spring; LANES],
+    pub reduced_inertia: [[f32; LANES]; 3],
+    pub displacement: [[f32; LANES]; 3],
+    pub velocity: [[f32; LANES]; 3],
+}
+
+impl SpringBatch {
+    pub fn set(&mut self, lane: usize, spring: Spring, instant: &SpringInstant<Vec3>) {
+        self.springs[lane] = spring;
+        for axis in 0..3 {
+            self.reduced_inertia[axis][lane] = instant.reduced_inertia[axis];
+            self.displacement[axis][lane] = instant.displacement[axis];
+            self.velocity[axis][lane] = instant.velocity[axis];
+        }
+    }
+
+    pub fn instant(&self, lane: usize) -> SpringInstant<Vec3> {
+        let vector =
+            |lanes: &[[f32; LANES]; 3]| Vec3::new(lanes[0][lane], lanes[1][lane], lanes[2][lane]);
+        SpringInstant {
+            reduced_inertia: vector(&self.reduced_inertia),
+            displacement: vector(&self.displacement),
+            velocity: vector(&self.velocity),
+        }
+    }
+
+    /// Impulse of every lane, like [`Spring::impulse`].
+    #[cfg(not(feature = "simd"))]
+    pub fn impulses(&self, timestep: f32) -> [Vec3; LANES] {
+        self.scalar_impulses(timestep)
+    }
+
+    /// Impulse of every lane, like [`Spring::impulse`].
+    #[cfg(feature = "simd")]
+    pub fn impulses(&self, timestep: f32) -> [Vec3; LANES] {
+        self.wide_impulses(timestep)
+    }
+
+    /// Impulse of every lane through [`Spring::impulse`] one at a time.
+    pub fn scalar_impulses(&self, timestep: f32) -> [Vec3; LANES] {
+        std::array::from_fn(|lane| self.springs[lane].impulse(timestep, self.instant(lane)))
+    }
+
+    /// Impulse of every lane computed at once with `wide` vectors.
+    #[cfg(feature = "simd")]
+    pub fn wide_impulses(&self, timestep: f32) -> [Vec3; LANES] {
+        use wide::f32x4;
+
+        let lanes =
+            |value: fn(&Spring) -> f32| f32x4::from(self.springs.map(|spring| value(&spring)));
+        let strength = lanes(Spring::strength) * f32x4::splat(1.0 / timestep);
+        let damping = lanes(Spring::damping);
+        let axial = lanes(|spring| match spring.damp_mode {
+            DampMode::Full => 0.0,
+            DampMode::Axial => 1.0,
+        });
+
+        let vector = |lanes: &[[f32; LANES]; 3]| lanes.map(f32x4::from);
+        let [dx, dy, dz] = vector(&self.displacement);
+        let [vx, vy, vz] = vector(&self.velocity);
+        let [rx, ry, rz] = vector(&self.reduced_inertia);
+
+        let length = (dx * dx + dy * dy + dz * dz).sqrt();
+        let recip = f32x4::ONE / length;
+        let valid = recip.is_finite() & recip.cmp_gt(f32x4::ZERO);
+        let recip = valid.blend(recip, f32x4::ZERO);
+        let (ux, uy, uz) = (dx * recip, dy * recip, dz * recip);
+
+        // Axial damping only keeps the velocity along the spring.
+        let along = vx * ux + vy * uy + vz * uz;
+        let vx = vx + axial * (ux * along - vx);
+        let vy = vy + axial * (uy * along - vy);
+        let vz = vz + axial * (uz * along - vz);
+
+        let x = -(ux * length * rx * strength + vx * rx * damping);
+        let y = -(uy * length * ry * strength + vy * ry * damping);
+        let z = -(uz * length * rz * strength + vz * rz * damping);
+
+        let (x, y, z) = (x.to_array(), y.to_array(), z.to_array());
+        std::array::from_fn(|lane| Vec3::new(x[lane], y[lane], z[lane]))
+    }
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_matches_scalar() {
+        let springs = [
+            Spring::snappy(),
+            Spring::bouncy().with_damp_mode(DampMode::Axial),
+            Spring::stiff(),
+            Spring::sluggish().with_damp_mode(DampMode::Axial),
+        ];
+        let instants = [
+            (Vec3::new(0.3, -1.2, 0.5), Vec3::new(2.0, 0.1, -0.7)),
+            (Vec3::new(-0.8, 0.4, 1.1), Vec3::new(0.5, -3.0, 0.2)),
+            // Particles on top of each other have no direction to push along.
+            (Vec3::ZERO, Vec3::new(1.0, 1.0, -1.0)),
+            (Vec3::new(5.0, 0.0, -2.0), Vec3::ZERO),
+        ];
+
+        let mut batch = SpringBatch::default();
+        for (lane, (displacement, velocity)) in instants.into_iter().enumerate() {
+            let instant = SpringInstant {
+                reduced_inertia: Vec3::new(0.5, 1.0, 2.0),
+                displacement,
+                velocity,
+            };
+            batch.set(lane, springs[lane], &instant);
+        }
+
+        let timestep = 1.0 / 60.0;
+        let scalar = batch.scalar_impulses(timestep);
+        let wide = batch.wide_impulses(timestep);
+        for lane in 0..LANES {
+            assert!(
+                wide[lane].abs_diff_eq(scalar[lane], 1e-4 * scalar[lane].length().max(1.0)),
+                "lane {lane}: wide {} != scalar {}",
+                wide[lane],
+                scalar[lane],
+            );
+        }
+    }
+
+    #[test]
+    fn unused_lanes_are_still() {
+        let mut batch = SpringBatch::default();
+        let instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: Vec3::X,
+            velocity: Vec3::Y,
+        };
+        batch.set(0, Spring::snappy(), &instant);
+
+        let wide = batch.wide_impulses(1.0 / 60.0);
+        assert_eq!(wide[1..], [Vec3::ZERO; LANES - 1]);
+        assert_eq!(wide[1..], batch.scalar_impulses(1.0 / 60.0)[1..]);
+    }
+}
@@ -0,0 +1,271 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    render::{mesh::Indices, render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
+    sprite::Mesh2dHandle,
+    transform::TransformSystem,
+};
+
+use crate::integration::{Impulse, Inertia, ParticleBundle};
+use crate::joint::{SpringBundle, SpringJoint};
+use crate::plugin::{SpringSchedule, SpringSet};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Builds a 2D soft body out of a ring of particles connected by edge
+/// springs, kept inflated by a [`BlobPressure`].
+///
+/// Add the [`SoftBlobPlugin`] for the pressure and the mesh of the blob, and a
+/// `Handle<ColorMaterial>` to the blob entity to render it.
+#[derive(Debug, Copy, Clone)]
+pub struct SoftBlob2 {
+    pub center: Vec2,
+    pub radius: f32,
+    /// Number of particles around the ring.
+    pub segments: usize,
+    /// Mass of every particle.
+    pub particle_mass: f32,
+    pub edge_spring: Spring,
+    /// How strongly the blob pushes back towards its rest area, see [`BlobPressure`].
+    pub pressure: f32,
+}
+
+impl SoftBlob2 {
+    pub fn new(center: Vec2, radius: f32, segments: usize) -> Self {
+        Self {
+            center,
+            radius,
+            segments: segments.max(3),
+            particle_mass: 1.0,
+            edge_spring: Spring::new(0.5, 0.5),
+            pressure: 10.0,
+        }
+    }
+
+    pub fn with_particle_mass(mut self, particle_mass: f32) -> Self {
+        self.particle_mass = particle_mass;
+        self
+    }
+
+    pub fn with_edge_spring(mut self, edge_spring: Spring) -> Self {
+        self.edge_spring = edge_spring;
+        self
+    }
+
+    pub fn with_pressure(mut self, pressure: f32) -> Self {
+        self.pressure = pressure;
+        self
+    }
+
+    /// Spawn the particles and the blob entity holding the [`BlobPressure`],
+    /// returning the blob entity.
+    pub fn build(&self, commands: &mut Commands) -> Entity {
+        let segments = self.segments.max(3);
+        let points: Vec<Vec2> = (0..segments)
+            .map(|index| {
+                let angle = TAU * index as f32 / segments as f32;
+                self.center + Vec2::from_angle(angle) * self.radius
+            })
+            .collect();
+
+        let particles: Vec<Entity> = points
+            .iter()
+            .map(|point| {
+                commands
+                    .spawn((
+                        TransformBundle::from_transform(Transform::from_translation(
+                            point.extend(0.0),
+                        )),
+                        ParticleBundle {
+                            inertia: Inertia {
+                                linear: self.particle_mass,
+                                angular: Vec3::splat(f32::INFINITY),
+                            },
+                            ..default()
+                        },
+                    ))
+                    .id()
+            })
+            .collect();
+
+        // Counter-clockwise, so the outward normal of an edge is its direction
+        // rotated clockwise.
+        for (index, particle) in particles.iter().enumerate() {
+            let next = (index + 1) % segments;
+            let joint = SpringJoint::linear(particles[next])
+                .with_rest_length(points[index].distance(points[next]));
+            commands.entity(*particle).insert(SpringBundle {
+                joint,
+                ..SpringBundle::new(particles[next], self.edge_spring).with_name("Blob edge")
+            });
+        }
+
+        commands
+            .spawn((
+                SpatialBundle::default(),
+                BlobPressure {
+                    rest_area: polygon_area(&points),
+                    pressure: self.pressure,
+                    particles,
+                },
+                Name::new("Soft blob"),
+            ))
+            .id()
+    }
+}
+
+/// Pressure keeping the area enclosed by a counter-clockwise ring of
+/// particles close to its rest area, pushing every edge outwards when
+/// squashed and pulling it in when inflated.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct BlobPressure {
+    pub particles: Vec<Entity>,
+    pub rest_area: f32,
+    /// Force per unit of edge length per unit of area the blob is squashed by.
+    pub pressure: f32,
+}
+
+impl MapEntities for BlobPressure {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for particle in &mut self.particles {
+            *particle = entity_mapper.map_entity(*particle);
+        }
+    }
+}
+
+/// Signed area of a polygon, positive when counter-clockwise.
+pub fn polygon_area(points: &[Vec2]) -> f32 {
+    let count = points.len();
+    0.5 * (0..count)
+        .map(|index| points[index].perp_dot(points[(index + 1) % count]))
+        .sum::<f32>()
+}
+
+pub struct SoftBlobPlugin;
+
+impl Plugin for SoftBlobPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<BlobPressure>()
+            .add_systems(SpringSchedule, blob_pressure.in_set(SpringSet::Forces))
+            .add_systems(
+                PostUpdate,
+                blob_meshes.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn blob_pressure(
+    timestep: Res<SpringTimestep>,
+    blobs: Query<&BlobPressure>,
+    transforms: Query<&Transform>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for blob in &blobs {
+        let Ok(points) = blob
+            .particles
+            .iter()
+            .map(|particle| transforms.get(*particle).map(|t| t.translation.truncate()))
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            continue;
+        };
+        if points.len() < 3 {
+            continue;
+        }
+
+        let squashed = blob.rest_area - polygon_area(&points);
+        for index in 0..points.len() {
+            let next = (index + 1) % points.len();
+            let edge = points[next] - points[index];
+            // Perpendicular with the length of the edge, so longer edges
+            // receive a proportionally larger push.
+            let outward = Vec2::new(edge.y, -edge.x);
+            let impulse = (outward * blob.pressure * squashed * timestep * 0.5).extend(0.0);
+
+            for particle in [blob.particles[index], blob.particles[next]] {
+                if let Ok(mut particle_impulse) = impulses.get_mut(particle) {
+                    particle_impulse.linear += impulse;
+                }
+            }
+        }
+    }
+}
+
+/// Fill the mesh of every blob with a triangle fan over its particles.
+pub fn blob_meshes(
+    mut commands: Commands,
+    blobs: Query<(
+        Entity,
+        &BlobPressure,
+        &GlobalTransform,
+        Option<&Mesh2dHandle>,
+    )>,
+    transforms: Query<&GlobalTransform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, blob, blob_transform, mesh) in &blobs {
+        let Ok(points) = blob
+            .particles
+            .iter()
+            .map(|particle| transforms.get(*particle).map(GlobalTransform::translation))
+            .collect::<Result<Vec<_>, _>>()
+        else {
+            continue;
+        };
+        if points.len() < 3 {
+            continue;
+        }
+
+        // The particles live in world space, the mesh in the space of the blob.
+        let inverse = blob_transform.affine().inverse();
+        let center = points.iter().sum::<Vec3>() / points.len() as f32;
+        let positions: Vec<[f32; 3]> = std::iter::once(center)
+            .chain(points.iter().copied())
+            .map(|point| inverse.transform_point3(point).to_array())
+            .collect();
+        let uvs: Vec<[f32; 2]> = std::iter::once([0.5, 0.5])
+            .chain((0..points.len()).map(|index| {
+                let angle = TAU * index as f32 / points.len() as f32;
+                (Vec2::from_angle(angle) * 0.5 + 0.5).to_array()
+            }))
+            .collect();
+        let count = points.len() as u32;
+        let indices: Vec<u32> = (0..count)
+            .flat_map(|index| [0, index + 1, (index + 1) % count + 1])
+            .collect();
+
+        let new_mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            vec![[0.0, 0.0, 1.0]; positions.len()],
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices));
+
+        match mesh.and_then(|mesh| meshes.get_mut(&mesh.0)) {
+            Some(mesh) => *mesh = new_mesh,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(Mesh2dHandle(meshes.add(new_mesh)));
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Floats this entity on water, springing its height up towards `draft`
+/// below the water surface and dragging it while submerged.
+///
+/// The strength of the spring and the drag grow with the depth until
+/// `full_depth`, so bodies settle into the surface instead of bouncing off it.
+/// Particles float with the [`SpringPlugin`](crate::SpringPlugin), rigid
+/// bodies when the buoyancy plugin of a rapier backend is added.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Buoyancy {
+    /// Height of the water surface, unless a [`WaterHeight`] resource is inserted.
+    pub surface: f32,
+    /// Depth below the surface the origin of the entity floats at.
+    pub draft: f32,
+    /// Depth below the surface at which the spring and drag are at full strength.
+    pub full_depth: f32,
+    pub spring: Spring,
+    /// Fraction of the velocity removed per second while fully submerged.
+    pub drag: f32,
+    /// How submerged the entity was at the last step, from 0 to 1.
+    pub submerged: f32,
+}
+
+impl Buoyancy {
+    pub fn new(surface: f32, spring: Spring) -> Self {
+        Self {
+            surface,
+            draft: 0.0,
+            full_depth: 1.0,
+            spring,
+            drag: 0.5,
+            submerged: 0.0,
+        }
+    }
+
+    pub fn with_draft(mut self, draft: f32) -> Self {
+        self.draft = draft;
+        self
+    }
+
+    pub fn with_full_depth(mut self, full_depth: f32) -> Self {
+        self.full_depth = full_depth;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    pub fn is_submerged(&self) -> bool {
+        self.submerged > 0.0
+    }
+
+    /// How submerged something is `depth` below the surface, from 0 to 1.
+    pub fn submersion(&self, depth: f32) -> f32 {
+        if depth <= 0.0 {
+            0.0
+        } else if self.full_depth <= 0.0 {
+            1.0
+        } else {
+            (depth / self.full_depth).min(1.0)
+        }
+    }
+
+    /// Impulse floating a particle on the water with its surface at `surface`.
+    ///
+    /// Also updates how submerged the particle is.
+    pub fn impulse(
+        &mut self,
+        timestep: f32,
+        surface: f32,
+        particle: &TranslationParticle3,
+    ) -> Vec3 {
+        self.submerged = self.submersion(surface - particle.translation.y);
+        if self.submerged == 0.0 || !particle.mass.is_normal() {
+            return Vec3::ZERO;
+        }
+
+        let height = Particle1 {
+            inertia: particle.mass,
+            position: particle.translation.y,
+            velocity: particle.velocity.y,
+        };
+        let rest = Particle1 {
+            inertia: f32::INFINITY,
+            position: surface - self.draft,
+            velocity: 0.0,
+        };
+        let spring = self.spring.scaled(self.submerged);
+        // Water only ever pushes up.
+        let lift = spring.impulse(timestep, height.instant(&rest)).max(0.0);
+
+        let drag = (self.drag * self.submerged * timestep).clamp(0.0, 1.0);
+        Vec3::Y * lift - particle.velocity * particle.mass * drag
+    }
+}
+
+/// Samples the height of the water surface at a point, e.g. from a wave
+/// function, for every [`Buoyancy`] instead of its flat `surface`.
+#[derive(Resource)]
+pub struct WaterHeight(pub Box<dyn Fn(Vec3) -> f32 + Send + Sync>);
+
+impl WaterHeight {
+    pub fn new(height: impl Fn(Vec3) -> f32 + Send + Sync + 'static) -> Self {
+        Self(Box::new(height))
+    }
+
+    pub fn sample(&self, point: Vec3) -> f32 {
+        (self.0)(point)
+    }
+}
+
+/// Height of the water surface above `point` for a [`Buoyancy`].
+pub fn water_surface(buoyancy: &Buoyancy, water: Option<&WaterHeight>, point: Vec3) -> f32 {
+    water.map_or(buoyancy.surface, |water| water.sample(point))
+}
+
+pub fn buoyancy(
+    timestep: Res<SpringTimestep>,
+    water: Option<Res<WaterHeight>>,
+    mut particles: Query<(&mut Buoyancy, &Transform, &Velocity, &Inertia, &mut Impulse)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut buoyancy, transform, velocity, inertia, mut impulse) in &mut particles {
+        let surface = water_surface(&buoyancy, water.as_deref(), transform.translation);
+        let particle = TranslationParticle3 {
+            mass: inertia.linear,
+            translation: transform.translation,
+            velocity: velocity.linear,
+        };
+        impulse.linear += buoyancy.impulse(timestep, surface, &particle);
+    }
+}
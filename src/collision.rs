@@ -0,0 +1,124 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, PreviousTranslation, Velocity};
+use crate::kinematic::Kinematic;
+use crate::timestep::SpringTimestep;
+
+/// Shape particles with a [`CollisionRadius`] are pushed out of, in the local
+/// space of the entity without its scale.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColliderShape {
+    Sphere {
+        radius: f32,
+    },
+    /// Capsule along the local Y axis.
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+}
+
+/// Simple collider for the particles of hair, tails and ropes, so chains
+/// don't sink through the character.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringCollider(pub ColliderShape);
+
+impl SpringCollider {
+    pub fn sphere(radius: f32) -> Self {
+        Self(ColliderShape::Sphere { radius })
+    }
+
+    pub fn capsule(half_height: f32, radius: f32) -> Self {
+        Self(ColliderShape::Capsule {
+            half_height,
+            radius,
+        })
+    }
+
+    /// Direction and distance to push a sphere at `point` with `radius` out of
+    /// the collider, `None` when they don't overlap.
+    pub fn penetration(
+        &self,
+        transform: &GlobalTransform,
+        point: Vec3,
+        radius: f32,
+    ) -> Option<(Vec3, f32)> {
+        let (_, rotation, center) = transform.to_scale_rotation_translation();
+        let (closest, shape_radius) = match self.0 {
+            ColliderShape::Sphere { radius } => (center, radius),
+            ColliderShape::Capsule {
+                half_height,
+                radius,
+            } => {
+                let axis = rotation * Vec3::Y * half_height;
+                let (a, b) = (center - axis, center + axis);
+                let t = (point - a).dot(b - a) / (b - a).length_squared().max(f32::EPSILON);
+                (a + (b - a) * t.clamp(0.0, 1.0), radius)
+            }
+        };
+
+        let offset = point - closest;
+        let distance = offset.length();
+        let depth = shape_radius + radius - distance;
+        if depth <= 0.0 {
+            return None;
+        }
+
+        let normal = offset.try_normalize().unwrap_or(rotation * Vec3::Y);
+        Some((normal, depth))
+    }
+}
+
+/// Radius of a particle colliding with [`SpringCollider`]s, particles without
+/// one pass through them.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct CollisionRadius(pub f32);
+
+/// Push particles out of colliders and remove the velocity into them, before
+/// the accumulated impulses are integrated.
+pub fn collide_particles(
+    timestep: Res<SpringTimestep>,
+    colliders: Query<(&GlobalTransform, &SpringCollider)>,
+    mut particles: Query<(
+        &mut Transform,
+        &Velocity,
+        &mut Impulse,
+        &Inertia,
+        &CollisionRadius,
+        Option<&mut PreviousTranslation>,
+    )>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (mut transform, velocity, mut impulse, inertia, radius, mut previous) in &mut particles {
+        let inverse_mass = inertia.linear.inverse();
+        if inverse_mass == 0.0 {
+            continue;
+        }
+
+        for (collider_transform, collider) in &colliders {
+            let Some((normal, depth)) =
+                collider.penetration(collider_transform, transform.translation, radius.0)
+            else {
+                continue;
+            };
+
+            transform.translation += normal * depth;
+            if let Some(previous) = previous.as_mut().and_then(|previous| previous.0.as_mut()) {
+                *previous += normal * depth;
+            }
+
+            let normal_velocity = (velocity.linear + impulse.linear * inverse_mass).dot(normal);
+            if normal_velocity < 0.0 {
+                impulse.linear -= normal * normal_velocity * inertia.linear;
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use bevy::{
+    ecs::{system::EntityCommands, world::Command},
+    prelude::*,
+};
+
+use crate::integration::{Inertia, ParticleBundle};
+use crate::joint::{SpringJoint, SpringState};
+use crate::*;
+
+/// Attach a spring from `entity` to `containing`, adding the
+/// [`ParticleBundle`] to either particle that isn't one yet.
+///
+/// Particles missing an [`Inertia`] get the default one, insert
+/// [`Inertia::INFINITY`] beforehand for a static anchor.
+pub struct InsertSpring {
+    pub entity: Entity,
+    pub joint: SpringJoint,
+    pub spring: Spring,
+}
+
+impl Command for InsertSpring {
+    fn apply(self, world: &mut World) {
+        let particles = [self.entity, self.joint.containing];
+        // Check both before touching either, so a missing particle doesn't
+        // leave the other half set up.
+        if let Some(missing) = particles
+            .into_iter()
+            .find(|particle| world.get_entity(*particle).is_none())
+        {
+            warn!("cannot attach a spring to the missing particle {missing:?}");
+            return;
+        }
+
+        for particle in particles {
+            let mut particle = world.entity_mut(particle);
+            if !particle.contains::<Inertia>() {
+                particle.insert(ParticleBundle::default());
+            }
+        }
+
+        // Same as a `SpringBundle`, without overwriting the name of an
+        // existing particle.
+        let mut entity = world.entity_mut(self.entity);
+        entity.insert((self.joint, self.spring, SpringState::default()));
+        if !entity.contains::<Name>() {
+            entity.insert(Name::new("Spring"));
+        }
+    }
+}
+
+pub trait SpringCommandsExt {
+    /// Spring `a` to `b`, springs live on the first of their particles so the
+    /// returned commands are for `a`.
+    fn spawn_spring(&mut self, a: Entity, b: Entity, spring: Spring) -> EntityCommands<'_>;
+}
+
+impl SpringCommandsExt for Commands<'_, '_> {
+    fn spawn_spring(&mut self, a: Entity, b: Entity, spring: Spring) -> EntityCommands<'_> {
+        self.add(InsertSpring {
+            entity: a,
+            joint: SpringJoint::new(b),
+            spring,
+        });
+        self.entity(a)
+    }
+}
+
+pub trait SpringEntityCommandsExt {
+    /// Spring this entity to `target`.
+    fn spring_to(&mut self, target: Entity, spring: Spring) -> &mut Self;
+
+    /// Spring this entity to the `containing` entity of a configured joint.
+    fn spring_joint(&mut self, joint: SpringJoint, spring: Spring) -> &mut Self;
+}
+
+impl SpringEntityCommandsExt for EntityCommands<'_> {
+    fn spring_to(&mut self, target: Entity, spring: Spring) -> &mut Self {
+        self.spring_joint(SpringJoint::new(target), spring)
+    }
+
+    fn spring_joint(&mut self, joint: SpringJoint, spring: Spring) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(InsertSpring {
+            entity,
+            joint,
+            spring,
+        });
+        self
+    }
+}
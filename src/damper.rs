@@ -0,0 +1,155 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::dangling::SpringLink;
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Velocity-only joint between this entity and the `containing` entity,
+/// removing their relative velocity without pulling them together.
+///
+/// For drag links, dashpots and loosely syncing a character with a moving platform.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Damper {
+    pub containing: Entity,
+    /// Disabled dampers are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Fraction of the relative linear velocity removed each step, from 0 to 1.
+    pub linear: f32,
+    /// Fraction of the relative angular velocity removed each step, from 0 to 1.
+    pub angular: f32,
+    /// Damp the linear velocity only along the line between the particles,
+    /// like a dashpot, or in every direction.
+    pub damp_mode: DampMode,
+}
+
+impl MapEntities for Damper {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringLink for Damper {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Damper {
+    pub fn new(containing: Entity, linear: f32, angular: f32) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            linear,
+            angular,
+            damp_mode: DampMode::Full,
+        }
+    }
+
+    /// Dashpot only resisting the particles moving towards or away from each other.
+    pub fn dashpot(containing: Entity, linear: f32) -> Self {
+        Self {
+            damp_mode: DampMode::Axial,
+            ..Self::new(containing, linear, 0.0)
+        }
+    }
+
+    pub fn with_damp_mode(mut self, damp_mode: DampMode) -> Self {
+        self.damp_mode = damp_mode;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Linear impulse applied to this entity, the `containing` entity receives the opposite.
+    pub fn linear_impulse(&self, instant: SpringInstant<Vec3>) -> Vec3 {
+        let instant = match self.damp_mode {
+            DampMode::Full => instant,
+            DampMode::Axial => {
+                let axis = instant.displacement.normalize_or_zero();
+                SpringInstant {
+                    velocity: axis * instant.velocity.dot(axis),
+                    ..instant
+                }
+            }
+        };
+        instant.damping_impulse(self.linear)
+    }
+
+    /// Angular impulse applied to this entity, the `containing` entity receives the opposite.
+    pub fn angular_impulse(&self, instant: SpringInstant<Vec3>) -> Vec3 {
+        instant.damping_impulse(self.angular)
+    }
+}
+
+pub fn damper_impulse(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    dampers: Query<(Entity, &Damper, Option<&SpringGroup>)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (entity, damper, group) in &dampers {
+        if !damper.enabled || !groups.is_active(group) || damper.containing == entity {
+            continue;
+        }
+
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(entity), particles.get(damper.containing))
+        else {
+            continue;
+        };
+        let Ok([mut impulse_a, mut impulse_b]) = impulses.get_many_mut([entity, damper.containing])
+        else {
+            continue;
+        };
+
+        // Include the impulses of the springs solved before this one.
+        let velocity_a = velocity_a.with_impulse(&impulse_a, inertia_a);
+        let velocity_b = velocity_b.with_impulse(&impulse_b, inertia_b);
+
+        let linear = TranslationParticle3 {
+            mass: inertia_a.linear,
+            translation: transform_a.translation,
+            velocity: velocity_a.linear,
+        }
+        .instant(&TranslationParticle3 {
+            mass: inertia_b.linear,
+            translation: transform_b.translation,
+            velocity: velocity_b.linear,
+        });
+        let angular = SpringInstant {
+            reduced_inertia: (inertia_a.angular.inverse() + inertia_b.angular.inverse()).inverse(),
+            displacement: Vec3::ZERO,
+            velocity: velocity_a.angular - velocity_b.angular,
+        };
+
+        let linear = damper.linear_impulse(linear);
+        let angular = damper.angular_impulse(angular);
+        impulse_a.linear += linear;
+        impulse_a.angular += angular;
+        impulse_b.linear -= linear;
+        impulse_b.angular -= angular;
+    }
+}
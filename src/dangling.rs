@@ -0,0 +1,334 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::{
+        entity::{Entities, EntityHashMap, EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+        system::EntityCommands,
+    },
+    prelude::*,
+    reflect::Reflect,
+    utils::{HashMap, HashSet},
+};
+
+use crate::joint::{SpringJoint, SpringState};
+use crate::multi::Springs;
+use crate::plugin::SpringConfig;
+
+/// What happens to a [`SpringJoint`], or any other [`SpringLink`] or
+/// [`Springs`] hub, whose `containing` entity was despawned.
+///
+/// The solver skips dangling springs regardless of the policy.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DanglingSpringPolicy {
+    /// Leave the spring as is, without sending an event.
+    Ignore,
+    /// Disable the joint, it stays disabled if the entity is reused.
+    Disable,
+    /// Remove the [`SpringJoint`] and [`SpringState`], or the other link,
+    /// keeping the entity as a particle.
+    #[default]
+    Remove,
+    /// Despawn the spring entity.
+    Despawn,
+    /// Leave the spring as is, only sending a [`SpringTargetLost`] event.
+    Event,
+}
+
+/// Sent once when the `containing` entity of a spring is despawned.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringTargetLost {
+    pub entity: Entity,
+    pub target: Entity,
+}
+
+/// Reverse index from the `containing` entity of every [`SpringLink`] to the
+/// springs attached to it.
+#[derive(Default, Debug, Clone, Resource)]
+pub struct SpringTargets {
+    springs: EntityHashMap<Vec<Entity>>,
+    targets: HashMap<(Entity, TypeId), Entity>,
+    /// Targets whose springs changed since [`SprungBy`] was last synced.
+    changed: Vec<Entity>,
+}
+
+/// Springs attached to this entity through the `containing` entity of a
+/// [`SpringLink`], mirrored from the [`SpringTargets`] for inspectors and queries.
+///
+/// Stands in for an ECS relationship, which Bevy 0.14 has no support for.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SprungBy(pub Vec<Entity>);
+
+impl MapEntities for SprungBy {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for entity in &mut self.0 {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+/// Component springing its entity to a single `containing` entity, indexed in
+/// the [`SpringTargets`] so it shows up in [`SprungBy`] and is handled by the
+/// [`DanglingSpringPolicy`] once the `containing` entity is despawned.
+pub trait SpringLink: Component + Sized {
+    fn containing(&self) -> Entity;
+
+    fn set_enabled(&mut self, enabled: bool);
+
+    /// Remove the link from its entity for [`DanglingSpringPolicy::Remove`].
+    fn remove(entity: &mut EntityCommands) {
+        entity.remove::<Self>();
+    }
+}
+
+impl SpringLink for SpringJoint {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    fn remove(entity: &mut EntityCommands) {
+        entity.remove::<(SpringJoint, SpringState)>();
+    }
+}
+
+impl SpringTargets {
+    /// Springs linked to `target`, once per link.
+    pub fn springs_of(&self, target: Entity) -> &[Entity] {
+        self.springs.get(&target).map_or(&[], Vec::as_slice)
+    }
+
+    /// The `containing` entity of the `C` link of `spring` as of the last index update.
+    pub fn target_of<C: SpringLink>(&self, spring: Entity) -> Option<Entity> {
+        self.targets.get(&(spring, TypeId::of::<C>())).copied()
+    }
+
+    pub fn insert<C: SpringLink>(&mut self, spring: Entity, target: Entity) {
+        if self.target_of::<C>(spring) == Some(target) {
+            return;
+        }
+
+        self.remove::<C>(spring);
+        self.targets.insert((spring, TypeId::of::<C>()), target);
+        self.springs.entry(target).or_default().push(spring);
+        self.changed.push(target);
+    }
+
+    pub fn remove<C: SpringLink>(&mut self, spring: Entity) {
+        let Some(target) = self.targets.remove(&(spring, TypeId::of::<C>())) else {
+            return;
+        };
+        self.changed.push(target);
+
+        if let Some(springs) = self.springs.get_mut(&target) {
+            if let Some(index) = springs.iter().position(|other| *other == spring) {
+                springs.swap_remove(index);
+            }
+            if springs.is_empty() {
+                self.springs.remove(&target);
+            }
+        }
+    }
+
+    /// Remove the `C` links whose target no longer exists from the index,
+    /// returning the spring and target of each.
+    pub fn remove_lost<C: SpringLink>(&mut self, entities: &Entities) -> Vec<(Entity, Entity)> {
+        let type_id = TypeId::of::<C>();
+        let mut lost = Vec::new();
+        for (target, springs) in &self.springs {
+            if entities.contains(*target) {
+                continue;
+            }
+            for spring in springs {
+                if self.targets.get(&(*spring, type_id)) == Some(target) {
+                    lost.push((*spring, *target));
+                }
+            }
+        }
+        lost.sort_unstable();
+        lost.dedup();
+
+        for (spring, _) in &lost {
+            self.remove::<C>(*spring);
+        }
+        lost
+    }
+}
+
+pub fn index_spring_targets<C: SpringLink>(
+    entities: &Entities,
+    mut targets: ResMut<SpringTargets>,
+    links: Query<(Entity, &C), Changed<C>>,
+    mut removed: RemovedComponents<C>,
+) {
+    for spring in removed.read() {
+        targets.remove::<C>(spring);
+    }
+
+    for (spring, link) in &links {
+        // Springs already reported by `dangling_springs` stay out of the index.
+        let containing = link.containing();
+        if entities.contains(containing) {
+            targets.insert::<C>(spring, containing);
+        } else if targets.target_of::<C>(spring) != Some(containing) {
+            targets.remove::<C>(spring);
+        }
+    }
+}
+
+pub fn dangling_springs<C: SpringLink>(
+    mut commands: Commands,
+    config: Res<SpringConfig>,
+    entities: &Entities,
+    mut targets: ResMut<SpringTargets>,
+    mut links: Query<&mut C>,
+    mut lost_events: EventWriter<SpringTargetLost>,
+) {
+    let policy = config.dangling;
+    for (entity, target) in targets.remove_lost::<C>(entities) {
+        let Ok(mut link) = links.get_mut(entity) else {
+            continue;
+        };
+
+        match policy {
+            DanglingSpringPolicy::Ignore => continue,
+            DanglingSpringPolicy::Disable => link.set_enabled(false),
+            DanglingSpringPolicy::Remove => C::remove(&mut commands.entity(entity)),
+            DanglingSpringPolicy::Despawn => commands.entity(entity).despawn_recursive(),
+            DanglingSpringPolicy::Event => {}
+        }
+
+        lost_events.send(SpringTargetLost { entity, target });
+    }
+}
+
+/// [`dangling_springs`] for the targets of [`Springs`] hubs, sending a
+/// [`SpringTargetLost`] once per lost target.
+pub fn dangling_multi_springs(
+    mut commands: Commands,
+    config: Res<SpringConfig>,
+    entities: &Entities,
+    mut hubs: Query<(Entity, &mut Springs)>,
+    mut lost_events: EventWriter<SpringTargetLost>,
+    mut reported: Local<HashSet<(Entity, Entity)>>,
+) {
+    reported.retain(|(hub, _)| entities.contains(*hub));
+
+    let policy = config.dangling;
+    for (entity, mut springs) in &mut hubs {
+        let lost = springs
+            .0
+            .iter()
+            .map(|spring_to| spring_to.target)
+            .filter(|target| !entities.contains(*target))
+            .collect::<Vec<_>>();
+
+        for target in lost {
+            if !reported.insert((entity, target)) {
+                continue;
+            }
+
+            match policy {
+                DanglingSpringPolicy::Ignore => continue,
+                DanglingSpringPolicy::Disable => {
+                    for spring_to in &mut springs.0 {
+                        if spring_to.target == target {
+                            spring_to.enabled = false;
+                        }
+                    }
+                }
+                DanglingSpringPolicy::Remove => {
+                    springs.remove(target);
+                }
+                DanglingSpringPolicy::Despawn => commands.entity(entity).despawn_recursive(),
+                DanglingSpringPolicy::Event => {}
+            }
+
+            lost_events.send(SpringTargetLost { entity, target });
+        }
+    }
+}
+
+/// Mirror the [`SpringTargets`] of changed targets into their [`SprungBy`].
+pub fn sync_sprung_by(
+    mut commands: Commands,
+    entities: &Entities,
+    mut targets: ResMut<SpringTargets>,
+) {
+    let mut changed = std::mem::take(&mut targets.changed);
+    changed.sort_unstable();
+    changed.dedup();
+
+    for target in changed {
+        if !entities.contains(target) {
+            continue;
+        }
+
+        let mut springs = targets.springs_of(target).to_vec();
+        springs.sort_unstable();
+        springs.dedup();
+        if springs.is_empty() {
+            commands.entity(target).remove::<SprungBy>();
+        } else {
+            commands.entity(target).insert(SprungBy(springs));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::Velocity;
+    use crate::testing::*;
+    use crate::Spring;
+
+    fn lose_anchor(policy: DanglingSpringPolicy) -> (App, Entity) {
+        let mut app = app(plugin().with_dangling_policy(policy));
+        let chain = chain(&mut app, 1, 1.0, Spring::snappy());
+        step(&mut app, 1);
+
+        app.world_mut().despawn(chain[0]);
+        step(&mut app, 1);
+        let lost = app.world().resource::<Events<SpringTargetLost>>();
+        let lost: Vec<_> = lost.iter_current_update_events().collect();
+        let sent = policy != DanglingSpringPolicy::Ignore;
+        assert_eq!(lost.len(), sent as usize);
+        if sent {
+            assert_eq!((lost[0].entity, lost[0].target), (chain[1], chain[0]));
+        }
+        (app, chain[1])
+    }
+
+    #[test]
+    fn remove_policy_keeps_the_particle() {
+        let (app, spring) = lose_anchor(DanglingSpringPolicy::Remove);
+        let spring = app.world().entity(spring);
+        assert!(!spring.contains::<SpringJoint>());
+        assert!(spring.contains::<Velocity>());
+    }
+
+    #[test]
+    fn disable_policy_keeps_the_joint() {
+        let (app, spring) = lose_anchor(DanglingSpringPolicy::Disable);
+        assert!(!app.world().get::<SpringJoint>(spring).unwrap().enabled);
+    }
+
+    #[test]
+    fn despawn_policy_despawns_the_spring() {
+        let (app, spring) = lose_anchor(DanglingSpringPolicy::Despawn);
+        assert!(app.world().get_entity(spring).is_none());
+    }
+
+    #[test]
+    fn ignore_policy_leaves_the_spring() {
+        let (mut app, spring) = lose_anchor(DanglingSpringPolicy::Ignore);
+        assert!(app.world().get::<SpringJoint>(spring).unwrap().enabled);
+        // The solver skips the dangling spring instead of panicking.
+        step(&mut app, 10);
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::Instant,
+};
+
+use crate::integration::Inertia;
+use crate::joint::{SpringJoint, SpringPair, SpringState};
+use crate::plugin::{SpringSchedule, SpringSet};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Registers diagnostics for the springs stepped by the [`SpringPlugin`].
+#[derive(Default)]
+pub struct SpringDiagnosticsPlugin;
+
+impl SpringDiagnosticsPlugin {
+    pub const SPRING_COUNT: DiagnosticPath = DiagnosticPath::const_new("springy/spring_count");
+    pub const POTENTIAL_ENERGY: DiagnosticPath =
+        DiagnosticPath::const_new("springy/potential_energy");
+    pub const MAX_IMPULSE: DiagnosticPath = DiagnosticPath::const_new("springy/max_impulse");
+    pub const SOLVER_TIME: DiagnosticPath = DiagnosticPath::const_new("springy/solver_time");
+}
+
+impl Plugin for SpringDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SolverTimer>()
+            .register_diagnostic(Diagnostic::new(Self::SPRING_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::POTENTIAL_ENERGY))
+            .register_diagnostic(Diagnostic::new(Self::MAX_IMPULSE))
+            .register_diagnostic(Diagnostic::new(Self::SOLVER_TIME).with_suffix("ms"))
+            .add_systems(
+                SpringSchedule,
+                (
+                    start_solver_timer
+                        .after(SpringSet::Forces)
+                        .before(SpringSet::Solve),
+                    stop_solver_timer
+                        .after(SpringSet::Solve)
+                        .before(SpringSet::Integrate),
+                ),
+            )
+            .add_systems(PostUpdate, diagnostic_system);
+    }
+}
+
+/// Time spent in [`SpringSet::Solve`] since the diagnostics were last measured.
+#[derive(Default, Resource)]
+struct SolverTimer {
+    started: Option<Instant>,
+    elapsed: Duration,
+}
+
+fn start_solver_timer(mut timer: ResMut<SolverTimer>) {
+    timer.started = Some(Instant::now());
+}
+
+fn stop_solver_timer(mut timer: ResMut<SolverTimer>) {
+    if let Some(started) = timer.started.take() {
+        timer.elapsed += started.elapsed();
+    }
+}
+
+fn diagnostic_system(
+    mut diagnostics: Diagnostics,
+    mut timer: ResMut<SolverTimer>,
+    timestep: Res<SpringTimestep>,
+    springs: Query<(
+        Entity,
+        &SpringJoint,
+        AnyOf<(&Spring, &SpringPair)>,
+        &SpringState,
+    )>,
+    inertias: Query<&Inertia>,
+) {
+    diagnostics.add_measurement(&SpringDiagnosticsPlugin::SPRING_COUNT, || {
+        springs.iter().count() as f64
+    });
+
+    diagnostics.add_measurement(&SpringDiagnosticsPlugin::POTENTIAL_ENERGY, || {
+        if !timestep.is_running() {
+            return 0.0;
+        }
+
+        springs
+            .iter()
+            .filter_map(|(entity, joint, settings, state)| {
+                let spring = joint.linear_spring(&SpringPair::resolve(settings)?)?;
+                let [a, b] = inertias.get_many([entity, joint.containing]).ok()?;
+                let reduced_inertia = (a.linear.inverse() + b.linear.inverse()).inverse();
+                let stretch = state.length - joint.rest_length;
+                Some(spring.potential_energy(reduced_inertia, timestep.delta(), stretch) as f64)
+            })
+            .sum()
+    });
+
+    diagnostics.add_measurement(&SpringDiagnosticsPlugin::MAX_IMPULSE, || {
+        springs
+            .iter()
+            .map(|(_, _, _, state)| state.impulse.length() as f64)
+            .fold(0.0, f64::max)
+    });
+
+    let elapsed = std::mem::take(&mut timer.elapsed);
+    diagnostics.add_measurement(&SpringDiagnosticsPlugin::SOLVER_TIME, || {
+        elapsed.as_secs_f64() * 1000.0
+    });
+}
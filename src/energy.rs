@@ -0,0 +1,196 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::force::{GlobalGravity, Gravity};
+use crate::integration::{Inertia, Velocity};
+use crate::joint::{SpringJoint, SpringPair, SpringState};
+use crate::plugin::{SpringSchedule, SpringSet};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Tracks the energy of every spring and of the whole simulation after each
+/// substep, warning when the total keeps growing, which a closed system of
+/// springs never should.
+///
+/// Growing energy means the springs are too strong for the timestep, see
+/// [`Spring::stable_limits`].
+pub struct SpringEnergyPlugin {
+    /// Substeps in a row the total energy has to grow for before warning.
+    pub growing_steps: u32,
+    /// Growth relative to the previous total ignored as numerical noise.
+    pub tolerance: f32,
+}
+
+impl Default for SpringEnergyPlugin {
+    fn default() -> Self {
+        Self {
+            growing_steps: 60,
+            tolerance: 1e-4,
+        }
+    }
+}
+
+impl Plugin for SpringEnergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringEnergy>()
+            .register_type::<TotalSpringEnergy>()
+            .insert_resource(TotalSpringEnergy {
+                growing_steps: self.growing_steps,
+                tolerance: self.tolerance,
+                ..default()
+            })
+            .add_event::<SpringEnergyDrift>()
+            .add_systems(
+                SpringSchedule,
+                (insert_spring_energy, spring_energy, total_spring_energy)
+                    .chain()
+                    .after(SpringSet::Integrate),
+            );
+    }
+}
+
+/// Energy stored in a spring after the last substep.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringEnergy {
+    /// Kinetic energy of the particles moving towards or away from each other.
+    pub kinetic: f32,
+    /// Potential energy of the linear spring stretched away from its rest length.
+    pub potential: f32,
+}
+
+impl SpringEnergy {
+    pub fn total(&self) -> f32 {
+        self.kinetic + self.potential
+    }
+}
+
+/// Energy of every particle and spring after the last substep.
+#[derive(Default, Debug, Clone, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct TotalSpringEnergy {
+    /// Linear and angular kinetic energy of the particles.
+    pub kinetic: f32,
+    /// Potential energy of the springs and of the particles in gravity.
+    pub potential: f32,
+    /// Substeps in a row the total has grown.
+    pub growing: u32,
+    pub growing_steps: u32,
+    pub tolerance: f32,
+}
+
+impl TotalSpringEnergy {
+    pub fn total(&self) -> f32 {
+        self.kinetic + self.potential
+    }
+}
+
+/// Sent when the total energy grew for `steps` substeps in a row.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringEnergyDrift {
+    /// Total energy after the last substep.
+    pub energy: f32,
+    pub steps: u32,
+}
+
+pub fn insert_spring_energy(
+    mut commands: Commands,
+    springs: Query<Entity, (With<SpringJoint>, Without<SpringEnergy>)>,
+) {
+    for entity in &springs {
+        commands.entity(entity).insert(SpringEnergy::default());
+    }
+}
+
+pub fn spring_energy(
+    timestep: Res<SpringTimestep>,
+    mut springs: Query<(
+        Entity,
+        &SpringJoint,
+        AnyOf<(&Spring, &SpringPair)>,
+        &SpringState,
+        &mut SpringEnergy,
+    )>,
+    inertias: Query<&Inertia>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (entity, joint, settings, state, mut energy) in &mut springs {
+        let (Some(settings), Ok([a, b])) = (
+            SpringPair::resolve(settings),
+            inertias.get_many([entity, joint.containing]),
+        ) else {
+            continue;
+        };
+
+        let reduced_inertia = (a.linear.inverse() + b.linear.inverse()).inverse();
+        let stretch = state.length - joint.rest_length;
+        let potential = match joint.linear_spring(&settings) {
+            Some(spring) if joint.axes.linear() => spring.at_stretch(stretch).potential_energy(
+                reduced_inertia,
+                timestep.delta(),
+                stretch,
+            ),
+            _ => 0.0,
+        };
+        energy.set_if_neq(SpringEnergy {
+            kinetic: 0.5 * reduced_inertia * state.stretch_velocity * state.stretch_velocity,
+            potential,
+        });
+    }
+}
+
+pub fn total_spring_energy(
+    timestep: Res<SpringTimestep>,
+    global_gravity: Res<GlobalGravity>,
+    mut total: ResMut<TotalSpringEnergy>,
+    springs: Query<&SpringEnergy>,
+    particles: Query<(&Transform, &Velocity, &Inertia, Option<&Gravity>)>,
+    mut drift: EventWriter<SpringEnergyDrift>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    let mut kinetic = 0.0;
+    let mut potential: f32 = springs.iter().map(|energy| energy.potential).sum();
+    for (transform, velocity, inertia, gravity) in &particles {
+        // Infinite/zero mass particles don't move, so they hold no energy.
+        if !inertia.linear.is_normal() {
+            continue;
+        }
+
+        kinetic += 0.5 * inertia.linear * velocity.linear.length_squared();
+        kinetic += (0..3)
+            .filter(|axis| inertia.angular[*axis].is_finite())
+            .map(|axis| 0.5 * inertia.angular[axis] * velocity.angular[axis].powi(2))
+            .sum::<f32>();
+
+        let gravity = gravity.map_or(global_gravity.0, |gravity| gravity.0);
+        potential -= inertia.linear * gravity.dot(transform.translation);
+    }
+
+    let previous = total.total();
+    let energy = kinetic + potential;
+    if energy > previous + total.tolerance * previous.abs() {
+        total.growing += 1;
+    } else {
+        total.growing = 0;
+    }
+    total.kinetic = kinetic;
+    total.potential = potential;
+
+    if total.growing == total.growing_steps {
+        warn!(
+            "spring energy grew for {} substeps in a row to {}, the springs may be unstable",
+            total.growing, energy
+        );
+        drift.send(SpringEnergyDrift {
+            energy,
+            steps: total.growing,
+        });
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::joint::{SpringJoint, SpringState};
+use crate::timestep::SpringTimestep;
+
+/// Opt-in thresholds for emitting spring lifecycle events from the solver state.
+///
+/// Each event is only sent when the spring transitions into that state.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringEvents {
+    /// Length above which the spring is considered stretched.
+    pub stretched_length: f32,
+    /// Magnitude of the linear impulse above which the spring is considered taut.
+    pub taut_impulse: f32,
+    /// Distance from the rest length below which the spring can be considered settled.
+    pub settled_length: f32,
+    /// Stretch velocity below which the spring can be considered settled.
+    pub settled_velocity: f32,
+    /// Stretch velocity, stretching or compressing, above which a
+    /// [`SpringTwang`] is sent for audio.
+    pub twang_speed: f32,
+    stretched: bool,
+    taut: bool,
+    settled: bool,
+    twanging: bool,
+}
+
+impl Default for SpringEvents {
+    fn default() -> Self {
+        Self {
+            stretched_length: f32::INFINITY,
+            taut_impulse: f32::INFINITY,
+            settled_length: 0.01,
+            settled_velocity: 0.01,
+            twang_speed: f32::INFINITY,
+            stretched: false,
+            taut: false,
+            settled: false,
+            twanging: false,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringStretched {
+    pub entity: Entity,
+    pub length: f32,
+    pub velocity: f32,
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringTaut {
+    pub entity: Entity,
+    pub impulse: f32,
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringSettled {
+    pub entity: Entity,
+}
+
+/// Sent when a spring starts stretching or compressing faster than its
+/// `twang_speed`, for triggering creaks and boings scaled by the intensity.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringTwang {
+    pub entity: Entity,
+    /// Rate of change of the length, positive when stretching.
+    pub stretch_speed: f32,
+    /// Force the linear spring pulls or pushes with.
+    pub tension: f32,
+}
+
+pub fn spring_events(
+    timestep: Res<SpringTimestep>,
+    mut springs: Query<(Entity, &SpringJoint, &SpringState, &mut SpringEvents)>,
+    mut stretched_events: EventWriter<SpringStretched>,
+    mut taut_events: EventWriter<SpringTaut>,
+    mut settled_events: EventWriter<SpringSettled>,
+    mut twang_events: EventWriter<SpringTwang>,
+) {
+    for (entity, joint, state, mut events) in &mut springs {
+        let stretched = state.length > events.stretched_length;
+        if stretched && !events.stretched {
+            stretched_events.send(SpringStretched {
+                entity,
+                length: state.length,
+                velocity: state.stretch_velocity,
+            });
+        }
+
+        let impulse = state.impulse.length();
+        let taut = impulse > events.taut_impulse;
+        if taut && !events.taut {
+            taut_events.send(SpringTaut { entity, impulse });
+        }
+
+        let settled = (state.length - joint.rest_length).abs() <= events.settled_length
+            && state.stretch_velocity.abs() <= events.settled_velocity;
+        if settled && !events.settled {
+            settled_events.send(SpringSettled { entity });
+        }
+
+        let twanging = state.stretch_velocity.abs() > events.twang_speed;
+        if twanging && !events.twanging && timestep.is_running() {
+            twang_events.send(SpringTwang {
+                entity,
+                stretch_speed: state.stretch_velocity,
+                tension: impulse / timestep.delta(),
+            });
+        }
+
+        if events.stretched != stretched
+            || events.taut != taut
+            || events.settled != settled
+            || events.twanging != twanging
+        {
+            events.stretched = stretched;
+            events.taut = taut;
+            events.settled = settled;
+            events.twanging = twanging;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Whether a [`SpringField`] pulls particles in or pushes them out.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FieldMode {
+    /// Spring particles towards the center, for magnets and tractor beams.
+    #[default]
+    Attract,
+    /// Spring particles out to the edge of the field, for soft explosions
+    /// and force shields.
+    Repel,
+}
+
+/// Springs every particle within `radius` of this entity towards or away
+/// from its translation.
+///
+/// Particles are affected by the fields in the [`SpringPlugin`](crate::SpringPlugin),
+/// rigid bodies when the field plugin of a rapier backend is added.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringField {
+    pub radius: f32,
+    pub spring: Spring,
+    pub mode: FieldMode,
+}
+
+impl SpringField {
+    pub fn attract(radius: f32, spring: Spring) -> Self {
+        Self {
+            radius,
+            spring,
+            mode: FieldMode::Attract,
+        }
+    }
+
+    pub fn repel(radius: f32, spring: Spring) -> Self {
+        Self {
+            radius,
+            spring,
+            mode: FieldMode::Repel,
+        }
+    }
+
+    /// Impulse applied to `particle` by the field centered on `center`, `None`
+    /// when it is outside the field.
+    pub fn impulse(
+        &self,
+        timestep: f32,
+        particle: &TranslationParticle3,
+        center: &TranslationParticle3,
+    ) -> Option<Vec3> {
+        if particle.translation.distance_squared(center.translation) > self.radius * self.radius {
+            return None;
+        }
+
+        let instant = particle.instant(center);
+        let instant = match self.mode {
+            FieldMode::Attract => instant,
+            FieldMode::Repel => instant.with_rest_length(self.radius),
+        };
+        Some(self.spring.impulse(timestep, instant))
+    }
+}
+
+pub fn spring_field(
+    timestep: Res<SpringTimestep>,
+    fields: Query<(Entity, &SpringField, &GlobalTransform, Option<&Velocity>)>,
+    mut particles: Query<(Entity, &Transform, &Velocity, &Inertia, &mut Impulse)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (field_entity, field, field_transform, field_velocity) in &fields {
+        let center = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: field_transform.translation(),
+            velocity: field_velocity.map_or(Vec3::ZERO, |velocity| velocity.linear),
+        };
+
+        for (entity, transform, velocity, inertia, mut impulse) in &mut particles {
+            if entity == field_entity {
+                continue;
+            }
+
+            let particle = TranslationParticle3 {
+                mass: inertia.linear,
+                translation: transform.translation,
+                velocity: velocity.linear,
+            };
+            if let Some(field_impulse) = field.impulse(timestep, &particle, &center) {
+                impulse.linear += field_impulse;
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    transform::TransformSystem,
+};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Springs the `Transform` of this entity towards a target entity, without
+/// needing any physics components.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SpringFollow {
+    pub target: Entity,
+    /// Offset from the target translation to follow.
+    pub offset: Vec3,
+    pub spring: Spring,
+    /// Current velocity of the follower.
+    pub velocity: Vec3,
+}
+
+impl MapEntities for SpringFollow {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.map_entity(self.target);
+    }
+}
+
+impl SpringFollow {
+    pub fn new(target: Entity, offset: Vec3) -> Self {
+        Self {
+            target,
+            offset,
+            spring: Spring::loose_follow(),
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_spring(mut self, spring: Spring) -> Self {
+        self.spring = spring;
+        self
+    }
+
+    /// Advance the follower at `translation` towards `target` by one timestep.
+    pub fn step(&mut self, translation: Vec3, target: Vec3, timestep: f32) -> Vec3 {
+        let follower = TranslationParticle3 {
+            mass: 1.0,
+            translation,
+            velocity: self.velocity,
+        };
+        let anchor = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: target + self.offset,
+            velocity: Vec3::ZERO,
+        };
+
+        self.velocity += self.spring.impulse(timestep, follower.instant(&anchor));
+        translation + self.velocity * timestep
+    }
+}
+
+pub struct SpringFollowPlugin;
+
+impl Plugin for SpringFollowPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringFollow>()
+            .register_type::<SpringClock>()
+            .add_systems(
+                PostUpdate,
+                spring_follow
+                    .run_if(springs_running)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn spring_follow(
+    clocks: SpringClocks,
+    mut followers: Query<(&mut Transform, &mut SpringFollow, Option<&SpringClock>)>,
+    targets: Query<&GlobalTransform>,
+) {
+    for (mut transform, mut follow, clock) in &mut followers {
+        let timestep = clocks.delta(clock);
+        if timestep == 0.0 {
+            continue;
+        }
+
+        let Ok(target) = targets.get(follow.target) else {
+            continue;
+        };
+
+        transform.translation = follow.step(transform.translation, target.translation(), timestep);
+    }
+}
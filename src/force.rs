@@ -0,0 +1,154 @@
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+
+/// Gravity applied to every particle without a [`Gravity`] override.
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct GlobalGravity(pub Vec3);
+
+impl Default for GlobalGravity {
+    fn default() -> Self {
+        Self(Vec3::new(0.0, -9.817, 0.0))
+    }
+}
+
+/// Per-particle gravity, overrides [`GlobalGravity`].
+///
+/// Use `Gravity(Vec3::ZERO)` to opt a particle out of gravity entirely.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Gravity(pub Vec3);
+
+/// Force and torque continuously applied to a particle.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct ConstantForce {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+/// Wind dragging particles along with the air, for flags, cloth and hanging
+/// ropes.
+///
+/// As a resource it blows on every particle, as a component it overrides the
+/// resource for that particle, e.g. `Wind::default()` to shelter it.
+#[derive(Default, Debug, Copy, Clone, Resource, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource, Component)]
+pub struct Wind {
+    /// Velocity of the air.
+    pub velocity: Vec3,
+    /// How strongly particles are dragged towards the velocity of the air,
+    /// as force per unit of relative velocity.
+    pub drag: f32,
+    /// Fraction the speed of the air varies by in gusts, from 0 to 1.
+    pub gust: f32,
+    /// Gusts per second.
+    pub gust_frequency: f32,
+}
+
+impl Wind {
+    pub fn new(velocity: Vec3) -> Self {
+        Self {
+            velocity,
+            drag: 1.0,
+            gust: 0.3,
+            gust_frequency: 0.5,
+        }
+    }
+
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    pub fn with_gusts(mut self, gust: f32, gust_frequency: f32) -> Self {
+        self.gust = gust;
+        self.gust_frequency = gust_frequency;
+        self
+    }
+
+    /// Velocity of the air at `translation` after `elapsed` seconds.
+    ///
+    /// Gusts are a sum of sines travelling along the wind, so neighbouring
+    /// particles are hit by the same gust shortly after each other.
+    pub fn velocity_at(&self, translation: Vec3, elapsed: f32) -> Vec3 {
+        let speed = self.velocity.length();
+        if speed == 0.0 || self.gust == 0.0 {
+            return self.velocity;
+        }
+
+        let phase = (elapsed - translation.dot(self.velocity) / (speed * speed))
+            * self.gust_frequency
+            * TAU;
+        let noise = 0.6 * phase.sin() + 0.3 * (phase * 2.3 + 1.7).sin() + 0.1 * (phase * 5.1).sin();
+        self.velocity * (1.0 + self.gust.clamp(0.0, 1.0) * noise)
+    }
+}
+
+pub fn gravity(
+    timestep: Res<SpringTimestep>,
+    global: Res<GlobalGravity>,
+    mut to_apply: Query<(&mut Impulse, &Inertia, Option<&Gravity>)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut impulse, inertia, gravity) in &mut to_apply {
+        // Infinite/zero mass particles aren't affected by gravity.
+        if !inertia.linear.is_normal() {
+            continue;
+        }
+
+        let acceleration = gravity.map(|gravity| gravity.0).unwrap_or(global.0);
+        impulse.linear += acceleration * inertia.linear * timestep;
+    }
+}
+
+pub fn constant_force(
+    timestep: Res<SpringTimestep>,
+    mut to_apply: Query<(&mut Impulse, &ConstantForce)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut impulse, force) in &mut to_apply {
+        impulse.linear += force.linear * timestep;
+        impulse.angular += force.angular * timestep;
+    }
+}
+
+pub fn wind(
+    time: Res<Time>,
+    timestep: Res<SpringTimestep>,
+    global: Res<Wind>,
+    mut to_apply: Query<(&mut Impulse, &Transform, &Velocity, &Inertia, Option<&Wind>)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+    let elapsed = time.elapsed_seconds();
+
+    for (mut impulse, transform, velocity, inertia, wind) in &mut to_apply {
+        // Infinite/zero mass particles aren't blown around.
+        if !inertia.linear.is_normal() {
+            continue;
+        }
+
+        let wind = wind.unwrap_or(&global);
+        let air = wind.velocity_at(transform.translation, elapsed);
+        impulse.linear += (air - velocity.linear) * wind.drag * timestep;
+    }
+}
@@ -0,0 +1,180 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::dangling::SpringLink;
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::torsion::WindUp;
+use crate::*;
+
+/// Couples the rotation of this entity around `axis` to the rotation of the
+/// `containing` entity around `containing_axis`, driving
+/// `angle * ratio + containing_angle` towards `rest_angle`.
+///
+/// For gears, steering linkages and doors opening in sync. A ratio of 1
+/// spins the two in opposite directions like meshed gears of the same size,
+/// -1 turns them together. The axes are in world space, and the angles are
+/// accumulated so the coupling holds through any number of turns.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct GearSpring {
+    pub containing: Entity,
+    /// Disabled gears are skipped while keeping their settings, the angles
+    /// are still tracked.
+    pub enabled: bool,
+    pub ratio: f32,
+    /// Axis in world space this entity rotates around, `Vec3::Z` for 2D.
+    pub axis: Vec3,
+    /// Axis in world space the `containing` entity rotates around.
+    pub containing_axis: Vec3,
+    /// Value of `angle * ratio + containing_angle` the spring rests at, in radians.
+    pub rest_angle: f32,
+    pub spring: Spring,
+    /// Accumulated rotation of this entity around `axis`.
+    pub angle: WindUp,
+    /// Accumulated rotation of the `containing` entity around `containing_axis`.
+    pub containing_angle: WindUp,
+}
+
+impl MapEntities for GearSpring {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringLink for GearSpring {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl GearSpring {
+    pub fn new(containing: Entity, ratio: f32, spring: Spring) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            ratio,
+            axis: Vec3::Z,
+            containing_axis: Vec3::Z,
+            rest_angle: 0.0,
+            spring,
+            angle: WindUp::default(),
+            containing_angle: WindUp::default(),
+        }
+    }
+
+    /// Rotate both entities around the same axis in world space.
+    pub fn with_axis(mut self, axis: Vec3) -> Self {
+        self.axis = axis;
+        self.containing_axis = axis;
+        self
+    }
+
+    pub fn with_containing_axis(mut self, containing_axis: Vec3) -> Self {
+        self.containing_axis = containing_axis;
+        self
+    }
+
+    pub fn with_rest_angle(mut self, rest_angle: f32) -> Self {
+        self.rest_angle = rest_angle;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// How far the coupling is from resting, in radians.
+    pub fn error(&self) -> f32 {
+        self.angle.angle * self.ratio + self.containing_angle.angle - self.rest_angle
+    }
+}
+
+/// Angle in radians of the twist of `rotation` around `axis`.
+fn twist_angle(rotation: Quat, axis: Vec3) -> f32 {
+    let axis = axis.normalize_or_zero();
+    2.0 * Vec3::new(rotation.x, rotation.y, rotation.z)
+        .dot(axis)
+        .atan2(rotation.w)
+}
+
+/// Inertia of a particle when rotating around `axis`.
+fn axial_inertia(inertia: Vec3, axis: Vec3) -> f32 {
+    (axis * axis).dot(inertia.inverse()).inverse()
+}
+
+pub fn gear_spring(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    mut gears: Query<(Entity, &mut GearSpring, Option<&SpringGroup>)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (entity, mut gear, group) in &mut gears {
+        if gear.containing == entity {
+            continue;
+        }
+
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(entity), particles.get(gear.containing))
+        else {
+            continue;
+        };
+        let Ok([mut impulse_a, mut impulse_b]) = impulses.get_many_mut([entity, gear.containing])
+        else {
+            continue;
+        };
+
+        // Include the impulses of the springs solved before this one.
+        let velocity_a = velocity_a.with_impulse(&impulse_a, inertia_a);
+        let velocity_b = velocity_b.with_impulse(&impulse_b, inertia_b);
+
+        let axis_a = gear.axis.normalize_or_zero();
+        let axis_b = gear.containing_axis.normalize_or_zero();
+        let angle_a = gear.angle.track(twist_angle(transform_a.rotation, axis_a));
+        let angle_b = gear
+            .containing_angle
+            .track(twist_angle(transform_b.rotation, axis_b));
+
+        // Scale this particle by the ratio and mirror the containing one
+        // around the rest angle, so the plain difference is the coupling error.
+        let ratio = gear.ratio;
+        let particle = AngularParticle2 {
+            inertia: axial_inertia(inertia_a.angular, axis_a) / (ratio * ratio),
+            rotation: angle_a * ratio,
+            velocity: velocity_a.angular.dot(axis_a) * ratio,
+        };
+        let containing = AngularParticle2 {
+            inertia: axial_inertia(inertia_b.angular, axis_b),
+            rotation: gear.rest_angle - angle_b,
+            velocity: -velocity_b.angular.dot(axis_b),
+        };
+        // Keep tracking the angles so re-enabling doesn't lose whole turns.
+        if !gear.enabled || !groups.is_active(group) {
+            continue;
+        }
+        let impulse = gear
+            .spring
+            .impulse(timestep, particle.unwrapped_instant(&containing));
+        impulse_a.angular += axis_a * impulse * ratio;
+        impulse_b.angular += axis_b * impulse;
+    }
+}
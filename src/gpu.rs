@@ -0,0 +1,789 @@
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Arc, Mutex,
+};
+
+use bevy::{
+    asset::load_internal_asset,
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    reflect::Reflect,
+    render::{
+        graph::CameraDriverLabel,
+        render_graph::{self, RenderGraph, RenderLabel},
+        render_resource::{
+            binding_types::{
+                storage_buffer_read_only_sized, storage_buffer_sized, uniform_buffer_sized,
+            },
+            *,
+        },
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+    },
+};
+
+use crate::graph::SpringGraph;
+use crate::kinematic::Kinematic;
+use crate::timestep::SpringsPaused;
+use crate::*;
+
+pub const GPU_SPRING_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(0x5b1e_a2f4_9c3d_4e8a_b7f0_1d26_c8e3_9a41);
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// States of the staging buffer a network is read back through.
+const READBACK_IDLE: u8 = 0;
+const READBACK_COPIED: u8 = 1;
+const READBACK_MAPPING: u8 = 2;
+const READBACK_MAPPED: u8 = 3;
+
+/// Network of particles and springs simulated by a compute shader, for cloth
+/// and spring networks too large for the CPU solvers.
+///
+/// The network is uploaded whenever it changes and is then simulated on the
+/// GPU alone, the positions are read back into [`GpuSpringPositions`] for
+/// rendering. It is separate from the [`SpringJoint`](crate::joint::SpringJoint)
+/// entities, which stay on the CPU so gameplay can rely on them.
+///
+/// The springs are solved with a Jacobi solver, so they are softer than the
+/// same springs on the CPU. The damp mode, compression and soft limit of the
+/// [`Spring`] are respected.
+///
+/// [`Self::step_cpu`] runs the same solver on the CPU, the ignored
+/// `gpu_matches_cpu_reference` test checks the shader against it on machines
+/// with a GPU.
+///
+/// **Experimental**: the compute backend may change or be removed.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct GpuSpringNetwork {
+    pub positions: Vec<Vec3>,
+    pub velocities: Vec<Vec3>,
+    /// Inverse mass of each particle, 0 for particles pinned in place.
+    pub inverse_masses: Vec<f32>,
+    pub springs: Vec<GpuSpring>,
+    /// Acceleration applied to every particle that isn't pinned.
+    pub gravity: Vec3,
+    pub iterations: u32,
+}
+
+/// Spring between two particles of a [`GpuSpringNetwork`], by index.
+#[derive(Debug, Copy, Clone, Reflect)]
+pub struct GpuSpring {
+    pub a: u32,
+    pub b: u32,
+    pub rest_length: f32,
+    pub spring: Spring,
+}
+
+impl GpuSpringNetwork {
+    pub fn new(gravity: Vec3) -> Self {
+        Self {
+            gravity,
+            iterations: 8,
+            ..default()
+        }
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Add a particle, returning its index.
+    pub fn add_particle(&mut self, position: Vec3, mass: f32) -> u32 {
+        self.positions.push(position);
+        self.velocities.push(Vec3::ZERO);
+        self.inverse_masses.push(mass.inverse());
+        self.positions.len() as u32 - 1
+    }
+
+    /// Add a spring between two particles, resting at their current distance.
+    pub fn add_spring(&mut self, a: u32, b: u32, spring: Spring) {
+        let rest_length = self.positions[a as usize].distance(self.positions[b as usize]);
+        self.springs.push(GpuSpring {
+            a,
+            b,
+            rest_length,
+            spring,
+        });
+    }
+
+    /// Network of the springs and particles of a [`SpringGraph`], in the state
+    /// they were last gathered in.
+    pub fn from_graph(graph: &SpringGraph, gravity: Vec3) -> Self {
+        let mut network = Self::new(gravity);
+        network.positions = graph
+            .transforms
+            .iter()
+            .map(|transform| transform.translation)
+            .collect();
+        network.velocities = graph
+            .velocities
+            .iter()
+            .map(|velocity| velocity.linear)
+            .collect();
+        network.inverse_masses = graph
+            .inertias
+            .iter()
+            .map(|inertia| inertia.linear.inverse())
+            .collect();
+        network.springs = (0..graph.spring_count())
+            .map(|spring| GpuSpring {
+                a: graph.a[spring] as u32,
+                b: graph.b[spring] as u32,
+                rest_length: graph.joints[spring].rest_length,
+                spring: graph.settings[spring].linear,
+            })
+            .collect();
+        network
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Step the network on the CPU with the same Jacobi solver as the compute
+    /// shader, as a reference to check the GPU against.
+    pub fn step_cpu(&mut self, timestep: f32) {
+        let particles = self.particle_count();
+        let mut adjacency = vec![Vec::new(); particles];
+        for spring in &self.springs {
+            adjacency[spring.a as usize].push(*spring);
+            adjacency[spring.b as usize].push(*spring);
+        }
+
+        for _ in 0..self.iterations.max(1) {
+            let velocities = self.velocities.clone();
+            let particle = |index: usize| TranslationParticle3 {
+                mass: self.inverse_masses[index].inverse(),
+                translation: self.positions[index],
+                velocity: velocities[index],
+            };
+
+            for (index, springs) in adjacency.iter().enumerate() {
+                let inverse_mass = self.inverse_masses[index];
+                if inverse_mass == 0.0 || springs.is_empty() {
+                    continue;
+                }
+
+                let mut impulse = Vec3::ZERO;
+                for spring in springs {
+                    let other = if spring.a as usize == index {
+                        spring.b
+                    } else {
+                        spring.a
+                    };
+                    let (a, b) = (particle(index), particle(other as usize));
+                    let stretch = a.translation.distance(b.translation) - spring.rest_length;
+                    let instant = a.instant(&b).with_rest_length(spring.rest_length);
+                    impulse += spring.spring.at_stretch(stretch).impulse(timestep, instant);
+                }
+
+                // Averaged like the shader does.
+                self.velocities[index] =
+                    velocities[index] + impulse * inverse_mass / springs.len() as f32;
+            }
+        }
+
+        for index in 0..particles {
+            if self.inverse_masses[index] > 0.0 {
+                self.velocities[index] += self.gravity * timestep;
+            }
+            self.positions[index] += self.velocities[index] * timestep;
+        }
+    }
+}
+
+/// Positions of the particles of a [`GpuSpringNetwork`] read back from the
+/// GPU, a frame or more behind the simulation.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct GpuSpringPositions(pub Vec<Vec3>);
+
+/// Positions read back in the render world, waiting to be applied in the main world.
+#[derive(Default, Clone, Resource)]
+struct GpuSpringReadback(Arc<Mutex<Vec<(Entity, Vec<Vec3>)>>>);
+
+/// Simulates [`GpuSpringNetwork`]s in a compute shader.
+///
+/// **Experimental**: not yet validated on real hardware.
+pub struct GpuSpringPlugin;
+
+impl Plugin for GpuSpringPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, GPU_SPRING_SHADER_HANDLE, "gpu.wgsl", Shader::from_wgsl);
+
+        let readback = GpuSpringReadback::default();
+        app.register_type::<GpuSpringNetwork>()
+            .register_type::<GpuSpringPositions>()
+            .insert_resource(readback.clone())
+            .add_systems(PreUpdate, apply_gpu_readback);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .insert_resource(readback)
+            .init_resource::<ExtractedGpuNetworks>()
+            .init_resource::<GpuNetworks>()
+            .add_systems(ExtractSchedule, extract_gpu_networks)
+            .add_systems(
+                Render,
+                (
+                    prepare_gpu_networks.in_set(RenderSet::PrepareBindGroups),
+                    read_back_gpu_networks.in_set(RenderSet::Cleanup),
+                ),
+            );
+
+        let mut graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        graph.add_node(GpuSpringLabel, GpuSpringNode);
+        graph.add_node_edge(GpuSpringLabel, CameraDriverLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<GpuSpringPipeline>();
+    }
+}
+
+fn apply_gpu_readback(
+    mut commands: Commands,
+    readback: Res<GpuSpringReadback>,
+    mut positions: Query<&mut GpuSpringPositions>,
+) {
+    let Ok(mut readback) = readback.0.lock() else {
+        return;
+    };
+
+    for (entity, read) in readback.drain(..) {
+        match positions.get_mut(entity) {
+            Ok(mut positions) => positions.0 = read,
+            Err(_) => {
+                if let Some(mut entity) = commands.get_entity(entity) {
+                    entity.insert(GpuSpringPositions(read));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+struct ExtractedGpuNetworks {
+    timestep: f32,
+    alive: Vec<Entity>,
+    /// Networks that changed since they were last uploaded.
+    uploads: Vec<(Entity, GpuSpringNetwork)>,
+}
+
+fn extract_gpu_networks(
+    mut extracted: ResMut<ExtractedGpuNetworks>,
+    networks: Extract<Query<(Entity, Ref<GpuSpringNetwork>)>>,
+    time: Extract<Res<Time>>,
+    paused: Extract<Option<Res<SpringsPaused>>>,
+) {
+    let paused = paused.as_ref().is_some_and(|paused| paused.0);
+    extracted.timestep = if paused { 0.0 } else { time.delta_seconds() };
+
+    extracted.alive.clear();
+    extracted.uploads.clear();
+    for (entity, network) in &networks {
+        extracted.alive.push(entity);
+        if network.is_changed() {
+            extracted.uploads.push((entity, network.clone()));
+        }
+    }
+}
+
+#[derive(Resource)]
+struct GpuSpringPipeline {
+    layout: BindGroupLayout,
+    solve: CachedComputePipelineId,
+    integrate: CachedComputePipelineId,
+}
+
+impl FromWorld for GpuSpringPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let layout = world.resource::<RenderDevice>().create_bind_group_layout(
+            "gpu_spring_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    uniform_buffer_sized(false, None),
+                    storage_buffer_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                    storage_buffer_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                    storage_buffer_read_only_sized(false, None),
+                ),
+            ),
+        );
+
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let queue = |entry_point: &'static str| {
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some(format!("gpu_spring_{entry_point}").into()),
+                layout: vec![layout.clone()],
+                push_constant_ranges: Vec::new(),
+                shader: GPU_SPRING_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: entry_point.into(),
+            })
+        };
+        let solve = queue("solve");
+        let integrate = queue("integrate");
+
+        Self {
+            layout,
+            solve,
+            integrate,
+        }
+    }
+}
+
+/// Buffers of one network on the GPU.
+struct GpuNetworkBuffers {
+    particles: u32,
+    iterations: u32,
+    gravity: Vec3,
+    params: Buffer,
+    positions: Buffer,
+    staging: Buffer,
+    /// One of the `READBACK_*` states, shared with the mapping callback.
+    readback: Arc<AtomicU8>,
+    /// Velocities flip between two buffers every pass.
+    bind_groups: [BindGroup; 2],
+    /// Bind group of the first pass this frame.
+    start: usize,
+    /// Whether the network is simulated this frame.
+    active: bool,
+}
+
+#[derive(Default, Resource)]
+struct GpuNetworks(EntityHashMap<GpuNetworkBuffers>);
+
+fn storage_buffer<T: bytemuck::Pod>(
+    device: &RenderDevice,
+    label: &str,
+    contents: &[T],
+    usage: BufferUsages,
+) -> Buffer {
+    device.create_buffer_with_data(&BufferInitDescriptor {
+        label: Some(label),
+        contents: bytemuck::cast_slice(contents),
+        usage: BufferUsages::STORAGE | usage,
+    })
+}
+
+impl GpuNetworkBuffers {
+    fn new(
+        device: &RenderDevice,
+        pipeline: &GpuSpringPipeline,
+        network: &GpuSpringNetwork,
+    ) -> Self {
+        let particles = network.particle_count();
+        let positions = (0..particles)
+            .map(|index| network.positions[index].extend(network.inverse_masses[index]))
+            .map(|position| position.to_array())
+            .collect::<Vec<_>>();
+        let velocities = (0..particles)
+            .map(|index| network.velocities[index].extend(0.0).to_array())
+            .collect::<Vec<_>>();
+
+        // Springs of each particle, so every invocation only writes its own particle.
+        let mut adjacency = vec![Vec::new(); particles];
+        for (index, spring) in network.springs.iter().enumerate() {
+            adjacency[spring.a as usize].push(index as u32);
+            adjacency[spring.b as usize].push(index as u32);
+        }
+        let mut offsets = vec![0u32];
+        for springs in &adjacency {
+            offsets.push(offsets[offsets.len() - 1] + springs.len() as u32);
+        }
+        let mut adjacency = adjacency.concat();
+
+        let mut spring_particles = network
+            .springs
+            .iter()
+            .map(|spring| [spring.a, spring.b])
+            .collect::<Vec<_>>();
+        // Two vectors per spring: rest length, strength, damp ratio and damp
+        // mode, then the compressed strength and damp ratio and the soft limit.
+        let mut spring_settings = network
+            .springs
+            .iter()
+            .flat_map(|spring| {
+                let settings = spring.spring;
+                let compression = settings.compression.unwrap_or(SpringCompression {
+                    strength: settings.strength,
+                    damp_ratio: settings.damp_ratio,
+                });
+                let soft_limit = settings.soft_limit.unwrap_or_default();
+                let damp_mode = match settings.damp_mode {
+                    DampMode::Full => 0.0,
+                    DampMode::Axial => 1.0,
+                };
+                [
+                    [
+                        spring.rest_length,
+                        settings.strength,
+                        settings.damp_ratio,
+                        damp_mode,
+                    ],
+                    [
+                        compression.strength,
+                        compression.damp_ratio,
+                        soft_limit.distance,
+                        soft_limit.extra_strength,
+                    ],
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        // Empty storage buffers can't be bound.
+        if spring_particles.is_empty() {
+            spring_particles.push([0; 2]);
+            spring_settings.extend([[0.0; 4]; 2]);
+        }
+        if adjacency.is_empty() {
+            adjacency.push(0);
+        }
+
+        let params = device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_spring_params"),
+            size: std::mem::size_of::<[f32; 4]>() as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let position_buffer = storage_buffer(
+            device,
+            "gpu_spring_positions",
+            &positions,
+            BufferUsages::COPY_SRC,
+        );
+        let staging = device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_spring_staging"),
+            size: position_buffer.size(),
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let velocities = [
+            storage_buffer(
+                device,
+                "gpu_spring_velocities",
+                &velocities,
+                BufferUsages::empty(),
+            ),
+            storage_buffer(
+                device,
+                "gpu_spring_velocities",
+                &velocities,
+                BufferUsages::empty(),
+            ),
+        ];
+        let spring_particles = storage_buffer(
+            device,
+            "gpu_spring_particles",
+            &spring_particles,
+            BufferUsages::empty(),
+        );
+        let spring_settings = storage_buffer(
+            device,
+            "gpu_spring_settings",
+            &spring_settings,
+            BufferUsages::empty(),
+        );
+        let offsets = storage_buffer(
+            device,
+            "gpu_spring_offsets",
+            &offsets,
+            BufferUsages::empty(),
+        );
+        let adjacency = storage_buffer(
+            device,
+            "gpu_spring_adjacency",
+            &adjacency,
+            BufferUsages::empty(),
+        );
+
+        let bind_group = |input: &Buffer, output: &Buffer| {
+            device.create_bind_group(
+                "gpu_spring_bind_group",
+                &pipeline.layout,
+                &BindGroupEntries::sequential((
+                    params.as_entire_binding(),
+                    position_buffer.as_entire_binding(),
+                    input.as_entire_binding(),
+                    output.as_entire_binding(),
+                    spring_particles.as_entire_binding(),
+                    spring_settings.as_entire_binding(),
+                    offsets.as_entire_binding(),
+                    adjacency.as_entire_binding(),
+                )),
+            )
+        };
+        let bind_groups = [
+            bind_group(&velocities[0], &velocities[1]),
+            bind_group(&velocities[1], &velocities[0]),
+        ];
+
+        Self {
+            particles: particles as u32,
+            iterations: network.iterations.max(1),
+            gravity: network.gravity,
+            params,
+            positions: position_buffer,
+            staging,
+            readback: Arc::new(AtomicU8::new(READBACK_IDLE)),
+            bind_groups,
+            start: 0,
+            active: false,
+        }
+    }
+}
+
+fn prepare_gpu_networks(
+    mut networks: ResMut<GpuNetworks>,
+    mut extracted: ResMut<ExtractedGpuNetworks>,
+    pipeline: Res<GpuSpringPipeline>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+) {
+    let extracted = &mut *extracted;
+    networks
+        .0
+        .retain(|entity, _| extracted.alive.contains(entity));
+    for (entity, network) in extracted.uploads.drain(..) {
+        if network.particle_count() == 0 {
+            networks.0.remove(&entity);
+            continue;
+        }
+        networks
+            .0
+            .insert(entity, GpuNetworkBuffers::new(&device, &pipeline, &network));
+    }
+
+    for buffers in networks.0.values_mut() {
+        // Every pass flips the velocity buffers, continue from where the
+        // last simulated frame left off.
+        if buffers.active {
+            buffers.start = (buffers.start + buffers.iterations as usize + 1) % 2;
+        }
+
+        buffers.active = extracted.timestep > 0.0;
+        let params = buffers.gravity.extend(extracted.timestep).to_array();
+        queue.write_buffer(&buffers.params, 0, bytemuck::cast_slice(&params));
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+struct GpuSpringLabel;
+
+struct GpuSpringNode;
+
+impl render_graph::Node for GpuSpringNode {
+    fn run(
+        &self,
+        _graph: &mut render_graph::RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), render_graph::NodeRunError> {
+        let pipeline = world.resource::<GpuSpringPipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(solve), Some(integrate)) = (
+            pipeline_cache.get_compute_pipeline(pipeline.solve),
+            pipeline_cache.get_compute_pipeline(pipeline.integrate),
+        ) else {
+            return Ok(());
+        };
+
+        for buffers in world.resource::<GpuNetworks>().0.values() {
+            if !buffers.active {
+                continue;
+            }
+
+            let workgroups = buffers.particles.div_ceil(WORKGROUP_SIZE);
+            let encoder = render_context.command_encoder();
+            {
+                let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor::default());
+                pass.set_pipeline(solve);
+                for iteration in 0..buffers.iterations as usize {
+                    pass.set_bind_group(
+                        0,
+                        &buffers.bind_groups[(buffers.start + iteration) % 2],
+                        &[],
+                    );
+                    pass.dispatch_workgroups(workgroups, 1, 1);
+                }
+
+                let last = (buffers.start + buffers.iterations as usize) % 2;
+                pass.set_pipeline(integrate);
+                pass.set_bind_group(0, &buffers.bind_groups[last], &[]);
+                pass.dispatch_workgroups(workgroups, 1, 1);
+            }
+
+            // The staging buffer can't be copied into while it is being read.
+            if buffers.readback.load(Ordering::Acquire) == READBACK_IDLE {
+                encoder.copy_buffer_to_buffer(
+                    &buffers.positions,
+                    0,
+                    &buffers.staging,
+                    0,
+                    buffers.positions.size(),
+                );
+                buffers.readback.store(READBACK_COPIED, Ordering::Release);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Map the staging buffers the positions were copied into, and hand the
+/// positions of the buffers that finished mapping to the main world.
+///
+/// Mapping never blocks the render thread, so the positions are a frame or
+/// more behind and frames simulated while a buffer is mapped aren't read back.
+fn read_back_gpu_networks(
+    networks: Res<GpuNetworks>,
+    device: Res<RenderDevice>,
+    readback: Res<GpuSpringReadback>,
+) {
+    for (entity, buffers) in &networks.0 {
+        match buffers.readback.load(Ordering::Acquire) {
+            READBACK_COPIED => {
+                buffers.readback.store(READBACK_MAPPING, Ordering::Release);
+                let state = buffers.readback.clone();
+                buffers
+                    .staging
+                    .slice(..)
+                    .map_async(MapMode::Read, move |result| {
+                        let next = match result {
+                            Ok(()) => READBACK_MAPPED,
+                            Err(_) => READBACK_IDLE,
+                        };
+                        state.store(next, Ordering::Release);
+                    });
+            }
+            READBACK_MAPPED => {
+                let positions = {
+                    let data = buffers.staging.slice(..).get_mapped_range();
+                    bytemuck::cast_slice::<u8, [f32; 4]>(&data)
+                        .iter()
+                        .map(|position| Vec4::from_array(*position).truncate())
+                        .collect::<Vec<_>>()
+                };
+                buffers.staging.unmap();
+                buffers.readback.store(READBACK_IDLE, Ordering::Release);
+
+                if let Ok(mut readback) = readback.0.lock() {
+                    readback.push((*entity, positions));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Run the callbacks of mappings that already finished without waiting
+    // for the rest.
+    device.poll(Maintain::Poll);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{
+        app::PluginsState, tasks::tick_global_task_pools_on_main_thread, time::TimeUpdateStrategy,
+        window::ExitCondition, winit::WinitPlugin,
+    };
+
+    use super::*;
+
+    const TIMESTEP: f32 = 1.0 / 60.0;
+    const FRAMES: usize = 120;
+
+    /// Chain swinging down from a pinned particle.
+    fn swinging_chain() -> GpuSpringNetwork {
+        let mut network = GpuSpringNetwork::new(Vec3::new(0.0, -9.81, 0.0)).with_iterations(4);
+        let mut previous = network.add_particle(Vec3::ZERO, f32::INFINITY);
+        for link in 1..=4 {
+            let particle = network.add_particle(Vec3::X * link as f32, 1.0);
+            network.add_spring(previous, particle, Spring::new(0.3, 0.2));
+            previous = particle;
+        }
+        network
+    }
+
+    fn max_distance(a: &[Vec3], b: &[Vec3]) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.distance(*b))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn cpu_reference_keeps_pinned_particles() {
+        let mut network = swinging_chain();
+        for _ in 0..FRAMES {
+            network.step_cpu(TIMESTEP);
+        }
+
+        assert_eq!(network.positions[0], Vec3::ZERO);
+        assert!(network.positions[4].y < -1.0, "{:?}", network.positions);
+        assert!(network
+            .positions
+            .iter()
+            .all(|position| position.is_finite()));
+    }
+
+    #[test]
+    #[ignore = "needs a GPU"]
+    fn gpu_matches_cpu_reference() {
+        let mut app = App::new();
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+            GpuSpringPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            TIMESTEP,
+        )));
+        while app.plugins_state() == PluginsState::Adding {
+            tick_global_task_pools_on_main_thread();
+        }
+        app.finish();
+        app.cleanup();
+
+        let network = app.world_mut().spawn(swinging_chain()).id();
+        for _ in 0..FRAMES {
+            app.update();
+        }
+        let gpu = app
+            .world()
+            .get::<GpuSpringPositions>(network)
+            .expect("no positions were read back")
+            .0
+            .clone();
+
+        // The readback lags an unknown number of frames behind, so compare
+        // with the closest state of the reference.
+        let mut reference = swinging_chain();
+        let mut error = max_distance(&gpu, &reference.positions);
+        for _ in 0..FRAMES {
+            reference.step_cpu(TIMESTEP);
+            error = error.min(max_distance(&gpu, &reference.positions));
+        }
+        assert!(error < 1e-3, "GPU drifted {error} from the CPU reference");
+    }
+}
@@ -0,0 +1,114 @@
+// Jacobi spring solver for a GpuSpringNetwork.
+//
+// Every invocation owns one particle and gathers the impulses of the springs
+// attached to it, so no two invocations write the same velocity.
+
+struct Params {
+    gravity: vec3<f32>,
+    timestep: f32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+// xyz is the position, w the inverse mass.
+@group(0) @binding(1) var<storage, read_write> positions: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read> velocities_in: array<vec4<f32>>;
+@group(0) @binding(3) var<storage, read_write> velocities_out: array<vec4<f32>>;
+// Particles of each spring.
+@group(0) @binding(4) var<storage, read> spring_particles: array<vec2<u32>>;
+// Two per spring: the rest length, strength, damp ratio and damp mode (0 for
+// full, 1 for axial), then the strength and damp ratio while compressed and
+// the distance and extra strength of the soft limit.
+@group(0) @binding(5) var<storage, read> spring_settings: array<vec4<f32>>;
+// Range of `adjacency` holding the springs of each particle.
+@group(0) @binding(6) var<storage, read> offsets: array<u32>;
+@group(0) @binding(7) var<storage, read> adjacency: array<u32>;
+
+// Strength added past the soft limit, matching `SoftLimit::strength`.
+fn soft_limit_strength(stretch: f32, distance: f32, extra_strength: f32) -> f32 {
+    if stretch <= distance {
+        return 0.0;
+    }
+    if distance <= 0.0 {
+        return extra_strength;
+    }
+
+    let t = min((stretch - distance) / distance, 1.0);
+    return extra_strength * t * t * (3.0 - 2.0 * t);
+}
+
+@compute @workgroup_size(64)
+fn solve(@builtin(global_invocation_id) id: vec3<u32>) {
+    let particle = id.x;
+    if particle >= arrayLength(&positions) {
+        return;
+    }
+
+    let inverse_mass = positions[particle].w;
+    let velocity = velocities_in[particle].xyz;
+    if inverse_mass == 0.0 {
+        velocities_out[particle] = vec4<f32>(velocity, 0.0);
+        return;
+    }
+
+    let start = offsets[particle];
+    let end = offsets[particle + 1u];
+    var impulse = vec3<f32>(0.0);
+    for (var index = start; index < end; index++) {
+        let spring = adjacency[index];
+        let pair = spring_particles[spring];
+        let other = select(pair.x, pair.y, pair.x == particle);
+        let settings = spring_settings[spring * 2u];
+        let limits = spring_settings[spring * 2u + 1u];
+
+        let reduced_mass = 1.0 / (inverse_mass + positions[other].w);
+        let offset = positions[particle].xyz - positions[other].xyz;
+        let span = length(offset);
+        var direction = vec3<f32>(0.0);
+        if span > 0.0 {
+            direction = offset / span;
+        }
+
+        // Same as `Spring::at_stretch` followed by `Spring::impulse`.
+        let stretch = span - settings.x;
+        var strength = settings.y;
+        var damp_ratio = settings.z;
+        if stretch < 0.0 {
+            strength = limits.x;
+            damp_ratio = limits.y;
+        }
+        strength = clamp(strength + soft_limit_strength(stretch, limits.z, limits.w), 0.0, 1.0);
+        let damping = clamp(clamp(damp_ratio, 0.0, 20.0) * 2.0 * sqrt(strength), 0.0, 1.0);
+
+        let displacement = direction * stretch;
+        var relative_velocity = velocity - velocities_in[other].xyz;
+        if settings.w > 0.5 {
+            relative_velocity = direction * dot(relative_velocity, direction);
+        }
+        impulse -= (displacement * strength / params.timestep + relative_velocity * damping)
+            * reduced_mass;
+    }
+
+    // Average the impulses so particles with many springs don't overshoot.
+    let springs = max(f32(end - start), 1.0);
+    velocities_out[particle] = vec4<f32>(velocity + impulse * inverse_mass / springs, 0.0);
+}
+
+@compute @workgroup_size(64)
+fn integrate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let particle = id.x;
+    if particle >= arrayLength(&positions) {
+        return;
+    }
+
+    let inverse_mass = positions[particle].w;
+    var velocity = velocities_in[particle].xyz;
+    if inverse_mass > 0.0 {
+        velocity += params.gravity * params.timestep;
+    }
+
+    velocities_out[particle] = vec4<f32>(velocity, 0.0);
+    positions[particle] = vec4<f32>(
+        positions[particle].xyz + velocity * params.timestep,
+        inverse_mass,
+    );
+}
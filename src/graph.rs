@@ -0,0 +1,362 @@
+use std::ops::Range;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::batch::{SpringBatch, LANES};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::island::SpringIslands;
+use crate::joint::{clamp_stable, JointImpulse, SpringJoint, SpringPair, SpringState};
+use crate::plugin::{SolveOrder, SpringConfig, SpringSchedule, SpringSet};
+use crate::timestep::SpringTimestep;
+use crate::torsion::torsion_spring;
+use crate::*;
+
+/// Solve springs from a flat [`SpringGraph`] instead of per entity component
+/// access, for scenes with many thousands of springs.
+///
+/// The topology of the graph is synced from the components whenever the
+/// [`SpringIslands`] are rebuilt once per step, the particle state is gathered
+/// once per substep and the springs are then solved in a tight loop over
+/// contiguous arrays, [`LANES`] springs that share no particles at a time.
+/// Replaces the parallel island solver of
+/// [`spring_impulse`](crate::joint::spring_impulse).
+///
+/// Springs are solved in the order of their colors, so
+/// [`SolveOrder::FromAnchors`](crate::plugin::SolveOrder) has no effect and
+/// warns once. [`SpringConfig::stability_clamp`] is applied when the graph is
+/// synced.
+#[derive(Default)]
+pub struct SpringGraphPlugin;
+
+impl Plugin for SpringGraphPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpringGraph>().add_systems(
+            SpringSchedule,
+            (
+                sync_spring_graph.run_if(resource_changed::<SpringIslands>),
+                solve_spring_graph,
+            )
+                .chain()
+                .before(torsion_spring)
+                .in_set(SpringSet::Solve),
+        );
+    }
+}
+
+/// Springs and particles of the [`SpringIslands`] in a struct of arrays layout.
+///
+/// Springs refer to their particles by index, so solving never goes through
+/// the entity lookups of a query. Springs are sorted into colors that share
+/// no particles, so the springs of a color can be solved as a [`SpringBatch`]
+/// with the same result as solving them one after another.
+#[derive(Default, Debug, Clone, Resource)]
+pub struct SpringGraph {
+    /// Entity of each particle.
+    pub particles: Vec<Entity>,
+    pub transforms: Vec<Transform>,
+    pub velocities: Vec<Velocity>,
+    pub inertias: Vec<Inertia>,
+    pub impulses: Vec<Impulse>,
+
+    /// Entity of each spring.
+    pub springs: Vec<Entity>,
+    pub joints: Vec<SpringJoint>,
+    pub settings: Vec<SpringPair>,
+    /// Index of the particle of the spring entity.
+    pub a: Vec<usize>,
+    /// Index of the particle of the `containing` entity.
+    pub b: Vec<usize>,
+    pub active_times: Vec<f32>,
+    /// End of each color in the spring arrays.
+    pub colors: Vec<usize>,
+    accumulated: Vec<JointImpulse>,
+    indices: HashMap<Entity, usize>,
+}
+
+impl SpringGraph {
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.transforms.clear();
+        self.velocities.clear();
+        self.inertias.clear();
+        self.impulses.clear();
+        self.springs.clear();
+        self.joints.clear();
+        self.settings.clear();
+        self.a.clear();
+        self.b.clear();
+        self.active_times.clear();
+        self.colors.clear();
+        self.accumulated.clear();
+        self.indices.clear();
+    }
+
+    pub fn spring_count(&self) -> usize {
+        self.springs.len()
+    }
+
+    fn particle_index(&mut self, entity: Entity) -> usize {
+        *self.indices.entry(entity).or_insert_with(|| {
+            self.particles.push(entity);
+            self.particles.len() - 1
+        })
+    }
+
+    /// Add the spring of the `spring` entity, indexing both of its particles.
+    pub fn push(
+        &mut self,
+        spring: Entity,
+        joint: SpringJoint,
+        settings: SpringPair,
+        active_time: f32,
+    ) {
+        let a = self.particle_index(spring);
+        let b = self.particle_index(joint.containing);
+        self.springs.push(spring);
+        self.joints.push(joint);
+        self.settings.push(settings);
+        self.a.push(a);
+        self.b.push(b);
+        self.active_times.push(active_time);
+    }
+
+    /// Clamp every spring to its [`Spring::stable_limits`] given the number of
+    /// springs sharing its busiest particle.
+    pub fn clamp_stable(&mut self) {
+        let mut shared = vec![0; self.particles.len()];
+        for spring in 0..self.springs.len() {
+            shared[self.a[spring]] += 1;
+            shared[self.b[spring]] += 1;
+        }
+
+        for spring in 0..self.springs.len() {
+            let count = shared[self.a[spring]].max(shared[self.b[spring]]);
+            self.settings[spring] =
+                clamp_stable(self.springs[spring], self.settings[spring], count);
+        }
+    }
+
+    /// Sort the springs into colors where no two springs share a particle,
+    /// greedily picking the first color free on both particles.
+    pub fn color(&mut self) {
+        let mut particle_colors = vec![Vec::<usize>::new(); self.particles.len()];
+        let mut spring_colors = Vec::with_capacity(self.springs.len());
+        for spring in 0..self.springs.len() {
+            let (a, b) = (self.a[spring], self.b[spring]);
+            let color = (0..)
+                .find(|color| {
+                    !particle_colors[a].contains(color) && !particle_colors[b].contains(color)
+                })
+                .unwrap_or_default();
+            particle_colors[a].push(color);
+            particle_colors[b].push(color);
+            spring_colors.push(color);
+        }
+
+        let mut order = (0..self.springs.len()).collect::<Vec<_>>();
+        order.sort_by_key(|spring| spring_colors[*spring]);
+
+        self.springs = order.iter().map(|spring| self.springs[*spring]).collect();
+        self.joints = order.iter().map(|spring| self.joints[*spring]).collect();
+        self.settings = order.iter().map(|spring| self.settings[*spring]).collect();
+        self.a = order.iter().map(|spring| self.a[*spring]).collect();
+        self.b = order.iter().map(|spring| self.b[*spring]).collect();
+        self.active_times = order
+            .iter()
+            .map(|spring| self.active_times[*spring])
+            .collect();
+
+        self.colors.clear();
+        for (index, spring) in order.iter().enumerate() {
+            let color = spring_colors[*spring];
+            if color >= self.colors.len() {
+                self.colors.push(index);
+            }
+            self.colors[color] = index + 1;
+        }
+    }
+
+    /// Solve every spring `iterations` times against the gathered particle
+    /// state, accumulating into [`Self::impulses`].
+    pub fn solve(&mut self, timestep: f32, iterations: u32) {
+        self.accumulated.clear();
+        self.accumulated
+            .resize(self.springs.len(), JointImpulse::default());
+
+        for _ in 0..iterations {
+            let mut start = 0;
+            for color in 0..self.colors.len() {
+                let end = self.colors[color];
+                for batch_start in (start..end).step_by(LANES) {
+                    self.solve_batch(batch_start..end.min(batch_start + LANES), timestep);
+                }
+                start = end;
+            }
+        }
+    }
+
+    /// Solve up to [`LANES`] springs of the same color at once.
+    fn solve_batch(&mut self, springs: Range<usize>, timestep: f32) {
+        let mut batch = SpringBatch::default();
+        let mut angular = [Vec3::ZERO; LANES];
+
+        for (lane, spring) in springs.clone().enumerate() {
+            let (a, b) = (self.a[spring], self.b[spring]);
+            let joint = &self.joints[spring];
+            let accumulated = self.accumulated[spring];
+
+            // Remove this spring's impulse from the previous iteration so it
+            // is solved against the other springs' impulses only.
+            self.impulses[a].linear -= accumulated.linear;
+            self.impulses[a].angular -= accumulated.angular;
+            self.impulses[b].linear += accumulated.linear;
+            self.impulses[b].angular += accumulated.angular;
+
+            let (particle_a, particle_b) = joint.particles(
+                (&self.transforms[a], &self.velocities[a], &self.inertias[a]),
+                &self.impulses[a],
+                (&self.transforms[b], &self.velocities[b], &self.inertias[b]),
+                &self.impulses[b],
+            );
+            let settings = self.settings[spring].scaled(joint.ramp(self.active_times[spring]));
+            if joint.axes.linear() {
+                if let Some(spring) =
+                    joint.stretched_linear_spring(&settings, &particle_a, &particle_b)
+                {
+                    batch.set(
+                        lane,
+                        spring,
+                        &joint.linear_instant(&particle_a, &particle_b),
+                    );
+                }
+            }
+            angular[lane] =
+                JointImpulse::angular(&settings, joint, timestep, &particle_a, &particle_b);
+        }
+
+        let linear = batch.impulses(timestep);
+        for (lane, spring) in springs.enumerate() {
+            let (a, b) = (self.a[spring], self.b[spring]);
+            let impulse = JointImpulse {
+                linear: linear[lane],
+                angular: angular[lane],
+            };
+
+            self.impulses[a].linear += impulse.linear;
+            self.impulses[a].angular += impulse.angular;
+            self.impulses[b].linear -= impulse.linear;
+            self.impulses[b].angular -= impulse.angular;
+            self.accumulated[spring] = impulse;
+        }
+    }
+}
+
+/// Rebuild the topology of the [`SpringGraph`] from the springs in the
+/// [`SpringIslands`].
+pub fn sync_spring_graph(
+    mut graph: ResMut<SpringGraph>,
+    config: Res<SpringConfig>,
+    islands: Res<SpringIslands>,
+    springs: Query<(
+        &SpringJoint,
+        AnyOf<(&Spring, &SpringPair)>,
+        Option<&SpringState>,
+    )>,
+) {
+    graph.clear();
+
+    if config.solve_order == SolveOrder::FromAnchors {
+        warn_once!(
+            "SolveOrder::FromAnchors is ignored by the SpringGraph, springs are solved by color"
+        );
+    }
+
+    for spring in islands.springs() {
+        let Ok((joint, settings, state)) = springs.get(spring) else {
+            continue;
+        };
+        let Some(settings) = SpringPair::resolve(settings) else {
+            continue;
+        };
+
+        graph.push(
+            spring,
+            *joint,
+            settings,
+            state.map_or(0.0, |state| state.active_time),
+        );
+    }
+
+    if config.stability_clamp {
+        graph.clamp_stable();
+    }
+    graph.color();
+
+    let particles = graph.particles.len();
+    graph.transforms.resize(particles, Transform::IDENTITY);
+    graph.velocities.resize(particles, Velocity::default());
+    graph.inertias.resize(particles, Inertia::default());
+    graph.impulses.resize(particles, Impulse::default());
+}
+
+/// Gather the particle state into the [`SpringGraph`], solve it and write the
+/// impulses and spring states back to the components.
+pub fn solve_spring_graph(
+    timestep: Res<SpringTimestep>,
+    config: Res<SpringConfig>,
+    mut graph: ResMut<SpringGraph>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+    mut states: Query<&mut SpringState>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+    let graph = &mut *graph;
+
+    for index in 0..graph.particles.len() {
+        let entity = graph.particles[index];
+        if let Ok((transform, velocity, inertia)) = particles.get(entity) {
+            graph.transforms[index] = *transform;
+            graph.velocities[index] = *velocity;
+            graph.inertias[index] = *inertia;
+        } else {
+            // Missing particles don't take part in the solve.
+            graph.inertias[index] = Inertia::INFINITY;
+        }
+        graph.impulses[index] = impulses.get(entity).copied().unwrap_or_default();
+    }
+
+    graph.solve(timestep, config.solver_iterations.max(1));
+
+    for (entity, impulse) in graph.particles.iter().zip(&graph.impulses) {
+        if let Ok(mut component) = impulses.get_mut(*entity) {
+            *component = *impulse;
+        }
+    }
+
+    for spring in 0..graph.springs.len() {
+        let Ok(mut state) = states.get_mut(graph.springs[spring]) else {
+            continue;
+        };
+        let (a, b) = (graph.a[spring], graph.b[spring]);
+        let (particle_a, particle_b) = graph.joints[spring].particles(
+            (
+                &graph.transforms[a],
+                &graph.velocities[a],
+                &graph.inertias[a],
+            ),
+            &Impulse::default(),
+            (
+                &graph.transforms[b],
+                &graph.velocities[b],
+                &graph.inertias[b],
+            ),
+            &Impulse::default(),
+        );
+        state.update(&particle_a, &particle_b, graph.accumulated[spring]);
+        state.active_time += timestep;
+        graph.active_times[spring] = state.active_time;
+    }
+}
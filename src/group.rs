@@ -0,0 +1,82 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+/// Groups a spring belongs to as a bitmask, so whole categories of springs
+/// can be toggled at runtime through [`SpringGroups`].
+///
+/// Springs without a group are always enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringGroup(pub u32);
+
+impl SpringGroup {
+    pub const DEFAULT: Self = Self(1);
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// Group for the bit at `index`, there are at most 32 groups.
+    ///
+    /// # Panics
+    ///
+    /// When `index` is 32 or above.
+    pub const fn bit(index: u32) -> Self {
+        assert!(index < 32, "springs only have 32 groups");
+        Self(1 << index)
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl Default for SpringGroup {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Bitmask of the enabled [`SpringGroup`]s, a spring is solved when every
+/// group it belongs to is enabled.
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct SpringGroups {
+    pub enabled: u32,
+}
+
+impl Default for SpringGroups {
+    fn default() -> Self {
+        Self { enabled: u32::MAX }
+    }
+}
+
+impl SpringGroups {
+    pub fn enable(&mut self, group: SpringGroup) {
+        self.enabled |= group.0;
+    }
+
+    pub fn disable(&mut self, group: SpringGroup) {
+        self.enabled &= !group.0;
+    }
+
+    pub fn set(&mut self, group: SpringGroup, enabled: bool) {
+        if enabled {
+            self.enable(group);
+        } else {
+            self.disable(group);
+        }
+    }
+
+    pub fn toggle(&mut self, group: SpringGroup) {
+        self.enabled ^= group.0;
+    }
+
+    pub fn is_enabled(&self, group: SpringGroup) -> bool {
+        self.enabled & group.0 == group.0
+    }
+
+    /// Whether the springs of an entity in `group` are solved, always for
+    /// entities without a group.
+    pub fn is_active(&self, group: Option<&SpringGroup>) -> bool {
+        group.is_none_or(|group| self.is_enabled(*group))
+    }
+}
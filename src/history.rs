@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::joint::{SpringJoint, SpringState};
+use crate::timestep::SpringTimestep;
+
+/// State of a spring at the end of one step, recorded by a [`SpringHistory`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpringSample {
+    /// Elapsed time of the schedule the springs run in, in seconds.
+    pub time: f32,
+    /// Distance between the particles minus the rest length.
+    pub displacement: f32,
+    /// Rate the spring is stretching at, the error the damping works against.
+    pub velocity_error: f32,
+    /// Linear impulse applied to the joint entity.
+    pub impulse: Vec3,
+    /// Angular impulse applied to the joint entity.
+    pub angular_impulse: Vec3,
+}
+
+/// Records the state of the spring on this entity over the last `capacity`
+/// steps, for plotting jittery springs while tuning them.
+#[derive(Debug, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringHistory {
+    pub capacity: usize,
+    pub samples: VecDeque<SpringSample>,
+}
+
+impl Default for SpringHistory {
+    fn default() -> Self {
+        Self::new(300)
+    }
+}
+
+impl SpringHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record a sample, dropping the oldest once full.
+    pub fn push(&mut self, sample: SpringSample) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        while self.samples.len() >= self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Samples from oldest to newest as CSV with a header row, e.g. to write
+    /// to a file and plot in a spreadsheet.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "time,displacement,velocity_error,impulse_x,impulse_y,impulse_z,\
+             angular_impulse_x,angular_impulse_y,angular_impulse_z\n",
+        );
+        for sample in &self.samples {
+            let (impulse, angular) = (sample.impulse, sample.angular_impulse);
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{}",
+                sample.time,
+                sample.displacement,
+                sample.velocity_error,
+                impulse.x,
+                impulse.y,
+                impulse.z,
+                angular.x,
+                angular.y,
+                angular.z,
+            );
+        }
+        csv
+    }
+}
+
+pub fn record_spring_history(
+    time: Res<Time>,
+    timestep: Res<SpringTimestep>,
+    mut springs: Query<(&SpringJoint, &SpringState, &mut SpringHistory)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (joint, state, mut history) in &mut springs {
+        history.push(SpringSample {
+            time: time.elapsed_seconds(),
+            displacement: state.length - joint.rest_length,
+            velocity_error: state.stretch_velocity,
+            impulse: state.impulse,
+            angular_impulse: state.angular_impulse,
+        });
+    }
+}
@@ -0,0 +1,228 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::kinematic::Kinematic;
+use crate::plugin::SpringConfig;
+use crate::timestep::SpringTimestep;
+
+/// Which integration scheme the built-in integrator uses to move particles.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Integrator {
+    /// Velocity is updated from the accumulated impulse and then used to move the position.
+    #[default]
+    SymplecticEuler,
+    /// Position based integration using the previous translation of the particle.
+    ///
+    /// Handles long chains of stiff springs better than symplectic Euler at
+    /// large timesteps. Only the translation is integrated this way, rotation
+    /// still uses symplectic Euler.
+    Verlet,
+}
+
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Velocity {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+impl Velocity {
+    pub fn is_zero(&self) -> bool {
+        self.linear == Vec3::ZERO && self.angular == Vec3::ZERO
+    }
+
+    /// Velocity of a particle with `inertia` once `impulse` is applied.
+    pub fn with_impulse(&self, impulse: &Impulse, inertia: &Inertia) -> Self {
+        Self {
+            linear: self.linear + impulse.linear * inertia.linear.inverse(),
+            angular: self.angular + impulse.angular * inertia.angular.inverse(),
+        }
+    }
+}
+
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Impulse {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+impl Impulse {
+    pub fn is_zero(&self) -> bool {
+        self.linear == Vec3::ZERO && self.angular == Vec3::ZERO
+    }
+}
+
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Inertia {
+    pub linear: f32,
+    pub angular: Vec3,
+}
+
+impl Default for Inertia {
+    fn default() -> Self {
+        Self {
+            linear: 1.0,
+            angular: Vec3::splat(0.05),
+        }
+    }
+}
+
+impl Inertia {
+    pub const INFINITY: Self = Inertia {
+        linear: f32::INFINITY,
+        angular: Vec3::splat(f32::INFINITY),
+    };
+}
+
+/// Translation of the particle at the previous step, used by [`Integrator::Verlet`].
+///
+/// `None` until the particle has been integrated once, in which case the previous
+/// translation is derived from the current velocity.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct PreviousTranslation(pub Option<Vec3>);
+
+/// Components needed for a particle to be moved by the built-in integrator.
+#[derive(Default, Bundle)]
+pub struct ParticleBundle {
+    pub velocity: Velocity,
+    pub impulse: Impulse,
+    pub inertia: Inertia,
+    pub previous_translation: PreviousTranslation,
+}
+
+pub fn integrator_is(integrator: Integrator) -> impl Fn(Res<SpringConfig>) -> bool {
+    move |config: Res<SpringConfig>| config.integrator == integrator
+}
+
+/// Integrate angular velocity into the rotation quaternion.
+pub fn integrate_rotation(rotation: &mut Quat, angular: Vec3, timestep: f32) {
+    let sql = angular.length_squared();
+    if sql > f32::EPSILON {
+        let inv_omega_mag = 1.0 / sql.sqrt();
+        let omega_axis = angular * inv_omega_mag;
+        let omega_angle = inv_omega_mag * sql * timestep;
+        let delta = Quat::from_axis_angle(omega_axis, omega_angle);
+        *rotation = (delta * *rotation).normalize();
+    }
+}
+
+/// Basic symplectic euler integration of the impulse/velocity/position.
+pub fn symplectic_euler(
+    timestep: Res<SpringTimestep>,
+    mut to_integrate: Query<(&mut Transform, &mut Velocity, &mut Impulse, &Inertia)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut position, mut velocity, mut impulse, inertia) in &mut to_integrate {
+        // Leave particles at rest untouched so change detection can skip them.
+        if velocity.is_zero() && impulse.is_zero() {
+            continue;
+        }
+
+        velocity.linear += impulse.linear * inertia.linear.inverse();
+        velocity.angular += impulse.angular * inertia.angular.inverse();
+
+        position.translation += velocity.linear * timestep;
+        integrate_rotation(&mut position.rotation, velocity.angular, timestep);
+
+        impulse.linear = Vec3::ZERO;
+        impulse.angular = Vec3::ZERO;
+    }
+}
+
+/// Position based verlet integration of the impulse/position, velocity is
+/// derived from the change in position.
+pub fn verlet(
+    timestep: Res<SpringTimestep>,
+    mut to_integrate: Query<(
+        &mut Transform,
+        &mut Velocity,
+        &mut Impulse,
+        &Inertia,
+        &mut PreviousTranslation,
+    )>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut position, mut velocity, mut impulse, inertia, mut previous) in &mut to_integrate {
+        let current = position.translation;
+        let previous_translation = previous.0.unwrap_or(current - velocity.linear * timestep);
+        if previous_translation == current && velocity.angular == Vec3::ZERO && impulse.is_zero() {
+            continue;
+        }
+
+        let displacement =
+            current - previous_translation + impulse.linear * inertia.linear.inverse() * timestep;
+
+        position.translation = current + displacement;
+        previous.0 = Some(current);
+        velocity.linear = displacement / timestep;
+
+        velocity.angular += impulse.angular * inertia.angular.inverse();
+        integrate_rotation(&mut position.rotation, velocity.angular, timestep);
+
+        impulse.linear = Vec3::ZERO;
+        impulse.angular = Vec3::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::force::GlobalGravity;
+    use crate::testing::*;
+    use crate::Spring;
+
+    fn falling(integrator: Integrator) -> Vec<Vec3> {
+        let mut app = app(plugin().with_integrator(integrator));
+        app.insert_resource(GlobalGravity::default());
+        let particle = particle(&mut app, Vec3::ZERO, 2.0);
+        (0..60)
+            .map(|_| {
+                step(&mut app, 1);
+                translation(&app, particle)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verlet_falls_like_symplectic_euler() {
+        let euler = falling(Integrator::SymplecticEuler);
+        let verlet = falling(Integrator::Verlet);
+        for (euler, verlet) in euler.iter().zip(&verlet) {
+            assert!(euler.distance(*verlet) < 1e-4, "{euler} != {verlet}");
+        }
+        assert!(verlet[59].y < -4.0);
+    }
+
+    #[test]
+    fn verlet_chain_converges_to_rest_length() {
+        let mut app = app(plugin()
+            .with_integrator(Integrator::Verlet)
+            .with_solver_iterations(4));
+        let chain = chain(&mut app, 3, 1.0, Spring::snappy());
+        app.world_mut()
+            .get_mut::<Transform>(chain[3])
+            .unwrap()
+            .translation = Vec3::X * 5.0;
+
+        step(&mut app, 600);
+        for link in chain.windows(2) {
+            let length = translation(&app, link[0]).distance(translation(&app, link[1]));
+            assert!((length - 1.0).abs() < 0.01, "link rests at {length}");
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::Velocity;
+use crate::plugin::SpringConfig;
+
+/// Renders the `Transform` of a particle blended between the last two spring
+/// steps by the overstep of `Time<Fixed>`, so motion stays smooth when the
+/// springs run in `FixedUpdate` at a lower rate than the frame rate.
+///
+/// Blending lags a step behind the simulation, add [`SpringSmoothing::Extrapolate`]
+/// to predict from the velocity instead.
+///
+/// The springs always step from the actual state, the blended transform is
+/// swapped out before each step. Setting the `Transform` outside of the
+/// springs teleports the particle without blending.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringInterpolation {
+    /// Transform at the step before the last one.
+    pub previous: Option<Transform>,
+    /// Transform at the last step.
+    pub current: Option<Transform>,
+    /// Blended transform last written, to notice the transform being set elsewhere.
+    rendered: Option<Transform>,
+}
+
+impl SpringInterpolation {
+    /// Blend between the last two steps, `overstep` being the fraction of a
+    /// step elapsed since the last one.
+    pub fn blend(&self, overstep: f32) -> Option<Transform> {
+        let (previous, current) = (self.previous?, self.current?);
+        Some(Transform {
+            translation: previous.translation.lerp(current.translation, overstep),
+            rotation: previous.rotation.slerp(current.rotation, overstep),
+            scale: previous.scale.lerp(current.scale, overstep),
+        })
+    }
+
+    /// Predict the transform `seconds` after the last step from the velocity.
+    pub fn extrapolate(&self, velocity: &Velocity, seconds: f32) -> Option<Transform> {
+        let current = self.current?;
+        Some(Transform {
+            translation: current.translation + velocity.linear * seconds,
+            rotation: Quat::from_scaled_axis(velocity.angular * seconds) * current.rotation,
+            ..current
+        })
+    }
+}
+
+/// How a particle with [`SpringInterpolation`] is smoothed between steps.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub enum SpringSmoothing {
+    /// Blend between the last two steps, smooth but a step behind.
+    #[default]
+    Interpolate,
+    /// Predict past the last step from the velocity, for latency sensitive
+    /// visuals like crosshairs and held items, at the cost of overshooting
+    /// when the velocity changes.
+    Extrapolate,
+}
+
+/// Add [`SpringInterpolation`] to every particle when
+/// [`SpringConfig::interpolate`] is enabled.
+pub fn insert_spring_interpolation(
+    mut commands: Commands,
+    config: Res<SpringConfig>,
+    particles: Query<Entity, (With<Velocity>, Without<SpringInterpolation>)>,
+) {
+    if !config.interpolate {
+        return;
+    }
+
+    for entity in &particles {
+        commands
+            .entity(entity)
+            .insert(SpringInterpolation::default());
+    }
+}
+
+/// Swap the blended transform back to the state of the last step before stepping.
+pub fn restore_interpolation(mut particles: Query<(&mut Transform, &mut SpringInterpolation)>) {
+    for (mut transform, mut interpolation) in &mut particles {
+        if interpolation.rendered != Some(*transform) {
+            // Moved by something other than the springs, don't blend from the old state.
+            interpolation.previous = Some(*transform);
+            interpolation.current = Some(*transform);
+        } else if let Some(current) = interpolation.current {
+            transform.set_if_neq(current);
+        }
+
+        interpolation.previous = interpolation.current;
+    }
+}
+
+/// Remember the state of the step that just ran.
+pub fn record_interpolation(mut particles: Query<(&Transform, &mut SpringInterpolation)>) {
+    for (transform, mut interpolation) in &mut particles {
+        interpolation.current = Some(*transform);
+        interpolation.rendered = Some(*transform);
+    }
+}
+
+/// Write the blended transforms for rendering, before transform propagation.
+pub fn interpolate_transforms(
+    time: Res<Time<Fixed>>,
+    mut particles: Query<(
+        &mut Transform,
+        &mut SpringInterpolation,
+        Option<&SpringSmoothing>,
+        Option<&Velocity>,
+    )>,
+) {
+    let overstep = time.overstep_fraction();
+    let overstep_seconds = time.overstep().as_secs_f32();
+    for (mut transform, mut interpolation, smoothing, velocity) in &mut particles {
+        if interpolation.rendered != Some(*transform) {
+            continue;
+        }
+        let blended = match (smoothing.copied().unwrap_or_default(), velocity) {
+            (SpringSmoothing::Extrapolate, Some(velocity)) => {
+                interpolation.extrapolate(velocity, overstep_seconds)
+            }
+            _ => interpolation.blend(overstep),
+        };
+        let Some(blended) = blended else {
+            continue;
+        };
+
+        transform.set_if_neq(blended);
+        interpolation.rendered = Some(blended);
+    }
+}
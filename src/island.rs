@@ -0,0 +1,258 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    reflect::Reflect,
+    utils::{HashMap, HashSet},
+};
+
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Inertia, Velocity};
+use crate::joint::{SpringJoint, SpringPair};
+use crate::plugin::{SolveOrder, SpringConfig};
+use crate::sleep::SpringSleep;
+use crate::Spring;
+
+/// Group of springs that share no particles with springs in any other island.
+#[derive(Default, Debug, Clone, Reflect)]
+pub struct SpringIsland {
+    /// Entities with a [`SpringJoint`] in this island, paired with the
+    /// particle the joint contained when the island was built.
+    ///
+    /// The two entities of a pair are always distinct and both in
+    /// [`particles`](Self::particles).
+    pub springs: Vec<(Entity, Entity)>,
+    /// Every particle connected by the springs of this island.
+    pub particles: Vec<Entity>,
+}
+
+/// Awake and enabled springs partitioned into islands, rebuilt at the start of every step.
+///
+/// Islands have no particles in common, so each of them can be solved on a
+/// separate thread without write conflicts.
+#[derive(Default, Debug, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct SpringIslands {
+    pub islands: Vec<SpringIsland>,
+}
+
+impl SpringIslands {
+    pub fn spring_count(&self) -> usize {
+        self.islands.iter().map(|island| island.springs.len()).sum()
+    }
+
+    /// Every spring that is solved this step.
+    pub fn springs(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.islands
+            .iter()
+            .flat_map(|island| island.springs.iter().map(|(spring, _)| *spring))
+    }
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+pub fn build_islands(
+    mut islands: ResMut<SpringIslands>,
+    groups: Res<SpringGroups>,
+    config: Res<SpringConfig>,
+    springs: Query<(
+        Entity,
+        Ref<SpringJoint>,
+        Option<&SpringSleep>,
+        Option<&SpringGroup>,
+        AnyOf<(Ref<Spring>, Ref<SpringPair>)>,
+    )>,
+    particle_changes: Query<(
+        Option<Ref<Transform>>,
+        Option<Ref<GlobalTransform>>,
+        Option<Ref<Velocity>>,
+    )>,
+    inertias: Query<&Inertia>,
+    mut indices: Local<HashMap<Entity, usize>>,
+    mut roots: Local<HashMap<usize, usize>>,
+) {
+    indices.clear();
+    roots.clear();
+    islands.islands.clear();
+
+    let mut particles = Vec::new();
+    let mut parents = Vec::new();
+    let mut index_of = |entity: Entity, parents: &mut Vec<usize>| {
+        *indices.entry(entity).or_insert_with(|| {
+            particles.push(entity);
+            parents.push(parents.len());
+            parents.len() - 1
+        })
+    };
+
+    let mut joints = Vec::new();
+    let particle_changed = |entity: Entity| {
+        particle_changes
+            .get(entity)
+            .map_or(true, |(transform, global, velocity)| {
+                transform.is_some_and(|c| c.is_changed())
+                    || global.is_some_and(|c| c.is_changed())
+                    || velocity.is_some_and(|c| c.is_changed())
+            })
+    };
+
+    for (spring_entity, joint, sleep, group, (spring, pair)) in &springs {
+        if joint.containing == spring_entity || !joint.is_active(group, &groups) {
+            continue;
+        }
+
+        if sleep.is_some_and(|sleep| sleep.asleep) {
+            continue;
+        }
+
+        // Nothing moved the particles last step, so the spring is at rest and
+        // solving it again with the same inputs would have no effect either.
+        // Impulses are left out since the solver writes them every step,
+        // external impulses still show up as a change in velocity.
+        let changed = !config.skip_unchanged
+            || groups.is_changed()
+            || joint.is_changed()
+            || spring.is_some_and(|c| c.is_changed())
+            || pair.is_some_and(|c| c.is_changed())
+            || particle_changed(spring_entity)
+            || particle_changed(joint.containing);
+
+        let a = index_of(spring_entity, &mut parents);
+        let b = index_of(joint.containing, &mut parents);
+        let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+        if root_a != root_b {
+            parents[root_b] = root_a;
+        }
+
+        joints.push((spring_entity, joint.containing, a, b, changed));
+    }
+
+    // A change propagates through the whole island within a step, e.g. a kick
+    // on one end of a rope reaches the far end, so islands are solved or
+    // skipped as a whole.
+    let mut changed_roots = HashSet::new();
+    for (_, _, a, _, changed) in &joints {
+        if *changed {
+            changed_roots.insert(find(&mut parents, *a));
+        }
+    }
+    joints.retain(|(_, _, a, _, _)| changed_roots.contains(&find(&mut parents, *a)));
+
+    if config.solve_order == SolveOrder::FromAnchors {
+        let depths = anchor_depths(&particles, &joints, &inertias);
+        joints.sort_by_key(|(_, _, a, b, _)| depths[*a].min(depths[*b]));
+    }
+
+    for (spring_entity, containing, index, _, _) in joints {
+        let root = find(&mut parents, index);
+        let island = *roots.entry(root).or_insert_with(|| {
+            islands.islands.push(SpringIsland::default());
+            islands.islands.len() - 1
+        });
+        islands.islands[island]
+            .springs
+            .push((spring_entity, containing));
+    }
+
+    for (index, particle) in particles.into_iter().enumerate() {
+        let root = find(&mut parents, index);
+        if let Some(island) = roots.get(&root) {
+            islands.islands[*island].particles.push(particle);
+        }
+    }
+}
+
+/// Number of springs between every particle and the closest anchor, a
+/// particle with infinite mass, `u32::MAX` for particles not connected to one.
+fn anchor_depths(
+    particles: &[Entity],
+    joints: &[(Entity, Entity, usize, usize, bool)],
+    inertias: &Query<&Inertia>,
+) -> Vec<u32> {
+    let mut neighbors = vec![Vec::new(); particles.len()];
+    for (_, _, a, b, _) in joints {
+        neighbors[*a].push(*b);
+        neighbors[*b].push(*a);
+    }
+
+    let mut depths = vec![u32::MAX; particles.len()];
+    let mut queue = VecDeque::new();
+    for (index, particle) in particles.iter().enumerate() {
+        if inertias
+            .get(*particle)
+            .is_ok_and(|inertia| inertia.linear.is_infinite())
+        {
+            depths[index] = 0;
+            queue.push_back(index);
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        for neighbor in &neighbors[index] {
+            if depths[*neighbor] == u32::MAX {
+                depths[*neighbor] = depths[index] + 1;
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+    depths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+
+    #[test]
+    fn skip_unchanged_solves_whole_island() {
+        let mut plugin = plugin().with_solver_iterations(3);
+        plugin.config.skip_unchanged = true;
+        let mut app = app(plugin);
+        let chain = chain(&mut app, 3, 1.0, Spring::snappy());
+
+        // At rest, so every island is skipped from the second step on.
+        step(&mut app, 2);
+        assert_eq!(app.world().resource::<SpringIslands>().spring_count(), 0);
+
+        app.world_mut()
+            .get_mut::<Velocity>(chain[3])
+            .unwrap()
+            .linear = Vec3::X;
+        step(&mut app, 1);
+        assert_eq!(app.world().resource::<SpringIslands>().spring_count(), 3);
+        assert_ne!(velocity(&app, chain[1]).linear, Vec3::ZERO);
+    }
+
+    #[test]
+    fn connected_springs_share_an_island() {
+        let mut app = app(plugin());
+        let left = chain(&mut app, 2, 1.0, Spring::snappy());
+        let right = chain(&mut app, 2, 1.0, Spring::snappy());
+        let island_count = |app: &App| app.world().resource::<SpringIslands>().islands.len();
+
+        step(&mut app, 1);
+        assert_eq!(island_count(&app), 2);
+
+        // Hang the right chain off the tip of the left one.
+        let bridge = SpringJoint::linear(left[2]).with_rest_length(1.0);
+        app.world_mut()
+            .entity_mut(right[0])
+            .insert((bridge, Spring::snappy()));
+        step(&mut app, 1);
+        assert_eq!(island_count(&app), 1);
+
+        let islands = app.world().resource::<SpringIslands>();
+        assert_eq!(islands.spring_count(), 5);
+        let particles = &islands.islands[0].particles;
+        assert!(left
+            .iter()
+            .chain(&right)
+            .all(|particle| particles.contains(particle)));
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::{
+    animation::animate_targets, prelude::*, reflect::Reflect, render::mesh::skinning::SkinnedMesh,
+    transform::TransformSystem,
+};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Secondary motion for a bone, springing the tip of the bone towards where
+/// the animated pose puts it so tails, hair and accessories lag and wobble.
+///
+/// Only the rotation of the bone is changed, its length is kept.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct JiggleBone {
+    pub spring: Spring,
+    /// Acceleration pulling the tip down, in world space.
+    pub gravity: Vec3,
+    /// Axis the bone points along in its local space.
+    pub axis: Vec3,
+    /// Distance from the bone to the simulated tip.
+    pub length: f32,
+    /// Rotation of the bone before jiggling, sampled every frame from the
+    /// animated pose.
+    pub rest_rotation: Option<Quat>,
+    /// Rotation written to the bone last frame, still there when nothing
+    /// animated the bone since.
+    jiggled: Option<Quat>,
+    tip: Option<Vec3>,
+    velocity: Vec3,
+}
+
+impl Default for JiggleBone {
+    fn default() -> Self {
+        Self {
+            spring: Spring::bouncy(),
+            gravity: Vec3::ZERO,
+            axis: Vec3::Y,
+            length: 1.0,
+            rest_rotation: None,
+            jiggled: None,
+            tip: None,
+            velocity: Vec3::ZERO,
+        }
+    }
+}
+
+impl JiggleBone {
+    pub fn new(spring: Spring) -> Self {
+        Self {
+            spring,
+            ..default()
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_axis(mut self, axis: Vec3, length: f32) -> Self {
+        self.axis = axis;
+        self.length = length;
+        self
+    }
+
+    /// Advance the tip towards `target`, returning the simulated tip kept at
+    /// the same distance from `origin` as the target.
+    pub fn step(&mut self, origin: Vec3, target: Vec3, timestep: f32) -> Vec3 {
+        let tip = self.tip.unwrap_or(target);
+        let particle = TranslationParticle3 {
+            mass: 1.0,
+            translation: tip,
+            velocity: self.velocity,
+        };
+        let anchor = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: target,
+            velocity: Vec3::ZERO,
+        };
+
+        self.velocity += self.spring.impulse(timestep, particle.instant(&anchor));
+        self.velocity += self.gravity * timestep;
+
+        // Keep the tip at the length of the bone, including any scale.
+        let moved = tip + self.velocity * timestep;
+        let direction = (moved - origin)
+            .try_normalize()
+            .unwrap_or((target - origin).normalize_or_zero());
+        let tip = origin + direction * target.distance(origin);
+        self.velocity = (tip - particle.translation) / timestep;
+        self.tip = Some(tip);
+        tip
+    }
+}
+
+/// Tags the joints of this [`SkinnedMesh`] whose [`Name`] is listed with a
+/// copy of `bone`.
+#[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct JiggleBones {
+    pub names: Vec<String>,
+    pub bone: JiggleBone,
+}
+
+pub struct JiggleBonePlugin;
+
+impl Plugin for JiggleBonePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<JiggleBone>()
+            .register_type::<JiggleBones>()
+            .register_type::<SpringClock>()
+            .add_systems(Update, tag_jiggle_bones)
+            .add_systems(
+                PostUpdate,
+                jiggle_bones
+                    .run_if(springs_running)
+                    .after(animate_targets)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn tag_jiggle_bones(
+    mut commands: Commands,
+    meshes: Query<(&SkinnedMesh, &JiggleBones), Or<(Added<SkinnedMesh>, Changed<JiggleBones>)>>,
+    names: Query<&Name, Without<JiggleBone>>,
+) {
+    for (mesh, jiggle) in &meshes {
+        for joint in &mesh.joints {
+            let Ok(name) = names.get(*joint) else {
+                continue;
+            };
+
+            if jiggle
+                .names
+                .iter()
+                .any(|jiggle_name| name.as_str() == jiggle_name)
+            {
+                commands.entity(*joint).insert(jiggle.bone);
+            }
+        }
+    }
+}
+
+pub fn jiggle_bones(
+    clocks: SpringClocks,
+    mut bones: Query<(
+        &mut Transform,
+        &mut JiggleBone,
+        &Parent,
+        Option<&SpringClock>,
+    )>,
+    parents: Query<&GlobalTransform>,
+) {
+    for (mut transform, mut bone, parent, clock) in &mut bones {
+        let timestep = clocks.delta(clock);
+        if timestep <= 0.0 {
+            continue;
+        }
+        let Ok(parent) = parents.get(parent.get()) else {
+            continue;
+        };
+
+        // Animations write the pose every frame, a bone still holding the
+        // rotation jiggled last frame wasn't animated and keeps its rest.
+        if bone.jiggled != Some(transform.rotation) {
+            bone.rest_rotation = Some(transform.rotation);
+        }
+        let rest_rotation = bone.rest_rotation.unwrap_or(transform.rotation);
+        let parent_rotation = parent.compute_transform().rotation;
+        let rest = parent.mul_transform(Transform {
+            rotation: rest_rotation,
+            ..*transform
+        });
+
+        let origin = rest.translation();
+        let rest_direction = rest_rotation * bone.axis;
+        let target = rest.transform_point(bone.axis * bone.length);
+        let tip = bone.step(origin, target, timestep);
+
+        let swing = Quat::from_rotation_arc(
+            (parent_rotation * rest_direction).normalize_or_zero(),
+            (tip - origin).normalize_or_zero(),
+        );
+        transform.rotation = parent_rotation.inverse() * swing * parent_rotation * rest_rotation;
+        bone.jiggled = Some(transform.rotation);
+    }
+}
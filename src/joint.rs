@@ -0,0 +1,1015 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bevy::{
+    ecs::{
+        entity::{EntityHashMap, EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    tasks::ComputeTaskPool,
+};
+
+use crate::adapter::{AccumulateImpulse, ReadInertia, ReadVelocity};
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::island::{SpringIsland, SpringIslands};
+use crate::kinematic::Kinematic;
+use crate::plugin::SpringConfig;
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Which parts of the motion between the particles a [`SpringJoint`] drives.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JointAxes {
+    /// Drive both the translation and the orientation.
+    #[default]
+    All,
+    /// Only drive the translation.
+    Linear,
+    /// Only drive the orientation.
+    Angular,
+}
+
+impl JointAxes {
+    pub fn linear(&self) -> bool {
+        matches!(self, Self::All | Self::Linear)
+    }
+
+    pub fn angular(&self) -> bool {
+        matches!(self, Self::All | Self::Angular)
+    }
+}
+
+/// Restricts the linear correction of a [`SpringJoint`] to part of space.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinearProjection {
+    /// Correct error in every direction.
+    #[default]
+    Free,
+    /// Only correct error along the axis.
+    Axis(Vec3),
+    /// Only correct error within the plane with this normal, e.g. `Vec3::Y`
+    /// for hovercraft that shouldn't be pulled vertically.
+    Plane(Vec3),
+}
+
+impl LinearProjection {
+    pub fn project(&self, vector: Vec3) -> Vec3 {
+        match *self {
+            Self::Free => vector,
+            Self::Axis(axis) => vector.project_onto(axis),
+            Self::Plane(normal) => vector.reject_from(normal),
+        }
+    }
+
+    pub fn project_instant(&self, instant: SpringInstant<Vec3>) -> SpringInstant<Vec3> {
+        SpringInstant {
+            reduced_inertia: instant.reduced_inertia,
+            displacement: self.project(instant.displacement),
+            velocity: self.project(instant.velocity),
+        }
+    }
+}
+
+/// How a [`SpringJoint`] corrects the distance between its particles.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpringMode {
+    /// Soft spring using the settings of the [`Spring`] component.
+    #[default]
+    Soft,
+    /// Inextensible rod, fully correcting the distance error every substep.
+    ///
+    /// With `projection` the positions are also moved back to the rest length
+    /// after integrating, so no drift builds up in long chains.
+    Rigid { projection: bool },
+    /// XPBD position-level spring with `compliance` (the inverse of the
+    /// stiffness) and `damping`, solved after integrating.
+    ///
+    /// Stays stable with very stiff springs and large mass ratios.
+    Xpbd { compliance: f32, damping: f32 },
+}
+
+impl SpringMode {
+    /// XPBD spring with the given stiffness in newtons per meter.
+    pub fn xpbd(stiffness: f32, damping: f32) -> Self {
+        Self::Xpbd {
+            compliance: stiffness.inverse(),
+            damping,
+        }
+    }
+}
+
+/// Spring joint between this entity and the `containing` entity.
+///
+/// The settings of the spring are read from the [`Spring`] or [`SpringPair`]
+/// component on the same entity.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SpringJoint {
+    pub containing: Entity,
+    /// Disabled joints are skipped by the solver while keeping their settings,
+    /// e.g. for a released grapple or an unlocked door.
+    pub enabled: bool,
+    /// Seconds the strength eases in over after the joint is spawned or
+    /// enabled, to avoid yanking distant bodies. Zero applies full strength
+    /// immediately.
+    pub ramp_time: f32,
+    pub axes: JointAxes,
+    pub mode: SpringMode,
+    /// Distance between the particles the linear spring rests at.
+    pub rest_length: f32,
+    pub projection: LinearProjection,
+    /// Local axis the angular spring aligns between the particles.
+    pub align_axis: Vec3,
+    /// Orientation relative to the `containing` entity the angular spring rests at.
+    ///
+    /// Only the aligned axis is driven, so rotations around `align_axis` have no effect.
+    pub rest_rotation: Quat,
+    /// Angle in radians the aligned axis moves freely within around its rest
+    /// direction, e.g. for head-look or a turret, before the angular spring
+    /// engages.
+    pub limp_angle: f32,
+}
+
+impl MapEntities for SpringJoint {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringJoint {
+    pub fn new(containing: Entity) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            ramp_time: 0.0,
+            axes: JointAxes::All,
+            mode: SpringMode::Soft,
+            rest_length: 0.0,
+            projection: LinearProjection::Free,
+            align_axis: Vec3::X,
+            rest_rotation: Quat::IDENTITY,
+            limp_angle: 0.0,
+        }
+    }
+
+    pub fn angular(containing: Entity) -> Self {
+        Self {
+            axes: JointAxes::Angular,
+            ..Self::new(containing)
+        }
+    }
+
+    pub fn linear(containing: Entity) -> Self {
+        Self {
+            axes: JointAxes::Linear,
+            ..Self::new(containing)
+        }
+    }
+
+    /// Inextensible rod of the given length between the particles.
+    pub fn rigid(containing: Entity, length: f32) -> Self {
+        Self {
+            axes: JointAxes::Linear,
+            mode: SpringMode::Rigid { projection: true },
+            rest_length: length,
+            ..Self::new(containing)
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn with_ramp_time(mut self, ramp_time: f32) -> Self {
+        self.ramp_time = ramp_time;
+        self
+    }
+
+    /// Whether the solver should solve this joint, regardless of sleeping.
+    pub fn is_active(&self, group: Option<&SpringGroup>, groups: &SpringGroups) -> bool {
+        self.enabled && groups.is_active(group)
+    }
+
+    /// Fraction of the strength applied after being active for `active_time` seconds.
+    pub fn ramp(&self, active_time: f32) -> f32 {
+        if self.ramp_time <= 0.0 {
+            return 1.0;
+        }
+
+        let t = (active_time / self.ramp_time).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    pub fn with_rest_length(mut self, rest_length: f32) -> Self {
+        self.rest_length = rest_length;
+        self
+    }
+
+    /// Only correct linear error within the plane with this normal.
+    pub fn with_plane(mut self, normal: Vec3) -> Self {
+        self.projection = LinearProjection::Plane(normal);
+        self
+    }
+
+    /// Only correct linear error along this axis.
+    pub fn with_axis(mut self, axis: Vec3) -> Self {
+        self.projection = LinearProjection::Axis(axis);
+        self
+    }
+
+    pub fn with_align_axis(mut self, align_axis: Vec3) -> Self {
+        self.align_axis = align_axis;
+        self
+    }
+
+    pub fn with_rest_rotation(mut self, rest_rotation: Quat) -> Self {
+        self.rest_rotation = rest_rotation;
+        self
+    }
+
+    /// Rest angle in radians around the Z axis, for 2D.
+    pub fn with_rest_angle(self, rest_angle: f32) -> Self {
+        self.with_rest_rotation(Quat::from_rotation_z(rest_angle))
+    }
+
+    pub fn with_limp_angle(mut self, limp_angle: f32) -> Self {
+        self.limp_angle = limp_angle;
+        self
+    }
+
+    /// Transform of the `containing` entity offset by the rest rotation, which
+    /// the angular spring aligns this entity to.
+    pub fn rest_transform(&self, containing: &Transform) -> Transform {
+        Transform {
+            rotation: containing.rotation * self.rest_rotation,
+            ..*containing
+        }
+    }
+
+    /// Linear spring instant between the particles, relative to the rest length
+    /// and restricted by the projection.
+    pub fn linear_instant(&self, a: &JointParticle, b: &JointParticle) -> SpringInstant<Vec3> {
+        let instant = a
+            .translation
+            .instant(&b.translation)
+            .with_rest_length(self.rest_length);
+        self.projection.project_instant(instant)
+    }
+
+    /// Spring solving the linear axes of the joint, `None` when they are
+    /// projected after integration instead.
+    pub fn linear_spring(&self, settings: &SpringPair) -> Option<Spring> {
+        match self.mode {
+            SpringMode::Soft => Some(settings.linear),
+            SpringMode::Rigid { .. } => Some(Spring::rigid()),
+            SpringMode::Xpbd { .. } => None,
+        }
+    }
+
+    /// How far the particles are stretched past the rest length, negative
+    /// when compressed.
+    pub fn stretch(&self, a: &JointParticle, b: &JointParticle) -> f32 {
+        a.translation
+            .translation
+            .distance(b.translation.translation)
+            - self.rest_length
+    }
+
+    /// [`Self::linear_spring`] with the settings for how far the particles
+    /// are stretched, see [`Spring::at_stretch`].
+    pub fn stretched_linear_spring(
+        &self,
+        settings: &SpringPair,
+        a: &JointParticle,
+        b: &JointParticle,
+    ) -> Option<Spring> {
+        self.linear_spring(settings)
+            .map(|spring| spring.at_stretch(self.stretch(a, b)))
+    }
+
+    /// Particles of this entity and the `containing` entity as seen by the joint,
+    /// from their local [`Transform`]s, see [`JointParticle::new`].
+    pub fn particles(
+        &self,
+        (transform_a, velocity_a, inertia_a): (&Transform, &Velocity, &Inertia),
+        impulse_a: &Impulse,
+        (transform_b, velocity_b, inertia_b): (&Transform, &Velocity, &Inertia),
+        impulse_b: &Impulse,
+    ) -> (JointParticle, JointParticle) {
+        (
+            JointParticle::new(
+                transform_a,
+                velocity_a,
+                impulse_a,
+                inertia_a,
+                self.align_axis,
+            ),
+            JointParticle::new(
+                &self.rest_transform(transform_b),
+                velocity_b,
+                impulse_b,
+                inertia_b,
+                self.align_axis,
+            ),
+        )
+    }
+}
+
+/// Separate linear and angular settings of a [`SpringJoint`], used instead of
+/// the [`Spring`] component when present since angular springs almost always
+/// need different tuning.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringPair {
+    pub linear: Spring,
+    pub angular: Spring,
+}
+
+impl SpringPair {
+    pub fn new(linear: Spring, angular: Spring) -> Self {
+        Self { linear, angular }
+    }
+
+    /// Same settings for the linear and angular spring.
+    pub fn uniform(spring: Spring) -> Self {
+        Self::new(spring, spring)
+    }
+
+    /// Both springs with their strength scaled by `scale`.
+    pub fn scaled(self, scale: f32) -> Self {
+        Self {
+            linear: self.linear.scaled(scale),
+            angular: self.angular.scaled(scale),
+        }
+    }
+
+    /// Both springs clamped to their [`Spring::stable_limits`], `None` when
+    /// both are already stable.
+    pub fn clamp_stable(&self, shared: u32) -> Option<Self> {
+        let (linear, angular) = (
+            self.linear.clamp_stable(shared),
+            self.angular.clamp_stable(shared),
+        );
+        if linear.is_none() && angular.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            linear: linear.unwrap_or(self.linear),
+            angular: angular.unwrap_or(self.angular),
+        })
+    }
+
+    /// Settings of a joint with either a [`Spring`] or a [`SpringPair`],
+    /// preferring the [`SpringPair`].
+    pub fn resolve((spring, pair): (Option<&Spring>, Option<&SpringPair>)) -> Option<Self> {
+        pair.copied().or_else(|| spring.copied().map(Self::uniform))
+    }
+}
+
+/// Components of an entity with a spring to another particle.
+///
+/// The entity also needs the particle components from [`ParticleBundle`](crate::integration::ParticleBundle)
+/// to be solved by the built-in solver.
+#[derive(Bundle)]
+pub struct SpringBundle {
+    pub joint: SpringJoint,
+    pub spring: Spring,
+    pub state: SpringState,
+    pub name: Name,
+}
+
+impl SpringBundle {
+    pub fn new(containing: Entity, spring: Spring) -> Self {
+        Self {
+            joint: SpringJoint::new(containing),
+            spring,
+            state: SpringState::default(),
+            name: Name::new("Spring"),
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<std::borrow::Cow<'static, str>>) -> Self {
+        self.name = Name::new(name);
+        self
+    }
+}
+
+/// Particle state of an entity read from the built-in particle components.
+pub struct JointParticle {
+    pub translation: TranslationParticle3,
+    pub angular: AngularParticle3,
+}
+
+impl JointParticle {
+    /// Particle state including the velocity change from impulses accumulated
+    /// so far this step.
+    ///
+    /// Reads the local [`Transform`], which the integrator moves every
+    /// substep, so particles of the built-in solver should not be under
+    /// scaled or moving parents. Rapier bodies are read in world space
+    /// including scale, see `RapierParticleQuery`.
+    pub fn new(
+        transform: &Transform,
+        velocity: &Velocity,
+        impulse: &Impulse,
+        inertia: &Inertia,
+        align_axis: Vec3,
+    ) -> Self {
+        Self {
+            translation: TranslationParticle3 {
+                mass: inertia.linear,
+                translation: transform.translation,
+                velocity: velocity.linear + impulse.linear * inertia.linear.inverse(),
+            },
+            angular: AngularParticle3::new(
+                inertia.angular,
+                transform.rotation,
+                align_axis,
+                velocity.angular + impulse.angular * inertia.angular.inverse(),
+            ),
+        }
+    }
+}
+
+/// Latest state of a spring, written by the solver every substep.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringState {
+    /// Distance between the two particles.
+    pub length: f32,
+    /// Rate of change of the length, positive when the spring is stretching.
+    pub stretch_velocity: f32,
+    /// Linear impulse applied to the joint entity.
+    pub impulse: Vec3,
+    /// Angular impulse applied to the joint entity.
+    pub angular_impulse: Vec3,
+    /// Seconds the spring has been solved since it was spawned or enabled.
+    pub active_time: f32,
+}
+
+impl SpringState {
+    pub fn update(&mut self, a: &JointParticle, b: &JointParticle, impulse: JointImpulse) {
+        let instant = a.translation.instant(&b.translation);
+        self.length = instant.displacement.length();
+        self.stretch_velocity = instant
+            .velocity
+            .dot(instant.displacement.normalize_or_zero());
+        self.impulse = impulse.linear;
+        self.angular_impulse = impulse.angular;
+    }
+}
+
+/// Linear and angular impulse a spring applies to the joint entity, the
+/// containing entity receives the opposite.
+#[derive(Default, Debug, Copy, Clone)]
+pub struct JointImpulse {
+    pub linear: Vec3,
+    pub angular: Vec3,
+}
+
+impl JointImpulse {
+    pub fn new(
+        springs: &SpringPair,
+        joint: &SpringJoint,
+        timestep: f32,
+        a: &JointParticle,
+        b: &JointParticle,
+    ) -> Self {
+        let mut impulse = Self::default();
+
+        if joint.axes.linear() {
+            if let Some(spring) = joint.stretched_linear_spring(springs, a, b) {
+                impulse.linear = spring.impulse(timestep, joint.linear_instant(a, b));
+            }
+        }
+
+        impulse.angular = Self::angular(springs, joint, timestep, a, b);
+        impulse
+    }
+
+    /// Angular part of the impulse only, zero when the joint has no angular axes.
+    pub fn angular(
+        springs: &SpringPair,
+        joint: &SpringJoint,
+        timestep: f32,
+        a: &JointParticle,
+        b: &JointParticle,
+    ) -> Vec3 {
+        if !joint.axes.angular() {
+            return Vec3::ZERO;
+        }
+
+        let angular_instant = a.angular.instant(&b.angular);
+        match angular_instant.outside_limp(joint.limp_angle) {
+            Some(instant) => -springs.angular.impulse(timestep, instant),
+            None => Vec3::ZERO,
+        }
+    }
+}
+
+/// Sequential impulse solver over every [`SpringJoint`].
+///
+/// Each iteration re-reads the velocities with the impulses accumulated so far,
+/// so springs sharing a particle converge towards a combined solution. Every
+/// island of [`SpringIslands`] is solved on a separate task.
+///
+/// Generic over the components of the particles, see [`adapter`](crate::adapter),
+/// the [`SpringPlugin`](crate::plugin::SpringPlugin) solves over
+/// [`Velocity`], [`Inertia`] and [`Impulse`].
+pub fn spring_impulse<V: ReadVelocity, I: ReadInertia, A: AccumulateImpulse>(
+    timestep: Res<SpringTimestep>,
+    config: Res<SpringConfig>,
+    islands: Res<SpringIslands>,
+    impulses: Query<&mut A>,
+    springs: Query<(&SpringJoint, AnyOf<(&Spring, &SpringPair)>)>,
+    states: Query<&mut SpringState>,
+    particles: Query<(&Transform, &V, I::Data)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+    let iterations = config.solver_iterations.max(1);
+    let stability_clamp = config.stability_clamp;
+
+    ComputeTaskPool::get().scope(|scope| {
+        for island in &islands.islands {
+            let (impulses, springs, states, particles) = (&impulses, &springs, &states, &particles);
+            scope.spawn(async move {
+                solve_island::<V, I, A>(
+                    island,
+                    timestep,
+                    iterations,
+                    stability_clamp,
+                    impulses,
+                    springs,
+                    states,
+                    particles,
+                );
+            });
+        }
+    });
+}
+
+fn solve_island<V: ReadVelocity, I: ReadInertia, A: AccumulateImpulse>(
+    island: &SpringIsland,
+    timestep: f32,
+    iterations: u32,
+    stability_clamp: bool,
+    impulses: &Query<&mut A>,
+    springs: &Query<(&SpringJoint, AnyOf<(&Spring, &SpringPair)>)>,
+    states: &Query<&mut SpringState>,
+    particles: &Query<(&Transform, &V, I::Data)>,
+) {
+    let mut accumulated = vec![JointImpulse::default(); island.springs.len()];
+    let shared = stability_clamp.then(|| shared_springs(island));
+
+    for _ in 0..iterations {
+        for (&(spring_entity, particle_entity), accumulated) in
+            island.springs.iter().zip(accumulated.iter_mut())
+        {
+            let Ok((joint, settings)) = springs.get(spring_entity) else {
+                continue;
+            };
+            // The joint was retargeted since the island was built, the new
+            // particle may belong to another island so leave it to the next step.
+            if joint.containing != particle_entity {
+                continue;
+            }
+            let Some(settings) = SpringPair::resolve(settings) else {
+                continue;
+            };
+            let active_time = states
+                .get(spring_entity)
+                .map_or(0.0, |state| state.active_time);
+            let settings = settings.scaled(joint.ramp(active_time));
+            let settings = match &shared {
+                Some(shared) => {
+                    let count = shared[&spring_entity].max(shared[&particle_entity]);
+                    clamp_stable(spring_entity, settings, count)
+                }
+                None => settings,
+            };
+
+            let Ok((transform_a, velocity_a, inertia_a)) = particles.get(spring_entity) else {
+                continue;
+            };
+            let Ok((transform_b, velocity_b, inertia_b)) = particles.get(particle_entity) else {
+                continue;
+            };
+            let (velocity_a, inertia_a) = (velocity_a.velocity(), I::inertia(inertia_a));
+            let (velocity_b, inertia_b) = (velocity_b.velocity(), I::inertia(inertia_b));
+
+            // SAFETY: `build_islands` only pairs distinct entities, and both are
+            // particles of this island. Islands are disjoint, so no other task
+            // accesses these impulses, and the entities come from the island
+            // rather than the joint, which could have changed since.
+            let (Ok(mut spring_impulse), Ok(mut particle_impulse)) = (unsafe {
+                (
+                    impulses.get_unchecked(spring_entity),
+                    impulses.get_unchecked(particle_entity),
+                )
+            }) else {
+                continue;
+            };
+
+            // Remove this spring's impulse from the previous iteration so it is
+            // solved against the other springs' impulses only.
+            spring_impulse.accumulate(-accumulated.linear, -accumulated.angular);
+            particle_impulse.accumulate(accumulated.linear, accumulated.angular);
+
+            let (a, b) = joint.particles(
+                (transform_a, &velocity_a, &inertia_a),
+                &spring_impulse.impulse(),
+                (transform_b, &velocity_b, &inertia_b),
+                &particle_impulse.impulse(),
+            );
+            let impulse = JointImpulse::new(&settings, joint, timestep, &a, &b);
+
+            spring_impulse.accumulate(impulse.linear, impulse.angular);
+            particle_impulse.accumulate(-impulse.linear, -impulse.angular);
+            *accumulated = impulse;
+        }
+    }
+
+    for (&(spring_entity, particle_entity), accumulated) in island.springs.iter().zip(accumulated) {
+        // SAFETY: `build_islands` adds every spring entity to exactly one
+        // island, and only once.
+        let Ok(mut state) = (unsafe { states.get_unchecked(spring_entity) }) else {
+            continue;
+        };
+        let Ok((joint, _)) = springs.get(spring_entity) else {
+            continue;
+        };
+        if joint.containing != particle_entity {
+            continue;
+        }
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(spring_entity), particles.get(particle_entity))
+        else {
+            continue;
+        };
+
+        let (a, b) = joint.particles(
+            (transform_a, &velocity_a.velocity(), &I::inertia(inertia_a)),
+            &Impulse::default(),
+            (transform_b, &velocity_b.velocity(), &I::inertia(inertia_b)),
+            &Impulse::default(),
+        );
+        state.update(&a, &b, accumulated);
+        state.active_time += timestep;
+    }
+}
+
+/// Number of springs of the island pulling on each of its particles.
+fn shared_springs(island: &SpringIsland) -> EntityHashMap<u32> {
+    let mut shared = EntityHashMap::default();
+    for (spring_entity, particle_entity) in &island.springs {
+        *shared.entry(*spring_entity).or_default() += 1;
+        *shared.entry(*particle_entity).or_default() += 1;
+    }
+    shared
+}
+
+static WARNED_UNSTABLE: AtomicBool = AtomicBool::new(false);
+
+/// Clamp the settings of a spring to stay stable, warning the first time a
+/// spring needs it.
+pub(crate) fn clamp_stable(entity: Entity, settings: SpringPair, shared: u32) -> SpringPair {
+    let Some(clamped) = settings.clamp_stable(shared) else {
+        return settings;
+    };
+
+    if !WARNED_UNSTABLE.swap(true, Ordering::Relaxed) {
+        warn!(
+            "spring {:?} is too strong to stay stable with {} springs sharing a particle, clamped from {:?} to {:?}",
+            entity, shared, settings, clamped
+        );
+    }
+    clamped
+}
+
+/// Restart the strength ramp of joints that are disabled directly or through their group.
+pub fn reset_inactive_springs(
+    groups: Res<SpringGroups>,
+    mut springs: Query<(&SpringJoint, Option<&SpringGroup>, &mut SpringState)>,
+) {
+    for (joint, group, mut state) in &mut springs {
+        if !joint.is_active(group, &groups) && state.active_time != 0.0 {
+            state.active_time = 0.0;
+        }
+    }
+}
+
+pub fn insert_spring_state(
+    mut commands: Commands,
+    springs: Query<Entity, (With<SpringJoint>, Without<SpringState>)>,
+) {
+    for entity in &springs {
+        commands.entity(entity).insert(SpringState::default());
+    }
+}
+
+/// Move the particles of rigid joints with projection back to their rest length.
+pub fn project_rigid_joints(
+    islands: Res<SpringIslands>,
+    joints: Query<&SpringJoint>,
+    inertias: Query<&Inertia>,
+    mut transforms: Query<&mut Transform>,
+) {
+    for entity in islands.springs() {
+        let Ok(joint) = joints.get(entity) else {
+            continue;
+        };
+        if !joint.axes.linear() || joint.mode != (SpringMode::Rigid { projection: true }) {
+            continue;
+        }
+
+        let (Ok(inertia_a), Ok(inertia_b)) = (inertias.get(entity), inertias.get(joint.containing))
+        else {
+            continue;
+        };
+        let (weight_a, weight_b) = (inertia_a.linear.inverse(), inertia_b.linear.inverse());
+        let total_weight = weight_a + weight_b;
+        if total_weight <= 0.0 {
+            continue;
+        }
+
+        let Ok([mut transform_a, mut transform_b]) =
+            transforms.get_many_mut([entity, joint.containing])
+        else {
+            continue;
+        };
+
+        let offset = transform_a.translation - transform_b.translation;
+        let error = joint
+            .projection
+            .project(offset.normalize_or_zero() * (offset.length() - joint.rest_length));
+
+        transform_a.translation -= error * (weight_a / total_weight);
+        transform_b.translation += error * (weight_b / total_weight);
+    }
+}
+
+/// Position-level solve of joints with [`SpringMode::Xpbd`], moving the
+/// particles and updating their velocities to match.
+pub fn solve_xpbd_joints(
+    timestep: Res<SpringTimestep>,
+    islands: Res<SpringIslands>,
+    joints: Query<&SpringJoint>,
+    inertias: Query<&Inertia>,
+    mut particles: Query<(&mut Transform, &mut Velocity)>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for entity in islands.springs() {
+        let Ok(joint) = joints.get(entity) else {
+            continue;
+        };
+        let SpringMode::Xpbd {
+            compliance,
+            damping,
+        } = joint.mode
+        else {
+            continue;
+        };
+        if !joint.axes.linear() {
+            continue;
+        }
+
+        let (Ok(inertia_a), Ok(inertia_b)) = (inertias.get(entity), inertias.get(joint.containing))
+        else {
+            continue;
+        };
+        let (weight_a, weight_b) = (inertia_a.linear.inverse(), inertia_b.linear.inverse());
+
+        let Ok([(mut transform_a, mut velocity_a), (mut transform_b, mut velocity_b)]) =
+            particles.get_many_mut([entity, joint.containing])
+        else {
+            continue;
+        };
+
+        let offset = joint
+            .projection
+            .project(transform_a.translation - transform_b.translation);
+        let length = offset.length();
+        let normal = offset.normalize_or_zero();
+        if normal == Vec3::ZERO {
+            continue;
+        }
+
+        let error = length - joint.rest_length;
+        let alpha = compliance / (timestep * timestep);
+        let gamma = alpha * damping / timestep;
+        let relative_velocity = normal.dot(velocity_a.linear - velocity_b.linear);
+
+        let denominator = (1.0 + gamma) * (weight_a + weight_b) + alpha;
+        if denominator <= 0.0 {
+            continue;
+        }
+        let lambda = (-error - gamma * relative_velocity * timestep) / denominator;
+
+        let correction = normal * lambda;
+        transform_a.translation += correction * weight_a;
+        transform_b.translation -= correction * weight_b;
+        velocity_a.linear += correction * weight_a / timestep;
+        velocity_b.linear -= correction * weight_b / timestep;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::scene::DynamicScene;
+
+    use super::*;
+    use crate::damper::Damper;
+    use crate::dangling::SprungBy;
+    use crate::gear::GearSpring;
+    use crate::motor::SpringMotor;
+    use crate::multi::{SpringTo, Springs};
+    use crate::pin::Pinned;
+    use crate::pulley::Pulley;
+    use crate::rope::Rope;
+    use crate::snap::SnapSlot;
+    use crate::testing::*;
+    use crate::torsion::TorsionSpring;
+
+    fn type_registry() -> AppTypeRegistry {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<SpringJoint>();
+            registry.register::<TorsionSpring>();
+            registry.register::<Springs>();
+            registry.register::<Rope>();
+            registry.register::<GearSpring>();
+            registry.register::<Pulley>();
+            registry.register::<Damper>();
+            registry.register::<SpringMotor>();
+            registry.register::<SnapSlot>();
+            registry.register::<SprungBy>();
+            registry.register::<Pinned>();
+        }
+        registry
+    }
+
+    #[test]
+    fn scene_remaps_spring_entities() {
+        let mut world = World::new();
+        world.insert_resource(type_registry());
+        let anchor = world.spawn_empty().id();
+        let link = world
+            .spawn((
+                SpringJoint::linear(anchor),
+                TorsionSpring::new(anchor, Spring::stiff()),
+            ))
+            .id();
+        let hub = world
+            .spawn(Springs::new([
+                SpringTo::new(anchor, Spring::snappy()),
+                SpringTo::new(link, Spring::snappy()),
+            ]))
+            .id();
+        let linked = world
+            .spawn((
+                GearSpring::new(anchor, 1.0, Spring::stiff()),
+                Pulley::new(link, Vec3::Y, 2.0, Spring::stiff()),
+                Damper::new(anchor, 0.5, 0.5),
+                SpringMotor::new(link),
+                SnapSlot {
+                    occupant: Some(link),
+                    ..SnapSlot::new(1.0, Spring::snappy())
+                },
+                Pinned::to(anchor, Vec3::ZERO),
+            ))
+            .id();
+        world
+            .entity_mut(anchor)
+            .insert((Rope::new([link]), SprungBy(vec![link])));
+        let scene = DynamicScene::from_world(&world);
+
+        // Occupy the original entities, so loading has to remap every one.
+        let mut loaded = World::new();
+        loaded.insert_resource(type_registry());
+        loaded.spawn_batch((0..8).map(|_| ()));
+        let mut entity_map = EntityHashMap::default();
+        scene.write_to_world(&mut loaded, &mut entity_map).unwrap();
+
+        let [anchor, link, hub, linked] = [anchor, link, hub, linked].map(|entity| {
+            let mapped = entity_map[&entity];
+            assert_ne!(mapped, entity, "{entity} was not remapped");
+            mapped
+        });
+
+        assert_eq!(loaded.get::<SpringJoint>(link).unwrap().containing, anchor);
+        assert_eq!(
+            loaded.get::<TorsionSpring>(link).unwrap().containing,
+            anchor
+        );
+        let targets: Vec<Entity> = loaded
+            .get::<Springs>(hub)
+            .unwrap()
+            .0
+            .iter()
+            .map(|spring| spring.target)
+            .collect();
+        assert_eq!(targets, [anchor, link]);
+        assert_eq!(loaded.get::<Rope>(anchor).unwrap().links, [link]);
+        assert_eq!(loaded.get::<SprungBy>(anchor).unwrap().0, [link]);
+
+        let linked = loaded.entity(linked);
+        assert_eq!(linked.get::<GearSpring>().unwrap().containing, anchor);
+        assert_eq!(linked.get::<Pulley>().unwrap().containing, link);
+        assert_eq!(linked.get::<Damper>().unwrap().containing, anchor);
+        assert_eq!(linked.get::<SpringMotor>().unwrap().containing, link);
+        assert_eq!(linked.get::<SnapSlot>().unwrap().occupant, Some(link));
+        assert_eq!(linked.get::<Pinned>().unwrap().target, Some(anchor));
+    }
+
+    fn length(app: &App, a: Entity, b: Entity) -> f32 {
+        translation(app, a).distance(translation(app, b))
+    }
+
+    #[test]
+    fn chain_converges_to_rest_length() {
+        let mut app = app(plugin().with_solver_iterations(4));
+        let chain = chain(&mut app, 3, 1.0, Spring::snappy());
+        app.world_mut()
+            .get_mut::<Transform>(chain[3])
+            .unwrap()
+            .translation = Vec3::X * 5.0;
+
+        step(&mut app, 600);
+        for link in chain.windows(2) {
+            let length = length(&app, link[0], link[1]);
+            assert!((length - 1.0).abs() < 0.01, "link rests at {length}");
+        }
+    }
+
+    #[test]
+    fn springs_conserve_momentum() {
+        let mut app = app(plugin().with_solver_iterations(4));
+        let a = particle(&mut app, Vec3::ZERO, 1.0);
+        let joint = SpringJoint::linear(a).with_rest_length(1.0);
+        let b = spring(
+            &mut app,
+            Vec3::new(2.0, 1.0, 0.0),
+            3.0,
+            joint,
+            Spring::bouncy(),
+        );
+        app.world_mut().get_mut::<Velocity>(a).unwrap().linear = Vec3::Y;
+
+        let momentum = |app: &App| velocity(app, a).linear + velocity(app, b).linear * 3.0;
+        let initial = momentum(&app);
+        for _ in 0..120 {
+            step(&mut app, 1);
+            let drift = momentum(&app).distance(initial);
+            assert!(drift < 1e-4, "momentum drifted by {drift}");
+        }
+        assert_ne!(length(&app, a, b), 2.0_f32.hypot(1.0));
+    }
+
+    #[test]
+    fn rigid_joint_keeps_its_length() {
+        let mut app = app(plugin());
+        let anchor = particle(&mut app, Vec3::ZERO, f32::INFINITY);
+        let joint = SpringJoint::rigid(anchor, 1.0);
+        let bob = spring(&mut app, Vec3::X, 1.0, joint, Spring::rigid());
+        app.world_mut().get_mut::<Velocity>(bob).unwrap().linear = Vec3::Y * 5.0;
+
+        for _ in 0..120 {
+            step(&mut app, 1);
+            let length = length(&app, anchor, bob);
+            assert!((length - 1.0).abs() < 1e-3, "rod stretched to {length}");
+        }
+    }
+
+    #[test]
+    fn xpbd_joint_settles_at_rest_length() {
+        let mut app = app(plugin());
+        let anchor = particle(&mut app, Vec3::ZERO, f32::INFINITY);
+        let joint = SpringJoint {
+            mode: SpringMode::xpbd(1000.0, 0.1),
+            ..SpringJoint::linear(anchor).with_rest_length(1.0)
+        };
+        let bob = spring(&mut app, Vec3::X * 2.0, 1.0, joint, Spring::snappy());
+
+        step(&mut app, 600);
+        let length = length(&app, anchor, bob);
+        assert!((length - 1.0).abs() < 0.01, "spring rests at {length}");
+    }
+}
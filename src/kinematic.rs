@@ -4,6 +4,41 @@ use bevy::{
 };
 //use bevy_inspector_egui::prelude::*;
 
+/// Square root used by the springs, routed through `libm` with the `libm`
+/// feature so the result is the same on every platform.
+#[inline]
+pub fn sqrt(value: f32) -> f32 {
+    #[cfg(feature = "libm")]
+    {
+        libm::sqrtf(value)
+    }
+    #[cfg(not(feature = "libm"))]
+    {
+        value.sqrt()
+    }
+}
+
+/// Arc cosine used by the springs, see [`sqrt`].
+#[inline]
+pub fn acos(value: f32) -> f32 {
+    #[cfg(feature = "libm")]
+    {
+        libm::acosf(value)
+    }
+    #[cfg(not(feature = "libm"))]
+    {
+        value.acos()
+    }
+}
+
+/// Angle in radians between two vectors, like `Vec3::angle_between` but
+/// going through [`sqrt`] and [`acos`].
+#[inline]
+pub fn angle_between(a: Vec3, b: Vec3) -> f32 {
+    let cosine = a.dot(b) / sqrt(a.length_squared() * b.length_squared());
+    acos(cosine.clamp(-1.0, 1.0))
+}
+
 pub trait Kinematic:
     std::ops::Sub<Self, Output = Self>
     + std::ops::Add<Self, Output = Self>
@@ -22,6 +57,7 @@ pub trait Kinematic:
     fn normalize_or_zero(self) -> Self;
     fn dot(self, other: Self) -> f32;
     fn inverse(self) -> Self;
+    fn splat(value: f32) -> Self;
 }
 
 impl Kinematic for f32 {
@@ -41,14 +77,22 @@ impl Kinematic for f32 {
             0.0
         }
     }
+    fn splat(value: f32) -> Self {
+        value
+    }
 }
 
 impl Kinematic for Vec2 {
     fn length(self) -> f32 {
-        self.length()
+        sqrt(self.length_squared())
     }
     fn normalize_or_zero(self) -> Self {
-        self.normalize_or_zero()
+        let recip = Kinematic::length(self).recip();
+        if recip.is_finite() && recip > 0.0 {
+            self * recip
+        } else {
+            Vec2::ZERO
+        }
     }
     fn dot(self, other: Self) -> f32 {
         self.dot(other)
@@ -56,14 +100,22 @@ impl Kinematic for Vec2 {
     fn inverse(self) -> Self {
         Vec2::new(self.x.inverse(), self.y.inverse())
     }
+    fn splat(value: f32) -> Self {
+        Vec2::splat(value)
+    }
 }
 
 impl Kinematic for Vec3 {
     fn length(self) -> f32 {
-        self.length()
+        sqrt(self.length_squared())
     }
     fn normalize_or_zero(self) -> Self {
-        self.normalize_or_zero()
+        let recip = Kinematic::length(self).recip();
+        if recip.is_finite() && recip > 0.0 {
+            self * recip
+        } else {
+            Vec3::ZERO
+        }
     }
     fn dot(self, other: Self) -> f32 {
         self.dot(other)
@@ -71,4 +123,7 @@ impl Kinematic for Vec3 {
     fn inverse(self) -> Self {
         Vec3::new(self.x.inverse(), self.y.inverse(), self.z.inverse())
     }
+    fn splat(value: f32) -> Self {
+        Vec3::splat(value)
+    }
 }
@@ -1,3 +1,6 @@
+// Bevy systems take their state as arguments and query tuples.
+#![allow(clippy::type_complexity, clippy::too_many_arguments)]
+
 use bevy::{
     prelude::*,
     reflect::{ Reflect},
@@ -5,21 +8,173 @@ use bevy::{
 //use bevy_inspector_egui::prelude::*;
 
 pub mod prelude {
-    #[cfg(any(feature = "rapier2d", feature = "rapier3d"))]
-    pub use crate::rapier::RapierParticleQuery;
-    pub use crate::Spring;
+    #[cfg(feature = "rapier2d")]
+    pub use crate::rapier2d::RapierParticleQuery2;
+    #[cfg(feature = "rapier3d")]
+    pub use crate::rapier3d::RapierParticleQuery3;
+    // Both backends define these, so they are only in the prelude when unambiguous.
+    #[cfg(all(feature = "rapier2d", not(feature = "rapier3d")))]
+    pub use crate::rapier2d::{
+        BuoyancyPlugin, ClosestPointSpring, ClosestPointSpringPlugin, FireGrapple,
+        GrappleAttached, GrappleAttachment, GrappleBroken, GrappleDetached, GrapplePlugin,
+        GrappleSpring, HoverPlugin, HoverSpring, OutputMode, RapierStateFallbackPlugin,
+        ReleaseGrapple, SpringFieldPlugin, SuspensionPlugin, SuspensionWheel,
+    };
+    #[cfg(all(feature = "rapier3d", not(feature = "rapier2d")))]
+    pub use crate::rapier3d::{
+        BuoyancyPlugin, ClosestPointSpring, ClosestPointSpringPlugin, FireGrapple,
+        GrappleAttached, GrappleAttachment, GrappleBroken, GrappleDetached, GrapplePlugin,
+        GrappleSpring, HoverPlugin, HoverSpring, OutputMode, RapierStateFallbackPlugin,
+        ReleaseGrapple, SpringFieldPlugin, SuspensionPlugin, SuspensionWheel,
+    };
+    pub use crate::adapter::{AccumulateImpulse, ReadInertia, ReadVelocity};
+    #[cfg(feature = "asset")]
+    pub use crate::asset::{SpringAsset, SpringAssetPlugin, SpringSettingsHandle};
+    #[cfg(feature = "blob")]
+    pub use crate::blob::{BlobPressure, SoftBlob2, SoftBlobPlugin};
+    pub use crate::buoyancy::{Buoyancy, WaterHeight};
+    pub use crate::collision::{ColliderShape, CollisionRadius, SpringCollider};
+    pub use crate::commands::{SpringCommandsExt, SpringEntityCommandsExt};
+    pub use crate::damper::Damper;
+    pub use crate::dangling::{
+        DanglingSpringPolicy, SpringLink, SpringTargetLost, SpringTargets, SprungBy,
+    };
+    pub use crate::diagnostic::SpringDiagnosticsPlugin;
+    pub use crate::energy::{SpringEnergy, SpringEnergyDrift, SpringEnergyPlugin, TotalSpringEnergy};
+    pub use crate::field::{FieldMode, SpringField};
+    pub use crate::follow::{SpringFollow, SpringFollowPlugin};
+    pub use crate::force::{ConstantForce, GlobalGravity, Gravity, Wind};
+    pub use crate::gear::GearSpring;
+    #[cfg(feature = "gpu")]
+    pub use crate::gpu::{GpuSpring, GpuSpringNetwork, GpuSpringPlugin, GpuSpringPositions};
+    pub use crate::graph::{SpringGraph, SpringGraphPlugin};
+    pub use crate::group::{SpringGroup, SpringGroups};
+    pub use crate::history::{SpringHistory, SpringSample};
+    pub use crate::integration::{Inertia, Integrator, ParticleBundle};
+    pub use crate::interpolation::{SpringInterpolation, SpringSmoothing};
+    #[cfg(feature = "jiggle")]
+    pub use crate::jiggle::{JiggleBone, JiggleBonePlugin, JiggleBones};
+    pub use crate::joint::{
+        JointAxes, LinearProjection, SpringBundle, SpringJoint, SpringMode, SpringPair,
+    };
+    pub use crate::lifetime::{LifetimeExpiry, SpringExpired, SpringLifetime};
+    pub use crate::motor::SpringMotor;
+    #[cfg(feature = "mouse")]
+    pub use crate::mouse::{MouseSpringPlugin, MouseSpringSettings};
+    pub use crate::multi::{SpringTo, Springs};
+    pub use crate::path::{PathAnchor, PathSpring};
+    pub use crate::pin::{Pinned, PinnedInertia};
+    pub use crate::pulley::Pulley;
+    pub use crate::ragdoll::{AngularLimits, RagdollBuilder};
+    pub use crate::recoil::{RecoilPlugin, RecoilSpring};
+    pub use crate::rollback::{rollback_types, RollbackTypes, SpringSnapshot};
+    pub use crate::rope::{
+        CutRope, Rope, RopeCut, RopeCutter, RopeHit, RopeSlack, RopeState, RopeTaut,
+    };
+    pub use crate::plugin::{SolveOrder, SpringConfig, SpringPlugin, SpringSchedule, SpringSet};
+    pub use crate::sanitize::{NonFinite, SpringQuarantined, SpringSanitizerPlugin};
+    pub use crate::shake::{SpringShake, SpringShakePlugin};
+    pub use crate::snap::{SnapAttached, SnapDetached, SnapKey, SnapSlot, SnapSlotPlugin};
+    #[cfg(feature = "spring_arm")]
+    pub use crate::spring_arm::{SpringArm, SpringArmPlugin};
+    pub use crate::tear::{SpringTorn, Tearable};
+    pub use crate::timestep::{SpringClock, SpringTimestep, SpringsPaused};
+    pub use crate::torsion::{TorsionSpring, WindUp};
+    pub use crate::transform_spring::{TransformSpring, TransformSpringPlugin, TransformTarget};
+    #[cfg(feature = "tuner")]
+    pub use crate::tuner::{SpringTuner, SpringTunerPlugin};
+    #[cfg(feature = "ui")]
+    pub use crate::ui::{UiSpring, UiSpringPlugin};
+    pub use crate::value::{SpringValue, SpringValuePlugin};
+    #[cfg(feature = "visual")]
+    pub use crate::visual::{SpringCoil, SpringVisual, SpringVisualPlugin};
+    pub use crate::winch::{Winch, WinchReeled};
+    pub use crate::{DampMode, SoftLimit, Spring, SpringCompression, StableLimits};
 }
 
-#[cfg(any(feature = "rapier2d", feature = "rapier3d"))]
-pub mod rapier;
+// The backends share the modules in `rapier/`, compiled once against each.
+#[cfg(feature = "rapier2d")]
+#[allow(clippy::duplicate_mod)]
+pub mod rapier2d;
+#[cfg(feature = "rapier3d")]
+#[allow(clippy::duplicate_mod)]
+pub mod rapier3d;
 
-#[cfg(any(feature = "rapier2d", feature = "rapier3d"))]
-pub use rapier::RapierParticleQuery;
+#[cfg(feature = "rapier2d")]
+pub use rapier2d::RapierParticleQuery2;
+#[cfg(feature = "rapier3d")]
+pub use rapier3d::RapierParticleQuery3;
 
+pub mod adapter;
+pub mod analytic;
+#[cfg(feature = "asset")]
+pub mod asset;
+pub mod batch;
+#[cfg(feature = "blob")]
+pub mod blob;
+pub mod buoyancy;
+pub mod collision;
+pub mod commands;
+pub mod damper;
+pub mod dangling;
+pub mod diagnostic;
+pub mod energy;
+pub mod event;
+pub mod field;
+pub mod follow;
+pub mod force;
+pub mod gear;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod graph;
+pub mod group;
+pub mod history;
+pub mod integration;
+pub mod interpolation;
+pub mod island;
+#[cfg(feature = "jiggle")]
+pub mod jiggle;
+pub mod joint;
 pub mod kinematic;
+pub mod lifetime;
+pub mod motor;
+#[cfg(feature = "mouse")]
+pub mod mouse;
+pub mod multi;
+pub mod path;
+pub mod pin;
+pub mod plugin;
+pub mod pulley;
+pub mod ragdoll;
+pub mod recoil;
+pub mod rollback;
+pub mod rope;
+pub mod sanitize;
+pub mod shake;
+pub mod sleep;
+pub mod snap;
+#[cfg(feature = "spring_arm")]
+pub mod spring_arm;
+pub mod tear;
+#[cfg(test)]
+mod testing;
+pub mod timestep;
+pub mod torsion;
+pub mod transform_spring;
+#[cfg(feature = "tuner")]
+pub mod tuner;
+#[cfg(feature = "ui")]
+pub mod ui;
+pub mod value;
+#[cfg(feature = "visual")]
+pub mod visual;
+pub mod winch;
 use kinematic::*;
 
+pub use plugin::{SpringConfig, SpringPlugin};
+
 #[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Component)]
 pub struct Spring {
     /// Strength of the spring-like impulse. This is a range between 0 and 1
@@ -34,10 +189,77 @@ pub struct Spring {
     /// So overshooting *may* happen if you have a really high strength value.
     //#[inspector(min = 0.0, max = 4.0, speed = 0.05)]
     pub damp_ratio: f32,
+    /// Which part of the relative velocity the damping acts on.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub damp_mode: DampMode,
+    /// Settings used instead while the spring is compressed shorter than its
+    /// rest length, the same both ways when `None`.
+    ///
+    /// Only applied through [`Spring::at_stretch`], as joints do, calling
+    /// [`Spring::impulse`] directly ignores it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compression: Option<SpringCompression>,
+    /// Stiffen the spring once stretched past a distance, the same at any
+    /// stretch when `None`.
+    ///
+    /// Only applied through [`Spring::at_stretch`], as joints do, calling
+    /// [`Spring::impulse`] directly ignores it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub soft_limit: Option<SoftLimit>,
+}
+
+/// Strength added to a [`Spring`] stretched past `distance` from its rest
+/// length, ramping up smoothly to `extra_strength` over another `distance`.
+///
+/// Keeps character tethers and ropes from stretching too far without the
+/// jolt of a hard limit.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftLimit {
+    pub distance: f32,
+    pub extra_strength: f32,
+}
+
+impl SoftLimit {
+    /// Strength added at `stretch` past the rest length.
+    pub fn strength(&self, stretch: f32) -> f32 {
+        if stretch <= self.distance {
+            return 0.0;
+        }
+        if self.distance <= 0.0 {
+            return self.extra_strength;
+        }
+
+        let t = ((stretch - self.distance) / self.distance).min(1.0);
+        self.extra_strength * t * t * (3.0 - 2.0 * t)
+    }
+}
+
+/// Strength and damping ratio of a [`Spring`] while compressed, for springs
+/// that are stiff when stretched but soft when compressed like bungee cords,
+/// or the other way around like crash bumpers.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpringCompression {
+    pub strength: f32,
+    pub damp_ratio: f32,
+}
+
+/// Which part of the relative velocity of a spring gets damped.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DampMode {
+    /// Damp all relative velocity, including motion perpendicular to the spring.
+    #[default]
+    Full,
+    /// Only damp velocity along the spring, leaving orbiting and pendulum
+    /// motion untouched.
+    Axial,
 }
 
 /// One dimensional spring particle
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Particle1 {
     /// Resistance the particle has to changes in motion.
     pub inertia: f32,
@@ -61,7 +283,8 @@ impl Particle1 {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TranslationParticle2 {
     /// Resistance the particle has to changes in motion.
     pub mass: f32,
@@ -71,7 +294,8 @@ pub struct TranslationParticle2 {
     pub velocity: Vec2,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AngularParticle2 {
     /// Resistance the particle has to changes in angular motion.
     pub inertia: f32,
@@ -81,7 +305,8 @@ pub struct AngularParticle2 {
     pub velocity: f32,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TranslationParticle3 {
     /// Resistance the particle has to changes in motion.
     pub mass: f32,
@@ -91,16 +316,18 @@ pub struct TranslationParticle3 {
     pub velocity: Vec3,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AngularParticle3 {
     /// Resistance the particle has to changes in angular motion.
     pub inertia: Vec3,
-    /// Current direction of the particle.
+    /// Current direction of the aligned axis of the particle in world space.
     pub direction: Vec3,
     /// Current angular velocity of the particle.
     pub velocity: Vec3,
 }
 
+#[derive(Debug, Copy, Clone)]
 pub struct SpringInstant<K: Kinematic> {
     pub reduced_inertia: K,
     /// Displacement of the spring, which is the relative positions between particles.
@@ -109,6 +336,42 @@ pub struct SpringInstant<K: Kinematic> {
     pub velocity: K,
 }
 
+impl<K: Kinematic> SpringInstant<K> {
+    /// Impulse removing a `damping` fraction of the relative velocity, from 0 to 1,
+    /// without any positional correction.
+    pub fn damping_impulse(&self, damping: f32) -> K {
+        -(self.velocity * self.reduced_inertia * damping.clamp(0.0, 1.0))
+    }
+
+    /// Instant of a spring resting at `rest_length` instead of when the
+    /// particles overlap.
+    pub fn with_rest_length(self, rest_length: f32) -> Self {
+        let length = self.displacement.length();
+        Self {
+            displacement: self.displacement.normalize_or_zero() * (length - rest_length),
+            ..self
+        }
+    }
+
+    /// Instant of a spring that is limp while displaced by at most `limp`,
+    /// `None` while within it so neither the stiffness nor the damping acts.
+    pub fn outside_limp(self, limp: f32) -> Option<Self> {
+        if limp <= 0.0 {
+            return Some(self);
+        }
+
+        let length = self.displacement.length();
+        if length <= limp {
+            return None;
+        }
+
+        Some(Self {
+            displacement: self.displacement.normalize_or_zero() * (length - limp),
+            ..self
+        })
+    }
+}
+
 impl TranslationParticle2 {
     pub fn reduced_mass(&self, other: &Self) -> f32 {
         (self.mass.inverse() + other.mass.inverse()).inverse()
@@ -128,7 +391,19 @@ impl AngularParticle2 {
         (self.inertia.inverse() + other.inertia.inverse()).inverse()
     }
 
+    /// Instant taking the shortest way around, the displacement is wrapped
+    /// into `[-PI, PI]`.
     pub fn instant(&self, other: &Self) -> SpringInstant<f32> {
+        let instant = self.unwrapped_instant(other);
+        SpringInstant {
+            displacement: wrap_angle(instant.displacement),
+            ..instant
+        }
+    }
+
+    /// Instant from the raw difference in rotation, so a spring wound several
+    /// turns unwinds through all of them.
+    pub fn unwrapped_instant(&self, other: &Self) -> SpringInstant<f32> {
         SpringInstant {
             reduced_inertia: self.reduced_inertia(other),
             displacement: self.rotation - other.rotation,
@@ -137,6 +412,12 @@ impl AngularParticle2 {
     }
 }
 
+/// Wrap an angle in radians into `[-PI, PI]`.
+pub fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
 impl TranslationParticle3 {
     pub fn reduced_mass(&self, other: &Self) -> f32 {
         (self.mass.inverse() + other.mass.inverse()).inverse()
@@ -152,13 +433,23 @@ impl TranslationParticle3 {
 }
 
 impl AngularParticle3 {
+    /// Particle aligning the local `axis` of a body with the given rotation,
+    /// e.g. `Vec3::Y` to keep a body upright.
+    pub fn new(inertia: Vec3, rotation: Quat, axis: Vec3, velocity: Vec3) -> Self {
+        Self {
+            inertia,
+            direction: rotation * axis,
+            velocity,
+        }
+    }
+
     pub fn reduced_inertia(&self, other: &Self) -> Vec3 {
         (self.inertia.inverse() + other.inertia.inverse()).inverse()
     }
 
     pub fn instant(&self, other: &Self) -> SpringInstant<Vec3> {
-        let angle = self.direction.angle_between(other.direction);
-        let axis = self.direction.cross(other.direction).normalize_or_zero();
+        let angle = kinematic::angle_between(self.direction, other.direction);
+        let axis = Kinematic::normalize_or_zero(self.direction.cross(other.direction));
         SpringInstant {
             reduced_inertia: self.reduced_inertia(other),
             displacement: axis * angle,
@@ -168,6 +459,128 @@ impl AngularParticle3 {
 }
 
 impl Spring {
+    pub fn new(strength: f32, damp_ratio: f32) -> Self {
+        Self {
+            strength,
+            damp_ratio,
+            ..Default::default()
+        }
+    }
+
+    /// Spring that reaches the target as fast as it can without overshooting.
+    pub fn critically_damped(strength: f32) -> Self {
+        Self::new(strength, 1.0)
+    }
+
+    /// Critically damped spring closing 99% of the gap to its target in
+    /// `duration` seconds when stepped every `timestep` seconds, the way
+    /// camera and cursor smoothing is usually tuned.
+    ///
+    /// Durations shorter than a few steps are limited to full strength.
+    pub fn arrive_in(duration: f32, timestep: f32) -> Self {
+        // Solution of (1 + x) * e^-x = 0.01, the remaining gap of a critically
+        // damped spring after x / omega seconds.
+        const SETTLE: f32 = 6.638352;
+
+        let omega = SETTLE / duration.max(f32::EPSILON);
+        Self::critically_damped((omega * timestep).powi(2).min(1.0))
+    }
+
+    /// Strong spring that settles almost immediately, good for attachments
+    /// that should barely lag behind.
+    pub fn stiff() -> Self {
+        Self::new(0.5, 1.0)
+    }
+
+    /// Under-dampened spring that overshoots and wobbles a few times before settling.
+    pub fn bouncy() -> Self {
+        Self::new(0.1, 0.25)
+    }
+
+    /// Slightly under-dampened spring with a small overshoot, feels snappy
+    /// for UI and held items.
+    pub fn snappy() -> Self {
+        Self::new(0.2, 0.7)
+    }
+
+    /// Weak critically damped spring that lazily trails its target, good for
+    /// cameras and companions.
+    pub fn loose_follow() -> Self {
+        Self::new(0.02, 1.0)
+    }
+
+    /// Full strength critically damped spring, correcting all of the error in one step.
+    pub fn rigid() -> Self {
+        Self::new(1.0, 1.0)
+    }
+
+    /// Weak over-dampened spring that drifts towards the target without any wobble.
+    pub fn sluggish() -> Self {
+        Self::new(0.01, 2.0)
+    }
+
+    pub fn with_damp_mode(mut self, damp_mode: DampMode) -> Self {
+        self.damp_mode = damp_mode;
+        self
+    }
+
+    /// Use a different strength and damping ratio while compressed.
+    pub fn with_compression(mut self, strength: f32, damp_ratio: f32) -> Self {
+        self.compression = Some(SpringCompression {
+            strength,
+            damp_ratio,
+        });
+        self
+    }
+
+    /// Stiffen the spring by up to `extra_strength` once stretched past `distance`.
+    pub fn with_soft_limit(mut self, distance: f32, extra_strength: f32) -> Self {
+        self.soft_limit = Some(SoftLimit {
+            distance,
+            extra_strength,
+        });
+        self
+    }
+
+    /// Settings for a spring stretched `stretch` past its rest length,
+    /// negative when compressed.
+    pub fn at_stretch(&self, stretch: f32) -> Self {
+        let spring = match self.compression {
+            Some(compression) if stretch < 0.0 => Self {
+                strength: compression.strength,
+                damp_ratio: compression.damp_ratio,
+                ..*self
+            },
+            _ => *self,
+        };
+
+        let extra_strength = self
+            .soft_limit
+            .map_or(0.0, |soft_limit| soft_limit.strength(stretch));
+        Self {
+            strength: spring.strength + extra_strength,
+            compression: None,
+            soft_limit: None,
+            ..spring
+        }
+    }
+
+    /// This spring with its strength scaled by `scale`, at any stretch.
+    pub fn scaled(&self, scale: f32) -> Self {
+        Self {
+            strength: self.strength * scale,
+            compression: self.compression.map(|compression| SpringCompression {
+                strength: compression.strength * scale,
+                ..compression
+            }),
+            soft_limit: self.soft_limit.map(|soft_limit| SoftLimit {
+                extra_strength: soft_limit.extra_strength * scale,
+                ..soft_limit
+            }),
+            ..*self
+        }
+    }
+
     pub fn strength(&self) -> f32 {
         self.strength.clamp(0.0, 1.0)
     }
@@ -177,21 +590,188 @@ impl Spring {
     }
 
     pub fn damping(&self) -> f32 {
-        (self.damp_ratio() * 2.0 * self.strength().sqrt()).clamp(0.0, 1.0)
+        (self.damp_ratio() * 2.0 * kinematic::sqrt(self.strength())).clamp(0.0, 1.0)
+    }
+
+    /// Equivalent stiffness of the spring for a given reduced inertia and timestep.
+    pub fn stiffness(&self, reduced_inertia: f32, timestep: f32) -> f32 {
+        self.strength() * reduced_inertia / (timestep * timestep)
+    }
+
+    /// Potential energy stored in the spring at the given displacement.
+    pub fn potential_energy(&self, reduced_inertia: f32, timestep: f32, displacement: f32) -> f32 {
+        0.5 * self.stiffness(reduced_inertia, timestep) * displacement * displacement
+    }
+
+    /// Largest settings this spring can be solved with while up to `shared`
+    /// springs, including this one, pull on one of its particles at once.
+    ///
+    /// A lone spring is stable with any strength, but the corrections of
+    /// springs sharing a particle add up. Semi-implicit Euler stays stable
+    /// while their combined damping removes at most all of the velocity and
+    /// their combined strength is below `4 - 2 * damping`.
+    pub fn stable_limits(&self, shared: u32) -> StableLimits {
+        let shared = shared.max(1) as f32;
+        let damping = self.damping().min(1.0 / shared);
+        StableLimits {
+            strength: ((4.0 - 2.0 * damping * shared) / shared).min(1.0),
+            damping: 1.0 / shared,
+        }
     }
 
+    /// This spring with its strength and damping clamped to its
+    /// [`stable_limits`](Self::stable_limits), `None` when it is already stable.
+    pub fn clamp_stable(&self, shared: u32) -> Option<Self> {
+        let limits = self.stable_limits(shared);
+        if self.strength() <= limits.strength && self.damping() <= limits.damping {
+            return None;
+        }
+
+        let strength = self.strength().min(limits.strength);
+        let root = kinematic::sqrt(strength);
+        let damp_ratio = if root > 0.0 {
+            self.damp_ratio().min(limits.damping / (2.0 * root))
+        } else {
+            self.damp_ratio()
+        };
+        Some(Self {
+            strength,
+            damp_ratio,
+            ..*self
+        })
+    }
+
+    /// Impulse moving the particles of `instant` towards each other.
+    ///
+    /// Uses `strength` and `damp_ratio` as they are and ignores
+    /// [`compression`](Self::compression) and [`soft_limit`](Self::soft_limit),
+    /// the displacement has no sign relative to a rest length. Resolve the settings for the stretch with
+    /// [`Spring::at_stretch`] first, like joints do.
     pub fn impulse<K: Kinematic>(&self, timestep: f32, instant: SpringInstant<K>) -> K {
         let inverse_timestep = 1.0 / timestep;
 
         let unit_vector = instant.displacement.normalize_or_zero();
         let distance_error = unit_vector * instant.displacement.length();
-        let velocity_error = instant.velocity;//.dot(unit_vector);
+        let velocity_error = match self.damp_mode {
+            DampMode::Full => instant.velocity,
+            DampMode::Axial => unit_vector * instant.velocity.dot(unit_vector),
+        };
 
         let distance_impulse =
             distance_error * instant.reduced_inertia * self.strength() * inverse_timestep;
         let velocity_impulse = velocity_error * instant.reduced_inertia * self.damping();
 
-        let impulse = -(distance_impulse + velocity_impulse);
-        impulse
+        -(distance_impulse + velocity_impulse)
+    }
+
+    /// Impulses of many instants of this spring at once, in the same order,
+    /// computed [`LANES`](batch::LANES) at a time by a [`SpringBatch`](batch::SpringBatch).
+    pub fn impulse_batch(&self, timestep: f32, instants: &[SpringInstant<Vec3>]) -> Vec<Vec3> {
+        let mut impulses = Vec::with_capacity(instants.len());
+        for chunk in instants.chunks(batch::LANES) {
+            let mut batch = batch::SpringBatch::default();
+            for (lane, instant) in chunk.iter().enumerate() {
+                batch.set(lane, *self, instant);
+            }
+            impulses.extend_from_slice(&batch.impulses(timestep)[..chunk.len()]);
+        }
+        impulses
+    }
+
+    /// Impulse driving the spring towards a moving setpoint, a PD controller
+    /// with `target_position` as the desired displacement and `target_velocity`
+    /// as the desired relative velocity.
+    ///
+    /// Equivalent to subtracting the target from both particles before calling
+    /// [`Self::impulse`].
+    pub fn pd_impulse<K: Kinematic>(
+        &self,
+        instant: SpringInstant<K>,
+        target_position: K,
+        target_velocity: K,
+        timestep: f32,
+    ) -> K {
+        let instant = SpringInstant {
+            displacement: instant.displacement - target_position,
+            velocity: instant.velocity - target_velocity,
+            ..instant
+        };
+        self.impulse(timestep, instant)
+    }
+
+    /// Roll the spring forward `steps` times from `initial` without touching
+    /// the ECS, returning the displacement after each step, e.g. for tools
+    /// drawing the response curve of settings before they are applied.
+    ///
+    /// The particles are integrated with symplectic Euler like the built-in
+    /// integrator, without any other forces.
+    pub fn simulate<K: Kinematic>(
+        &self,
+        initial: SpringInstant<K>,
+        timestep: f32,
+        steps: usize,
+    ) -> Vec<K> {
+        let inverse_inertia = initial.reduced_inertia.inverse();
+        let mut instant = initial;
+        (0..steps)
+            .map(|_| {
+                let impulse = self.impulse(timestep, instant);
+                instant.velocity = instant.velocity + impulse * inverse_inertia;
+                instant.displacement = instant.displacement + instant.velocity * timestep;
+                instant.displacement
+            })
+            .collect()
     }
 }
+
+/// Largest strength and damping a [`Spring`] stays stable with, from
+/// [`Spring::stable_limits`].
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+pub struct StableLimits {
+    /// Largest [`Spring::strength`].
+    pub strength: f32,
+    /// Largest fraction of the relative velocity removed per step, see [`Spring::damping`].
+    pub damping: f32,
+}
+
+impl StableLimits {
+    /// Largest stiffness in newtons per meter for particles with
+    /// `reduced_inertia` solved over `timestep` seconds.
+    pub fn stiffness(&self, reduced_inertia: f32, timestep: f32) -> f32 {
+        self.strength * reduced_inertia / (timestep * timestep)
+    }
+
+    /// Largest damping coefficient in newton seconds per meter for particles
+    /// with `reduced_inertia` solved over `timestep` seconds.
+    pub fn damping_coefficient(&self, reduced_inertia: f32, timestep: f32) -> f32 {
+        self.damping * reduced_inertia / timestep
+    }
+}
+
+/// Move `current` towards `target` with a critically damped spring, the classic
+/// camera follow helper.
+///
+/// `smooth_time` is roughly the time it takes to reach the target, `velocity`
+/// is the state of the spring and should be kept between calls.
+pub fn smooth_damp<K: Kinematic>(
+    current: K,
+    target: K,
+    velocity: &mut K,
+    smooth_time: f32,
+    timestep: f32,
+) -> K {
+    if timestep <= 0.0 {
+        return current;
+    }
+
+    let omega = 2.0 / smooth_time.max(0.0001);
+    let spring = Spring::critically_damped((omega * timestep).powi(2));
+    let instant = SpringInstant {
+        reduced_inertia: K::splat(1.0),
+        displacement: current - target,
+        velocity: *velocity,
+    };
+
+    *velocity = *velocity + spring.impulse(timestep, instant);
+    current + *velocity * timestep
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::joint::SpringJoint;
+use crate::timestep::SpringTimestep;
+
+/// What happens to a spring once its [`SpringLifetime`] runs out.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LifetimeExpiry {
+    /// Disable the [`SpringJoint`] and remove the [`SpringLifetime`].
+    #[default]
+    Disable,
+    /// Despawn the spring entity.
+    Despawn,
+}
+
+/// Spring that only lasts for a duration, for temporary effects like
+/// knockback recovery or hit reactions.
+///
+/// A [`SpringExpired`] event is sent once the duration has elapsed.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringLifetime {
+    pub duration: Duration,
+    pub elapsed: Duration,
+    pub expiry: LifetimeExpiry,
+}
+
+impl SpringLifetime {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+            expiry: LifetimeExpiry::Disable,
+        }
+    }
+
+    pub fn from_secs(secs: f32) -> Self {
+        Self::new(Duration::from_secs_f32(secs))
+    }
+
+    pub fn despawn(mut self) -> Self {
+        self.expiry = LifetimeExpiry::Despawn;
+        self
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.duration.saturating_sub(self.elapsed)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+impl From<Duration> for SpringLifetime {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringExpired {
+    pub entity: Entity,
+    pub expiry: LifetimeExpiry,
+}
+
+pub fn spring_lifetime(
+    mut commands: Commands,
+    timestep: Res<SpringTimestep>,
+    mut springs: Query<(Entity, &mut SpringLifetime, &mut SpringJoint)>,
+    mut expired_events: EventWriter<SpringExpired>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let delta = Duration::from_secs_f32(timestep.step_delta());
+
+    for (entity, mut lifetime, mut joint) in &mut springs {
+        lifetime.elapsed += delta;
+        if !lifetime.finished() {
+            continue;
+        }
+
+        match lifetime.expiry {
+            LifetimeExpiry::Disable => {
+                joint.enabled = false;
+                commands.entity(entity).remove::<SpringLifetime>();
+            }
+            LifetimeExpiry::Despawn => {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+
+        expired_events.send(SpringExpired {
+            entity,
+            expiry: lifetime.expiry,
+        });
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::dangling::SpringLink;
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Drives the velocity of this entity relative to the `containing` entity
+/// towards a target velocity, instead of driving the displacement to zero.
+///
+/// For powered wheels, conveyors and ragdoll muscles. Targets are in the
+/// local space of the `containing` entity, so a wheel spinning around its
+/// axle keeps spinning around it as the vehicle turns.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SpringMotor {
+    pub containing: Entity,
+    /// Disabled motors are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Target relative linear velocity, `None` leaves the linear velocity free.
+    pub linear_velocity: Option<Vec3>,
+    /// Target relative angular velocity, `None` leaves the angular velocity free.
+    pub angular_velocity: Option<Vec3>,
+    /// Fraction of the velocity error corrected each step, from 0 to 1.
+    pub gain: f32,
+    /// Largest impulse the motor applies each step, linear and angular.
+    pub max_impulse: f32,
+}
+
+impl MapEntities for SpringMotor {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringLink for SpringMotor {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl SpringMotor {
+    pub fn new(containing: Entity) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            linear_velocity: None,
+            angular_velocity: None,
+            gain: 0.5,
+            max_impulse: f32::INFINITY,
+        }
+    }
+
+    pub fn with_linear_velocity(mut self, linear_velocity: Vec3) -> Self {
+        self.linear_velocity = Some(linear_velocity);
+        self
+    }
+
+    pub fn with_angular_velocity(mut self, angular_velocity: Vec3) -> Self {
+        self.angular_velocity = Some(angular_velocity);
+        self
+    }
+
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    pub fn with_max_impulse(mut self, max_impulse: f32) -> Self {
+        self.max_impulse = max_impulse;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Impulse applied to this entity to bring the relative `velocity` of the
+    /// `instant` to `target`, the `containing` entity receives the opposite.
+    pub fn impulse(&self, instant: SpringInstant<Vec3>, target: Vec3) -> Vec3 {
+        let instant = SpringInstant {
+            velocity: instant.velocity - target,
+            ..instant
+        };
+        instant
+            .damping_impulse(self.gain)
+            .clamp_length_max(self.max_impulse)
+    }
+}
+
+pub fn spring_motor(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    motors: Query<(Entity, &SpringMotor, Option<&SpringGroup>)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (entity, motor, group) in &motors {
+        if !motor.enabled || !groups.is_active(group) || motor.containing == entity {
+            continue;
+        }
+
+        let (Ok((_, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(entity), particles.get(motor.containing))
+        else {
+            continue;
+        };
+        let Ok([mut impulse_a, mut impulse_b]) = impulses.get_many_mut([entity, motor.containing])
+        else {
+            continue;
+        };
+
+        // Include the impulses of the springs solved before this one.
+        let velocity_a = velocity_a.with_impulse(&impulse_a, inertia_a);
+        let velocity_b = velocity_b.with_impulse(&impulse_b, inertia_b);
+
+        let mut linear = Vec3::ZERO;
+        if let Some(target) = motor.linear_velocity {
+            let instant = SpringInstant {
+                reduced_inertia: Vec3::splat(
+                    (inertia_a.linear.inverse() + inertia_b.linear.inverse()).inverse(),
+                ),
+                displacement: Vec3::ZERO,
+                velocity: velocity_a.linear - velocity_b.linear,
+            };
+            linear = motor.impulse(instant, transform_b.rotation * target);
+        }
+
+        let mut angular = Vec3::ZERO;
+        if let Some(target) = motor.angular_velocity {
+            let instant = SpringInstant {
+                reduced_inertia: (inertia_a.angular.inverse() + inertia_b.angular.inverse())
+                    .inverse(),
+                displacement: Vec3::ZERO,
+                velocity: velocity_a.angular - velocity_b.angular,
+            };
+            angular = motor.impulse(instant, transform_b.rotation * target);
+        }
+
+        impulse_a.linear += linear;
+        impulse_a.angular += angular;
+        impulse_b.linear -= linear;
+        impulse_b.angular -= angular;
+    }
+}
@@ -0,0 +1,140 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    window::PrimaryWindow,
+};
+
+use crate::integration::{Inertia, ParticleBundle};
+use crate::joint::{SpringJoint, SpringState};
+use crate::*;
+
+/// Settings of the [`MouseSpringPlugin`].
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MouseSpringSettings {
+    /// Button held to drag particles around.
+    pub button: MouseButton,
+    /// Furthest distance from the cursor ray a particle can be grabbed from.
+    pub pick_radius: f32,
+    pub spring: Spring,
+}
+
+impl Default for MouseSpringSettings {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            pick_radius: 1.0,
+            spring: Spring::snappy(),
+        }
+    }
+}
+
+/// Infinite mass particle following the cursor, springing the grabbed
+/// particle along with it.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct MouseAnchor {
+    pub grabbed: Entity,
+    /// Distance along the cursor ray the particle was grabbed at.
+    pub distance: f32,
+}
+
+impl MapEntities for MouseAnchor {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.grabbed = entity_mapper.map_entity(self.grabbed);
+    }
+}
+
+/// Drag particles of the built-in solver around with the mouse, the "mouse
+/// joint" of physics sandboxes.
+///
+/// Clicking grabs the particle closest to the camera under the cursor and
+/// spawns a [`MouseAnchor`] with a linear [`SpringJoint`] to it, the anchor is
+/// despawned again once the button is released.
+pub struct MouseSpringPlugin;
+
+impl Plugin for MouseSpringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MouseSpringSettings>()
+            .register_type::<MouseSpringSettings>()
+            .register_type::<MouseAnchor>()
+            .add_systems(Update, mouse_spring);
+    }
+}
+
+fn cursor_ray(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Ray3d> {
+    let cursor = windows.get_single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = cameras.iter().find(|(camera, _)| camera.is_active)?;
+    camera.viewport_to_world(camera_transform, cursor)
+}
+
+pub fn mouse_spring(
+    mut commands: Commands,
+    settings: Res<MouseSpringSettings>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    particles: Query<(Entity, &GlobalTransform), (With<Inertia>, Without<MouseAnchor>)>,
+    mut anchors: Query<(Entity, &MouseAnchor, &mut Transform)>,
+) {
+    if !buttons.pressed(settings.button) {
+        for (entity, _, _) in &anchors {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let Some(ray) = cursor_ray(&windows, &cameras) else {
+        return;
+    };
+
+    if buttons.just_pressed(settings.button) {
+        let mut closest: Option<(Entity, f32)> = None;
+        for (entity, transform) in &particles {
+            let offset = transform.translation() - ray.origin;
+            let distance = offset.dot(*ray.direction);
+            if distance < 0.0
+                || (offset - *ray.direction * distance).length() > settings.pick_radius
+            {
+                continue;
+            }
+
+            if closest.is_none_or(|(_, closest)| distance < closest) {
+                closest = Some((entity, distance));
+            }
+        }
+
+        if let Some((grabbed, distance)) = closest {
+            commands.spawn((
+                Name::new("Mouse Anchor"),
+                MouseAnchor { grabbed, distance },
+                TransformBundle::from_transform(Transform::from_translation(
+                    ray.get_point(distance),
+                )),
+                ParticleBundle {
+                    inertia: Inertia::INFINITY,
+                    ..default()
+                },
+                SpringJoint::linear(grabbed),
+                settings.spring,
+                SpringState::default(),
+            ));
+        }
+    }
+
+    for (entity, anchor, mut transform) in &mut anchors {
+        if particles.get(anchor.grabbed).is_err() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation = ray.get_point(anchor.distance);
+    }
+}
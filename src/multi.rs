@@ -0,0 +1,149 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// One linear spring of [`Springs`] from the hub to `target`.
+#[derive(Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpringTo {
+    pub target: Entity,
+    pub spring: Spring,
+    /// Disabled springs are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Distance between the particles the spring rests at.
+    pub rest_length: f32,
+    /// Strain the spring tears at, never when `None`, see [`Tearable`](crate::tear::Tearable).
+    pub max_strain: Option<f32>,
+}
+
+impl SpringTo {
+    pub fn new(target: Entity, spring: Spring) -> Self {
+        Self {
+            target,
+            spring,
+            enabled: true,
+            rest_length: 0.0,
+            max_strain: None,
+        }
+    }
+
+    pub fn with_rest_length(mut self, rest_length: f32) -> Self {
+        self.rest_length = rest_length;
+        self
+    }
+
+    pub fn with_max_strain(mut self, max_strain: f32) -> Self {
+        self.max_strain = Some(max_strain);
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// Linear springs from this entity to any number of targets, for hubs like
+/// the node of a net or a cargo pallet with many straps, where a single
+/// [`SpringJoint`](crate::joint::SpringJoint) per entity isn't enough.
+///
+/// Can be combined with a `SpringJoint` on the same entity.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Springs(pub Vec<SpringTo>);
+
+impl MapEntities for Springs {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for spring in &mut self.0 {
+            spring.target = entity_mapper.map_entity(spring.target);
+        }
+    }
+}
+
+impl Springs {
+    pub fn new(springs: impl IntoIterator<Item = SpringTo>) -> Self {
+        Self(springs.into_iter().collect())
+    }
+
+    pub fn with(mut self, spring: SpringTo) -> Self {
+        self.0.push(spring);
+        self
+    }
+
+    /// Remove every spring to `target`, returning whether there was any.
+    pub fn remove(&mut self, target: Entity) -> bool {
+        let count = self.0.len();
+        self.0.retain(|spring| spring.target != target);
+        self.0.len() != count
+    }
+}
+
+pub fn multi_spring(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    hubs: Query<(Entity, &Springs, Option<&SpringGroup>)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (entity, springs, group) in &hubs {
+        if !groups.is_active(group) {
+            continue;
+        }
+        let Ok((transform_a, velocity_a, inertia_a)) = particles.get(entity) else {
+            continue;
+        };
+
+        for spring_to in &springs.0 {
+            if !spring_to.enabled || spring_to.target == entity {
+                continue;
+            }
+
+            let Ok((transform_b, velocity_b, inertia_b)) = particles.get(spring_to.target) else {
+                continue;
+            };
+            let Ok([mut impulse_a, mut impulse_b]) =
+                impulses.get_many_mut([entity, spring_to.target])
+            else {
+                continue;
+            };
+
+            // Include the impulses of the springs solved before this one, so
+            // the springs of a hub see each other.
+            let a = TranslationParticle3 {
+                mass: inertia_a.linear,
+                translation: transform_a.translation,
+                velocity: velocity_a.linear + impulse_a.linear * inertia_a.linear.inverse(),
+            };
+            let b = TranslationParticle3 {
+                mass: inertia_b.linear,
+                translation: transform_b.translation,
+                velocity: velocity_b.linear + impulse_b.linear * inertia_b.linear.inverse(),
+            };
+
+            let stretch = a.translation.distance(b.translation) - spring_to.rest_length;
+            let instant = a.instant(&b).with_rest_length(spring_to.rest_length);
+            let impulse = spring_to
+                .spring
+                .at_stretch(stretch)
+                .impulse(timestep, instant);
+            impulse_a.linear += impulse;
+            impulse_b.linear -= impulse;
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::{math::cubic_splines::CubicCurve, prelude::*};
+
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Where along the curve of a [`PathSpring`] the anchor is.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathAnchor {
+    /// Closest point of the curve to the particle, leaving it free to move
+    /// along the curve, for carts and beads on a wire.
+    Closest,
+    /// Point at this parameter of the curve, from 0 to the number of
+    /// segments, for camera rails and guided objects driven along the curve.
+    Parameter(f32),
+}
+
+/// Springs this particle towards a point on a curve, softly constraining it
+/// to a rail or track. The curve is in world space.
+#[derive(Debug, Clone, Component)]
+pub struct PathSpring {
+    pub curve: CubicCurve<Vec3>,
+    pub anchor: PathAnchor,
+    pub spring: Spring,
+    /// Disabled springs are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Points sampled per segment when searching for the closest point,
+    /// before refining it.
+    pub samples: u32,
+    /// Parameter of the anchor at the last step.
+    pub parameter: f32,
+    previous_anchor: Option<Vec3>,
+}
+
+impl PathSpring {
+    pub fn new(curve: CubicCurve<Vec3>, spring: Spring) -> Self {
+        Self {
+            curve,
+            anchor: PathAnchor::Closest,
+            spring,
+            enabled: true,
+            samples: 16,
+            parameter: 0.0,
+            previous_anchor: None,
+        }
+    }
+
+    pub fn with_parameter(mut self, parameter: f32) -> Self {
+        self.anchor = PathAnchor::Parameter(parameter);
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Parameter of the point of the curve closest to `point`.
+    pub fn closest_parameter(&self, point: Vec3) -> f32 {
+        let segments = self.curve.segments().len();
+        if segments == 0 {
+            return 0.0;
+        }
+
+        let samples = (self.samples.max(1) as usize) * segments;
+        let step = segments as f32 / samples as f32;
+        let distance = |t: f32| self.curve.position(t).distance_squared(point);
+
+        let mut closest = (0..=samples)
+            .map(|sample| sample as f32 * step)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+            .unwrap_or_default();
+
+        // Narrow down between the neighbouring samples.
+        let (mut low, mut high) = (
+            (closest - step).max(0.0),
+            (closest + step).min(segments as f32),
+        );
+        for _ in 0..8 {
+            let third = (high - low) / 3.0;
+            if distance(low + third) < distance(high - third) {
+                high -= third;
+            } else {
+                low += third;
+            }
+            closest = (low + high) * 0.5;
+        }
+        closest
+    }
+
+    /// Parameter of the anchor for a particle at `point`.
+    pub fn anchor_parameter(&self, point: Vec3) -> f32 {
+        match self.anchor {
+            PathAnchor::Closest => self.closest_parameter(point),
+            PathAnchor::Parameter(parameter) => {
+                parameter.clamp(0.0, self.curve.segments().len() as f32)
+            }
+        }
+    }
+}
+
+pub fn path_spring(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    mut particles: Query<(
+        &mut PathSpring,
+        &Transform,
+        &Velocity,
+        &Inertia,
+        &mut Impulse,
+        Option<&SpringGroup>,
+    )>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (mut path, transform, velocity, inertia, mut impulse, group) in &mut particles {
+        if !path.enabled || !groups.is_active(group) || path.curve.segments().is_empty() {
+            continue;
+        }
+
+        let parameter = path.anchor_parameter(transform.translation);
+        let anchor = path.curve.position(parameter);
+        let particle_velocity = velocity.linear + impulse.linear * inertia.linear.inverse();
+        let anchor_velocity = match path.anchor {
+            // The anchor slides along with the particle.
+            PathAnchor::Closest => {
+                let tangent = path.curve.velocity(parameter).normalize_or_zero();
+                tangent * particle_velocity.dot(tangent)
+            }
+            PathAnchor::Parameter(_) => path
+                .previous_anchor
+                .map_or(Vec3::ZERO, |previous| (anchor - previous) / timestep),
+        };
+        path.parameter = parameter;
+        path.previous_anchor = Some(anchor);
+
+        let particle = TranslationParticle3 {
+            mass: inertia.linear,
+            translation: transform.translation,
+            velocity: particle_velocity,
+        };
+        let anchor = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: anchor,
+            velocity: anchor_velocity,
+        };
+        impulse.linear += path.spring.impulse(timestep, particle.instant(&anchor));
+    }
+}
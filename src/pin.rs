@@ -0,0 +1,118 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::integration::{Inertia, PreviousTranslation, Velocity};
+use crate::timestep::SpringTimestep;
+
+/// Pins this particle to a point, e.g. the corners of a cloth to a moving
+/// body or the end of a rope to a hand.
+///
+/// Pinned particles get an infinite [`Inertia`] so springs can't move them,
+/// the original inertia is restored when the component is removed. Every
+/// step the particle is moved onto the pin, with a matching velocity so the
+/// springs attached to it are damped against the motion of the target.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Pinned {
+    /// Entity the pin follows, the pin stays in place in world space without one.
+    pub target: Option<Entity>,
+    /// Point in the local space of the `target`, or in world space without one.
+    pub offset: Vec3,
+}
+
+impl MapEntities for Pinned {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        if let Some(target) = &mut self.target {
+            *target = entity_mapper.map_entity(*target);
+        }
+    }
+}
+
+impl Pinned {
+    /// Pin to a fixed point in world space.
+    pub fn at(translation: Vec3) -> Self {
+        Self {
+            target: None,
+            offset: translation,
+        }
+    }
+
+    /// Pin to a point in the local space of `target`.
+    pub fn to(target: Entity, offset: Vec3) -> Self {
+        Self {
+            target: Some(target),
+            offset,
+        }
+    }
+
+    /// Point in world space the particle is pinned to, `None` when the
+    /// target has no transform.
+    pub fn translation(&self, targets: &Query<&GlobalTransform>) -> Option<Vec3> {
+        match self.target {
+            Some(target) => targets
+                .get(target)
+                .ok()
+                .map(|transform| transform.transform_point(self.offset)),
+            None => Some(self.offset),
+        }
+    }
+}
+
+/// Inertia of a [`Pinned`] particle from before it was pinned.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct PinnedInertia(pub Inertia);
+
+/// Swap the inertia of newly pinned particles for an infinite one, and
+/// restore it for unpinned particles.
+pub fn pin_inertia(
+    mut commands: Commands,
+    mut pinned: Query<(Entity, &mut Inertia), (With<Pinned>, Without<PinnedInertia>)>,
+    mut unpinned: Query<(Entity, &mut Inertia, &PinnedInertia), Without<Pinned>>,
+) {
+    for (entity, mut inertia) in &mut pinned {
+        commands.entity(entity).insert(PinnedInertia(*inertia));
+        *inertia = Inertia::INFINITY;
+    }
+
+    for (entity, mut inertia, original) in &mut unpinned {
+        *inertia = original.0;
+        commands.entity(entity).remove::<PinnedInertia>();
+    }
+}
+
+/// Set the velocity of pinned particles to land them on their pin by the
+/// end of the step.
+pub fn pin_particles(
+    timestep: Res<SpringTimestep>,
+    mut pinned: Query<(
+        &Pinned,
+        &Transform,
+        &mut Velocity,
+        Option<&mut PreviousTranslation>,
+    )>,
+    targets: Query<&GlobalTransform>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (pin, transform, mut velocity, previous) in &mut pinned {
+        let pin_translation = pin.translation(&targets).unwrap_or(transform.translation);
+        velocity.linear = (pin_translation - transform.translation) / timestep.step_delta();
+        velocity.angular = Vec3::ZERO;
+
+        // Verlet derives the velocity from the previous translation instead.
+        if let Some(mut previous) = previous {
+            previous.0 = Some(transform.translation - velocity.linear * timestep.delta());
+        }
+    }
+}
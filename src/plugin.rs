@@ -0,0 +1,370 @@
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+    reflect::Reflect,
+    transform::TransformSystem,
+};
+
+use crate::buoyancy::*;
+use crate::collision::*;
+use crate::damper::*;
+use crate::dangling::*;
+use crate::event::*;
+use crate::field::*;
+use crate::force::*;
+use crate::gear::*;
+use crate::graph::SpringGraph;
+use crate::group::*;
+use crate::history::*;
+use crate::integration::*;
+use crate::interpolation::*;
+use crate::island::*;
+use crate::joint::*;
+use crate::lifetime::*;
+use crate::motor::*;
+use crate::multi::*;
+use crate::path::*;
+use crate::pin::*;
+use crate::pulley::*;
+use crate::ragdoll::*;
+use crate::rope::*;
+use crate::sleep::*;
+use crate::tear::*;
+use crate::timestep::*;
+use crate::torsion::*;
+use crate::winch::*;
+use crate::*;
+
+/// Configuration of the built-in spring systems.
+#[derive(Debug, Clone, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct SpringConfig {
+    /// Integration scheme used to move particles.
+    pub integrator: Integrator,
+    /// Number of substeps each step is divided into.
+    ///
+    /// Forces, springs and integration all run once per substep with a
+    /// proportionally smaller timestep, trading CPU for stability of strong springs.
+    pub substeps: u32,
+    /// Number of times every spring is solved per substep.
+    ///
+    /// Springs sharing a particle (ropes, trusses) converge faster with more iterations.
+    pub solver_iterations: u32,
+    /// Put springs at rest to sleep so the solver can skip them, disabled when `None`.
+    pub sleep: Option<SleepSettings>,
+    /// Skip islands where no joint, settings or particle changed since the
+    /// last step, e.g. static decorative setups that already came to rest.
+    ///
+    /// Off by default, change detection only sees the crate's own components,
+    /// so springs driven by anything else would never be solved.
+    pub skip_unchanged: bool,
+    /// How springs whose `containing` entity was despawned are handled.
+    pub dangling: DanglingSpringPolicy,
+    /// Add [`SpringInterpolation`] to every particle, for smooth rendering
+    /// when the springs run in `FixedUpdate`.
+    pub interpolate: bool,
+    /// Clamp springs sharing particles to their [`Spring::stable_limits`],
+    /// warning once when a spring is clamped.
+    pub stability_clamp: bool,
+    /// Order the springs of an island are solved in each iteration.
+    pub solve_order: SolveOrder,
+}
+
+/// Order [`spring_impulse`] solves the springs of an island in.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolveOrder {
+    /// Whatever order the springs are queried in.
+    #[default]
+    Query,
+    /// Springs closest to an anchor, a particle with infinite mass, first,
+    /// like shock propagation.
+    ///
+    /// Anchored chains like ropes converge much faster at the same number of
+    /// iterations, as each spring sees the corrections of the ones closer to
+    /// the anchor in the same iteration.
+    FromAnchors,
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self {
+            integrator: Integrator::default(),
+            substeps: 1,
+            solver_iterations: 1,
+            sleep: None,
+            skip_unchanged: false,
+            dangling: DanglingSpringPolicy::default(),
+            interpolate: false,
+            stability_clamp: false,
+            solve_order: SolveOrder::default(),
+        }
+    }
+}
+
+/// Schedule ran once per substep by the [`SpringPlugin`].
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpringSchedule;
+
+#[derive(SystemSet, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SpringSet {
+    /// Update the [`SpringTimestep`] for this step.
+    Prepare,
+    /// Run the [`SpringSchedule`] for each substep.
+    Step,
+    /// Send events derived from the state of the springs after stepping.
+    Events,
+    /// Accumulate external forces like gravity into impulses.
+    Forces,
+    /// Accumulate spring impulses.
+    Solve,
+    /// Integrate the accumulated impulses into velocities and positions.
+    Integrate,
+}
+
+/// Runs the [`SpringSchedule`] once per substep.
+pub fn run_spring_schedule(world: &mut World) {
+    let timestep = *world.resource::<SpringTimestep>();
+    if !timestep.is_running() {
+        return;
+    }
+
+    for _ in 0..timestep.substeps() {
+        world.run_schedule(SpringSchedule);
+    }
+}
+
+pub struct SpringPlugin {
+    /// Schedule the spring systems run in, `FixedUpdate` by default.
+    pub schedule: InternedScheduleLabel,
+    pub config: SpringConfig,
+}
+
+impl Default for SpringPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: FixedUpdate.intern(),
+            config: SpringConfig::default(),
+        }
+    }
+}
+
+impl SpringPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            ..default()
+        }
+    }
+
+    pub fn with_integrator(mut self, integrator: Integrator) -> Self {
+        self.config.integrator = integrator;
+        self
+    }
+
+    pub fn with_substeps(mut self, substeps: u32) -> Self {
+        self.config.substeps = substeps;
+        self
+    }
+
+    pub fn with_sleep(mut self, sleep: SleepSettings) -> Self {
+        self.config.sleep = Some(sleep);
+        self
+    }
+
+    pub fn with_dangling_policy(mut self, dangling: DanglingSpringPolicy) -> Self {
+        self.config.dangling = dangling;
+        self
+    }
+
+    pub fn with_interpolation(mut self) -> Self {
+        self.config.interpolate = true;
+        self
+    }
+
+    pub fn with_stability_clamp(mut self) -> Self {
+        self.config.stability_clamp = true;
+        self
+    }
+
+    pub fn with_solve_order(mut self, solve_order: SolveOrder) -> Self {
+        self.config.solve_order = solve_order;
+        self
+    }
+
+    pub fn with_solver_iterations(mut self, solver_iterations: u32) -> Self {
+        self.config.solver_iterations = solver_iterations;
+        self
+    }
+}
+
+impl Plugin for SpringPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone())
+            .register_type::<SpringConfig>()
+            .register_type::<Velocity>()
+            .register_type::<Impulse>()
+            .register_type::<Inertia>()
+            .register_type::<PreviousTranslation>()
+            .register_type::<SpringInterpolation>()
+            .register_type::<SpringSmoothing>()
+            .init_resource::<SpringTimestep>()
+            .init_resource::<SpringsPaused>()
+            .register_type::<SpringsPaused>()
+            .register_type::<SpringTimestep>()
+            .init_resource::<GlobalGravity>()
+            .register_type::<GlobalGravity>()
+            .init_resource::<Wind>()
+            .register_type::<Wind>()
+            .register_type::<Gravity>()
+            .register_type::<ConstantForce>()
+            .register_type::<SpringField>()
+            .register_type::<Buoyancy>()
+            .register_type::<SpringJoint>()
+            .register_type::<AngularLimits>()
+            .register_type::<TorsionSpring>()
+            .register_type::<WindUp>()
+            .register_type::<GearSpring>()
+            .register_type::<Damper>()
+            .register_type::<SpringCollider>()
+            .register_type::<CollisionRadius>()
+            .register_type::<SpringMotor>()
+            .register_type::<SpringSleep>()
+            .register_type::<SprungBy>()
+            .register_type::<SpringLifetime>()
+            .register_type::<Winch>()
+            .register_type::<SpringHistory>()
+            .register_type::<Pulley>()
+            .register_type::<Springs>()
+            .register_type::<Pinned>()
+            .register_type::<PinnedInertia>()
+            .register_type::<Tearable>()
+            .register_type::<Rope>()
+            .register_type::<RopeState>()
+            .init_resource::<SpringIslands>()
+            .init_resource::<SpringGroups>()
+            .init_resource::<SpringTargets>()
+            .register_type::<SpringIslands>()
+            .register_type::<Spring>()
+            .register_type::<SpringPair>()
+            .register_type::<SpringGroup>()
+            .register_type::<SpringGroups>()
+            .register_type::<Particle1>()
+            .register_type::<TranslationParticle2>()
+            .register_type::<AngularParticle2>()
+            .register_type::<TranslationParticle3>()
+            .register_type::<AngularParticle3>()
+            .init_schedule(SpringSchedule)
+            .register_type::<SpringState>()
+            .register_type::<SpringEvents>()
+            .add_event::<SpringStretched>()
+            .add_event::<SpringTaut>()
+            .add_event::<SpringSettled>()
+            .add_event::<SpringTwang>()
+            .add_event::<SpringTorn>()
+            .add_event::<RopeCut>()
+            .add_event::<RopeTaut>()
+            .add_event::<RopeSlack>()
+            .add_event::<SpringExpired>()
+            .add_event::<WinchReeled>()
+            .add_event::<SpringTargetLost>()
+            .configure_sets(
+                self.schedule,
+                (SpringSet::Prepare, SpringSet::Step, SpringSet::Events).chain(),
+            )
+            .configure_sets(
+                SpringSchedule,
+                (SpringSet::Forces, SpringSet::Solve, SpringSet::Integrate).chain(),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    (
+                        insert_spring_interpolation,
+                        restore_interpolation,
+                        update_timestep,
+                        pin_inertia,
+                        pin_particles,
+                        spring_lifetime,
+                        winch,
+                        (
+                            index_spring_targets::<SpringJoint>,
+                            index_spring_targets::<TorsionSpring>,
+                            index_spring_targets::<GearSpring>,
+                            index_spring_targets::<Damper>,
+                            index_spring_targets::<SpringMotor>,
+                            index_spring_targets::<Pulley>,
+                        ),
+                        (
+                            dangling_springs::<SpringJoint>,
+                            dangling_springs::<TorsionSpring>,
+                            dangling_springs::<GearSpring>,
+                            dangling_springs::<Damper>,
+                            dangling_springs::<SpringMotor>,
+                            dangling_springs::<Pulley>,
+                            dangling_multi_springs,
+                        ),
+                        sync_sprung_by,
+                        insert_spring_state,
+                        insert_rope_state,
+                        insert_spring_sleep,
+                        update_spring_sleep,
+                        reset_inactive_springs,
+                        build_islands,
+                    )
+                        .chain()
+                        .in_set(SpringSet::Prepare),
+                    run_spring_schedule.in_set(SpringSet::Step),
+                    (
+                        spring_events,
+                        record_interpolation,
+                        record_spring_history,
+                        tear_springs,
+                        tear_multi_springs,
+                        rope_state,
+                    )
+                        .in_set(SpringSet::Events),
+                ),
+            )
+            .add_systems(
+                PostUpdate,
+                interpolate_transforms
+                    .run_if(resource_exists::<Time<Fixed>>)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                SpringSchedule,
+                (gravity, constant_force, wind, spring_field, buoyancy).in_set(SpringSet::Forces),
+            )
+            .add_systems(
+                SpringSchedule,
+                (
+                    spring_impulse::<Velocity, Inertia, Impulse>
+                        .run_if(not(resource_exists::<SpringGraph>)),
+                    torsion_spring,
+                    gear_spring,
+                    damper_impulse,
+                    spring_motor,
+                    path_spring,
+                    pulley_spring,
+                    multi_spring,
+                    angular_limits,
+                )
+                    .chain()
+                    .in_set(SpringSet::Solve),
+            )
+            .add_systems(
+                SpringSchedule,
+                (
+                    collide_particles.before(symplectic_euler).before(verlet),
+                    symplectic_euler.run_if(integrator_is(Integrator::SymplecticEuler)),
+                    verlet.run_if(integrator_is(Integrator::Verlet)),
+                    (project_rigid_joints, solve_xpbd_joints)
+                        .after(symplectic_euler)
+                        .after(verlet),
+                )
+                    .in_set(SpringSet::Integrate),
+            );
+    }
+}
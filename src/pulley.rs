@@ -0,0 +1,177 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::dangling::SpringLink;
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Rope running from this entity over a pulley to the `containing` entity,
+/// for elevators and counterweights.
+///
+/// The two segments share `total_length`, so shortening one lengthens the
+/// other. Like a real rope it only pulls, going slack when shorter.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Pulley {
+    pub containing: Entity,
+    /// Disabled pulleys are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Point in world space the segment of this entity hangs from.
+    pub anchor: Vec3,
+    /// Point in world space the segment of the `containing` entity hangs from,
+    /// the same as `anchor` for a single pulley wheel.
+    pub containing_anchor: Vec3,
+    /// Length of this segment plus `ratio` times the other segment the rope rests at.
+    pub total_length: f32,
+    /// How much the segment of the `containing` entity counts towards the
+    /// total length, e.g. 2 for a block and tackle halving its travel.
+    pub ratio: f32,
+    pub spring: Spring,
+}
+
+impl MapEntities for Pulley {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringLink for Pulley {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl Pulley {
+    pub fn new(containing: Entity, anchor: Vec3, total_length: f32, spring: Spring) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            anchor,
+            containing_anchor: anchor,
+            total_length,
+            ratio: 1.0,
+            spring,
+        }
+    }
+
+    pub fn with_containing_anchor(mut self, containing_anchor: Vec3) -> Self {
+        self.containing_anchor = containing_anchor;
+        self
+    }
+
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Current length of the rope with the particles at these translations.
+    pub fn length(&self, translation: Vec3, containing_translation: Vec3) -> f32 {
+        translation.distance(self.anchor)
+            + self.ratio * containing_translation.distance(self.containing_anchor)
+    }
+}
+
+pub fn pulley_spring(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    pulleys: Query<(Entity, &Pulley, Option<&SpringGroup>)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (entity, pulley, group) in &pulleys {
+        if !pulley.enabled || !groups.is_active(group) || pulley.containing == entity {
+            continue;
+        }
+
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(entity), particles.get(pulley.containing))
+        else {
+            continue;
+        };
+        let Ok([mut impulse_a, mut impulse_b]) = impulses.get_many_mut([entity, pulley.containing])
+        else {
+            continue;
+        };
+
+        let offset_a = transform_a.translation - pulley.anchor;
+        let offset_b = transform_b.translation - pulley.containing_anchor;
+        let direction_a = offset_a.normalize_or_zero();
+        let direction_b = offset_b.normalize_or_zero();
+        let velocity_a = velocity_a.linear + impulse_a.linear * inertia_a.linear.inverse();
+        let velocity_b = velocity_b.linear + impulse_b.linear * inertia_b.linear.inverse();
+
+        // Both segments along a single axis, with the other segment standing
+        // in for the rest position of this one.
+        let ratio = pulley.ratio;
+        let particle = Particle1 {
+            inertia: inertia_a.linear,
+            position: offset_a.length(),
+            velocity: velocity_a.dot(direction_a),
+        };
+        let counterweight = Particle1 {
+            inertia: inertia_b.linear / (ratio * ratio),
+            position: pulley.total_length - ratio * offset_b.length(),
+            velocity: -ratio * velocity_b.dot(direction_b),
+        };
+
+        let instant = particle.instant(&counterweight);
+        if instant.displacement <= 0.0 {
+            continue;
+        }
+
+        // Only ever pull the segments towards their anchors.
+        let pull = pulley.spring.impulse(timestep, instant).min(0.0);
+        impulse_a.linear += direction_a * pull;
+        impulse_b.linear += direction_b * pull * ratio;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::force::GlobalGravity;
+    use crate::testing::*;
+
+    #[test]
+    fn heavier_side_descends_keeping_total_length() {
+        let mut app = app(plugin());
+        app.insert_resource(GlobalGravity::default());
+        let (anchor, counterweight_anchor) = (Vec3::NEG_X, Vec3::X);
+        let counterweight = particle(&mut app, Vec3::new(1.0, -2.0, 0.0), 2.0);
+        let pulley = Pulley::new(counterweight, anchor, 4.0, Spring::stiff())
+            .with_containing_anchor(counterweight_anchor);
+        let load = particle(&mut app, Vec3::new(-1.0, -2.0, 0.0), 1.0);
+        app.world_mut().entity_mut(load).insert(pulley);
+
+        for _ in 0..60 {
+            step(&mut app, 1);
+            let length = pulley.length(translation(&app, load), translation(&app, counterweight));
+            assert!((length - 4.0).abs() < 0.05, "rope stretched to {length}");
+        }
+        assert!(translation(&app, counterweight).y < -3.0);
+        assert!(translation(&app, load).y > -1.0);
+    }
+}
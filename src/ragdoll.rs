@@ -0,0 +1,190 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::island::SpringIslands;
+use crate::joint::{SpringJoint, SpringState};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Swing and twist limits of a [`SpringJoint`], relative to the orientation
+/// of the `containing` particle.
+///
+/// The twist axis is the local X axis, the default axis angular springs align.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct AngularLimits {
+    /// Maximum angle in radians the twist axis can swing away from the parent's.
+    pub swing: f32,
+    /// Maximum angle in radians the joint can twist around the twist axis.
+    pub twist: f32,
+    /// Spring pushing the joint back inside of the limits.
+    pub spring: Spring,
+}
+
+impl AngularLimits {
+    pub fn new(swing: f32, twist: f32) -> Self {
+        Self {
+            swing,
+            twist,
+            spring: Spring::stiff(),
+        }
+    }
+
+    /// Rotation of `relative` clamped inside of the limits.
+    pub fn clamp(&self, relative: Quat) -> Quat {
+        let (swing, twist) = swing_twist(relative, Vec3::X);
+
+        let twist_angle = 2.0 * twist.x.atan2(twist.w);
+        let twist_angle = wrap_angle(twist_angle).clamp(-self.twist, self.twist);
+
+        let (swing_axis, swing_angle) = swing.to_axis_angle();
+        let swing_angle = wrap_angle(swing_angle).clamp(-self.swing, self.swing);
+
+        Quat::from_axis_angle(swing_axis, swing_angle) * Quat::from_axis_angle(Vec3::X, twist_angle)
+    }
+}
+
+/// Decompose a rotation into a swing away from `axis` and a twist around it.
+pub fn swing_twist(rotation: Quat, axis: Vec3) -> (Quat, Quat) {
+    let projected = axis * rotation.xyz().dot(axis);
+    let twist = Quat::from_xyzw(projected.x, projected.y, projected.z, rotation.w);
+    let twist = if twist.length_squared() > f32::EPSILON {
+        twist.normalize()
+    } else {
+        Quat::IDENTITY
+    };
+
+    (rotation * twist.inverse(), twist)
+}
+
+/// Push joints with [`AngularLimits`] back inside of their limits.
+pub fn angular_limits(
+    timestep: Res<SpringTimestep>,
+    islands: Res<SpringIslands>,
+    joints: Query<(&SpringJoint, &AngularLimits)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for entity in islands.springs() {
+        let Ok((joint, limits)) = joints.get(entity) else {
+            continue;
+        };
+        let (
+            Ok((child, child_velocity, child_inertia)),
+            Ok((parent, parent_velocity, parent_inertia)),
+        ) = (particles.get(entity), particles.get(joint.containing))
+        else {
+            continue;
+        };
+
+        let relative = parent.rotation.inverse() * child.rotation;
+        let clamped = parent.rotation * limits.clamp(relative);
+        let mut error = child.rotation * clamped.inverse();
+        if error.w < 0.0 {
+            error = -error;
+        }
+
+        let displacement = error.to_scaled_axis();
+        if displacement.length_squared() <= f32::EPSILON {
+            continue;
+        }
+
+        let axis = displacement.normalize();
+        let velocity = axis * (child_velocity.angular - parent_velocity.angular).dot(axis);
+        let instant = SpringInstant {
+            reduced_inertia: (child_inertia.angular.inverse() + parent_inertia.angular.inverse())
+                .inverse(),
+            displacement,
+            velocity,
+        };
+        let impulse = limits.spring.impulse(timestep, instant);
+
+        let Ok([mut child_impulse, mut parent_impulse]) =
+            impulses.get_many_mut([entity, joint.containing])
+        else {
+            continue;
+        };
+        child_impulse.angular += impulse;
+        parent_impulse.angular -= impulse;
+    }
+}
+
+/// Bone of a ragdoll, springing its orientation towards its parent's.
+#[derive(Debug, Copy, Clone)]
+pub struct RagdollBone {
+    pub entity: Entity,
+    pub parent: Entity,
+    pub spring: Spring,
+    pub limits: Option<AngularLimits>,
+}
+
+/// Builds an active ragdoll out of existing particles by adding an angular
+/// [`SpringJoint`] from every bone to its parent.
+///
+/// Only orientations are driven, the bones should be kept together by other
+/// means, e.g. physics joints.
+#[derive(Default, Debug, Clone)]
+pub struct RagdollBuilder {
+    pub bones: Vec<RagdollBone>,
+    /// Spring used for bones added with [`RagdollBuilder::bone`].
+    pub spring: Spring,
+    /// Limits used for bones added with [`RagdollBuilder::bone`].
+    pub limits: Option<AngularLimits>,
+}
+
+impl RagdollBuilder {
+    pub fn new(spring: Spring) -> Self {
+        Self {
+            spring,
+            ..default()
+        }
+    }
+
+    pub fn with_limits(mut self, limits: AngularLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// Add a bone with the default spring and limits of the builder.
+    pub fn bone(self, entity: Entity, parent: Entity) -> Self {
+        let (spring, limits) = (self.spring, self.limits);
+        self.bone_with(entity, parent, spring, limits)
+    }
+
+    pub fn bone_with(
+        mut self,
+        entity: Entity,
+        parent: Entity,
+        spring: Spring,
+        limits: Option<AngularLimits>,
+    ) -> Self {
+        self.bones.push(RagdollBone {
+            entity,
+            parent,
+            spring,
+            limits,
+        });
+        self
+    }
+
+    pub fn build(&self, commands: &mut Commands) {
+        for bone in &self.bones {
+            let mut entity = commands.entity(bone.entity);
+            entity.insert((
+                SpringJoint::angular(bone.parent),
+                bone.spring,
+                SpringState::default(),
+            ));
+
+            if let Some(limits) = bone.limits {
+                entity.insert(limits);
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::*;
+use crate::buoyancy::{water_surface, Buoyancy, WaterHeight};
+use crate::timestep::springs_running;
+
+/// Floats rigid bodies with a [`Buoyancy`], pushing on their center of mass.
+pub struct BuoyancyPlugin;
+
+impl Plugin for BuoyancyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Buoyancy>().add_systems(
+            PostUpdate,
+            rapier_buoyancy
+                .run_if(springs_running)
+                .after(clear_spring_forces)
+                .before(apply_spring_velocities),
+        );
+        add_spring_output(app);
+    }
+}
+
+pub fn rapier_buoyancy(
+    time: Res<Time>,
+    water: Option<Res<WaterHeight>>,
+    mut floating: Query<(Entity, &mut Buoyancy, Option<&OutputMode>)>,
+    bodies: Query<RapierParticleQuery>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (entity, mut buoyancy, mode) in &mut floating {
+        let mode = mode.copied().unwrap_or_default();
+        let Some(owner) = owners.owner(entity) else {
+            continue;
+        };
+        let Ok(body) = bodies.get(owner) else {
+            continue;
+        };
+
+        let point = body.center_of_mass();
+        let particle = TranslationParticle3 {
+            mass: body.output_mass(mode).mass,
+            translation: extend(point),
+            velocity: extend(body.velocity_at_point(point)),
+        };
+        let surface = water_surface(&buoyancy, water.as_deref(), particle.translation);
+        let impulse = buoyancy.impulse(timestep, surface, &particle);
+        if buoyancy.is_submerged() {
+            output.apply_at(mode, &body, unit(impulse), point, timestep);
+        }
+    }
+}
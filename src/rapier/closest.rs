@@ -0,0 +1,148 @@
+use bevy::ecs::entity::{EntityMapper, MapEntities};
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::reflect::Reflect;
+
+use super::*;
+use crate::timestep::springs_running;
+
+/// Spring between the closest points on the collider of this entity and the
+/// collider of the `containing` entity, instead of between their origins, so
+/// large or irregular bodies are pulled surface to surface.
+///
+/// The impulses go to the rigid bodies owning the colliders, at the closest
+/// points. Overlapping colliders are pushed apart like a compressed spring.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct ClosestPointSpring {
+    /// Entity with the collider the closest points are measured to.
+    pub containing: Entity,
+    pub spring: Spring,
+    /// Distance between the surfaces the spring rests at.
+    pub rest_length: f32,
+    /// Furthest distance between the surfaces the spring acts over.
+    pub max_distance: f32,
+    /// Closest points on this collider and on the containing collider in
+    /// world space, `None` when further apart than `max_distance`.
+    pub points: Option<(Vec3, Vec3)>,
+}
+
+impl MapEntities for ClosestPointSpring {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl ClosestPointSpring {
+    pub fn new(containing: Entity, spring: Spring) -> Self {
+        Self {
+            containing,
+            spring,
+            rest_length: 0.0,
+            max_distance: f32::MAX,
+            points: None,
+        }
+    }
+
+    pub fn with_rest_length(mut self, rest_length: f32) -> Self {
+        self.rest_length = rest_length;
+        self
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+}
+
+pub struct ClosestPointSpringPlugin;
+
+impl Plugin for ClosestPointSpringPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ClosestPointSpring>().add_systems(
+            PostUpdate,
+            closest_point_spring
+                .run_if(springs_running)
+                .after(clear_spring_forces)
+                .before(apply_spring_velocities),
+        );
+        add_spring_output(app);
+    }
+}
+
+pub fn closest_point_spring(
+    time: Res<Time>,
+    mut springs: Query<(Entity, &mut ClosestPointSpring, Option<&OutputMode>)>,
+    colliders: Query<(&Collider, &GlobalTransform)>,
+    bodies: Query<RapierParticleQuery>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (entity, mut closest, mode) in &mut springs {
+        let mode = mode.copied().unwrap_or_default();
+        let (Ok((collider_a, transform_a)), Ok((collider_b, transform_b))) =
+            (colliders.get(entity), colliders.get(closest.containing))
+        else {
+            closest.points = None;
+            continue;
+        };
+
+        // Colliders are placed at their entity, with the scale already
+        // applied to the shape by rapier.
+        let contact = parry::query::contact(
+            &transform_to_iso(&transform_a.compute_transform()),
+            &*collider_a.raw,
+            &transform_to_iso(&transform_b.compute_transform()),
+            &*collider_b.raw,
+            closest.max_distance,
+        );
+        let Ok(Some(contact)) = contact else {
+            closest.points = None;
+            continue;
+        };
+
+        let point_a: Unit = contact.point1.into();
+        let point_b: Unit = contact.point2.into();
+        let normal: Unit = (*contact.normal1).into();
+        closest.points = Some((extend(point_a), extend(point_b)));
+
+        let body_a = owners
+            .owner(entity)
+            .and_then(|owner| bodies.get(owner).ok());
+        let body_b = owners
+            .owner(closest.containing)
+            .and_then(|owner| bodies.get(owner).ok());
+        let particle =
+            |body: &Option<RapierParticleQueryItem>, point: Unit, position: f32| Particle1 {
+                inertia: body
+                    .as_ref()
+                    .map_or(f32::INFINITY, |body| body.output_mass(mode).mass),
+                position,
+                velocity: body
+                    .as_ref()
+                    .map_or(0.0, |body| body.velocity_at_point(point).dot(normal)),
+            };
+
+        // Measured along the normal pointing out of this collider, negative
+        // while the colliders overlap.
+        let stretch = contact.dist - closest.rest_length;
+        let particle_a = particle(&body_a, point_a, 0.0);
+        let particle_b = particle(&body_b, point_b, stretch);
+        let push = closest
+            .spring
+            .at_stretch(stretch)
+            .impulse(timestep, particle_a.instant(&particle_b));
+        let impulse = normal * push;
+
+        if let Some(body_a) = &body_a {
+            output.apply_at(mode, body_a, impulse, point_a, timestep);
+        }
+        if let Some(body_b) = &body_b {
+            output.apply_at(mode, body_b, -impulse, point_b, timestep);
+        }
+    }
+}
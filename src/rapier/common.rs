@@ -0,0 +1,359 @@
+use bevy::ecs::query::QueryItem;
+use bevy::ecs::system::SystemParam;
+use bevy::utils::HashMap;
+
+use super::*;
+use crate::adapter::{AccumulateImpulse, ReadInertia, ReadVelocity};
+use crate::integration::{Impulse, Inertia};
+
+/// How a spring hands its result to rapier.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub enum OutputMode {
+    /// Add to the `ExternalImpulse` of the body, which rapier clears every step.
+    #[default]
+    Impulse,
+    /// Add to the `ExternalForce` of the body, dividing the impulse by the timestep.
+    ///
+    /// The force is removed again before the springs run next frame, so other
+    /// forces on the body are left alone.
+    Force,
+    /// Add the velocity change directly to the `Velocity` of the body, as if it
+    /// were a dynamic body with the mass of its colliders.
+    ///
+    /// For `KinematicVelocityBased` bodies, which ignore impulses and forces.
+    Velocity,
+}
+
+/// Forces applied by springs last frame, removed from `ExternalForce` before
+/// the springs apply new ones.
+#[derive(Default, Debug, Resource)]
+pub struct AppliedSpringForces(pub HashMap<Entity, ExternalForce>);
+
+pub fn clear_spring_forces(
+    mut applied: ResMut<AppliedSpringForces>,
+    mut forces: Query<&mut ExternalForce>,
+) {
+    for (entity, applied) in applied.0.drain() {
+        if let Ok(mut external) = forces.get_mut(entity) {
+            external.force -= applied.force;
+            external.torque -= applied.torque;
+        }
+    }
+}
+
+/// Velocity changes from springs with [`OutputMode::Velocity`], applied after
+/// the springs ran so they can read `Velocity` while writing.
+#[derive(Default, Debug, Resource)]
+pub struct SpringVelocityChanges(pub HashMap<Entity, Velocity>);
+
+pub fn apply_spring_velocities(
+    mut changes: ResMut<SpringVelocityChanges>,
+    mut velocities: Query<&mut Velocity>,
+) {
+    for (entity, change) in changes.0.drain() {
+        if let Ok(mut velocity) = velocities.get_mut(entity) {
+            velocity.linvel += change.linvel;
+            velocity.angvel += change.angvel;
+        }
+    }
+}
+
+/// Add [`clear_spring_forces`] and [`apply_spring_velocities`] once, spring
+/// systems writing through [`RapierOutput`] should run between them.
+pub(crate) fn add_spring_output(app: &mut App) {
+    if app.world().contains_resource::<AppliedSpringForces>() {
+        return;
+    }
+
+    app.register_type::<OutputMode>()
+        .init_resource::<AppliedSpringForces>()
+        .init_resource::<SpringVelocityChanges>()
+        .add_systems(
+            PostUpdate,
+            (
+                clear_spring_forces.before(PhysicsSet::SyncBackend),
+                apply_spring_velocities.before(PhysicsSet::SyncBackend),
+            ),
+        );
+}
+
+/// Finds the rigid body owning a collider, so springs attached to child
+/// collider entities push on the body they belong to.
+#[derive(SystemParam)]
+pub struct RigidBodyOwners<'w, 's> {
+    parents: Query<'w, 's, &'static Parent>,
+    rigid_bodies: Query<'w, 's, (), With<RigidBody>>,
+}
+
+impl<'w, 's> RigidBodyOwners<'w, 's> {
+    /// Walk up the hierarchy from `entity` to the first entity with a `RigidBody`.
+    pub fn owner(&self, entity: Entity) -> Option<Entity> {
+        let mut current = entity;
+        loop {
+            if self.rigid_bodies.contains(current) {
+                return Some(current);
+            }
+            current = self.parents.get(current).ok()?.get();
+        }
+    }
+}
+
+/// Writes spring results to rapier according to the [`OutputMode`] of the spring.
+#[derive(SystemParam)]
+pub struct RapierOutput<'w, 's> {
+    impulses: Query<'w, 's, &'static mut ExternalImpulse>,
+    forces: Query<'w, 's, &'static mut ExternalForce>,
+    applied: ResMut<'w, AppliedSpringForces>,
+    velocity_changes: ResMut<'w, SpringVelocityChanges>,
+}
+
+impl<'w, 's> RapierOutput<'w, 's> {
+    /// Apply an impulse to `body` at a point in world space.
+    pub fn apply_at(
+        &mut self,
+        mode: OutputMode,
+        body: &RapierParticleQueryItem,
+        impulse: Unit,
+        point: Unit,
+        timestep: f32,
+    ) {
+        match mode {
+            OutputMode::Impulse => {
+                if let Ok(mut external) = self.impulses.get_mut(body.entity) {
+                    body.apply_impulse_at(&mut external, impulse, point);
+                }
+            }
+            OutputMode::Force => {
+                let Ok(mut external) = self.forces.get_mut(body.entity) else {
+                    return;
+                };
+                let force = impulse / timestep;
+                let torque = body.torque_at(force, point);
+                external.force += force;
+                external.torque += torque;
+
+                let applied = self.applied.0.entry(body.entity).or_default();
+                applied.force += force;
+                applied.torque += torque;
+            }
+            OutputMode::Velocity => {
+                let mass = body.unconstrained_mass();
+                let torque = body.torque_at(impulse, point);
+
+                let change = self.velocity_changes.0.entry(body.entity).or_default();
+                change.linvel += impulse * mass.mass.inverse();
+                change.angvel += body.angular_velocity_change(&mass, torque);
+            }
+        }
+    }
+}
+
+/// Ground found by [`cast_ground`].
+#[derive(Debug, Copy, Clone, Reflect)]
+pub struct GroundHit {
+    /// Collider that was hit.
+    pub entity: Entity,
+    /// Distance from the origin of the ray to the ground.
+    pub distance: f32,
+    /// Point on the ground in world space.
+    pub point: Unit,
+}
+
+/// Cast a ray looking for the ground below a body, ignoring the body itself.
+pub fn cast_ground(
+    rapier_context: &RapierContext,
+    origin: Unit,
+    direction: Unit,
+    max_distance: f32,
+    exclude: Entity,
+) -> Option<GroundHit> {
+    let filter = QueryFilter::default().exclude_rigid_body(exclude);
+    rapier_context
+        .cast_ray(origin, direction, max_distance, true, filter)
+        .map(|(entity, distance)| GroundHit {
+            entity,
+            distance,
+            point: origin + direction * distance,
+        })
+}
+
+/// Inserts the `Velocity` and `ReadMassProperties` components springs read on
+/// rigid bodies missing them, pulling their current state from the
+/// [`RapierContext`], e.g. for bodies spawned by third-party plugins.
+pub struct RapierStateFallbackPlugin;
+
+impl Plugin for RapierStateFallbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            insert_missing_rapier_state.after(PhysicsSet::Writeback),
+        );
+    }
+}
+
+pub fn insert_missing_rapier_state(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    bodies: Query<
+        (Entity, &RigidBody, Has<Velocity>, Has<ReadMassProperties>),
+        Or<(Without<Velocity>, Without<ReadMassProperties>)>,
+    >,
+    mut mass_modified: EventWriter<MassModifiedEvent>,
+) {
+    for (entity, rigid_body, has_velocity, has_mass) in &bodies {
+        if !matches!(
+            rigid_body,
+            RigidBody::Dynamic | RigidBody::KinematicVelocityBased
+        ) {
+            continue;
+        }
+
+        let Some(body) = rapier_context
+            .entity2body()
+            .get(&entity)
+            .and_then(|handle| rapier_context.bodies.get(*handle))
+        else {
+            continue;
+        };
+
+        let mut entity_commands = commands.entity(entity);
+        if !has_velocity {
+            entity_commands.insert(Velocity {
+                linvel: (*body.linvel()).into(),
+                angvel: angvel(body),
+            });
+        }
+
+        if !has_mass {
+            // Rapier fills in the mass properties once it sees the event.
+            entity_commands.insert(ReadMassProperties::default());
+            mass_modified.send(MassModifiedEvent(entity));
+        }
+    }
+}
+
+impl<'w, 's> RapierParticleQueryItem<'w, 's> {
+    pub fn name<'a>(&'a self) -> Box<dyn std::fmt::Debug + 'a> {
+        match self.name {
+            Some(name) => Box::new(name),
+            None => Box::new(self.entity),
+        }
+    }
+
+    pub fn velocity(&self) -> Velocity {
+        match self.velocity {
+            Some(velocity) => *velocity,
+            None => match self.rigid_body {
+                Some(
+                    rigid_body @ RigidBody::Dynamic
+                    | rigid_body @ RigidBody::KinematicVelocityBased,
+                ) => {
+                    warn!(
+                        "{:?} rigidbody for {:?} needs a `Velocity` component for spring damping, see `RapierStateFallbackPlugin`",
+                        rigid_body,
+                        self.name()
+                    );
+                    Velocity::default()
+                }
+                _ => Velocity::default(),
+            },
+        }
+    }
+
+    pub fn mass(&self) -> MassProperties {
+        let mut prop = self.unconstrained_mass();
+
+        if let Some(
+            RigidBody::KinematicVelocityBased
+            | RigidBody::KinematicPositionBased
+            | RigidBody::Fixed,
+        ) = self.rigid_body
+        {
+            prop.mass = 0.0;
+            prop.principal_inertia = Default::default();
+        }
+
+        prop
+    }
+
+    /// Mass properties computed from the colliders, even for bodies that
+    /// springs can't move with impulses.
+    pub fn unconstrained_mass(&self) -> MassProperties {
+        match self.mass {
+            Some(mass) => *mass.get(),
+            None => {
+                if let Some(RigidBody::KinematicVelocityBased | RigidBody::Dynamic) =
+                    self.rigid_body
+                {
+                    warn!(
+                        "{:?} rigidbody for {:?} needs a `ReadMassProperties` component for spring damping, see `RapierStateFallbackPlugin`",
+                        self.rigid_body,
+                        self.name()
+                    );
+                }
+                MassProperties::default()
+            }
+        }
+    }
+
+    /// Mass properties springs see for the body with the given output mode.
+    pub fn output_mass(&self, mode: OutputMode) -> MassProperties {
+        match mode {
+            OutputMode::Velocity => self.unconstrained_mass(),
+            OutputMode::Impulse | OutputMode::Force => self.mass(),
+        }
+    }
+
+    /// Apply an impulse at a point in world space, adding the torque from the
+    /// lever arm around the center of mass.
+    pub fn apply_impulse_at(&self, external: &mut ExternalImpulse, impulse: Unit, point: Unit) {
+        external.impulse += impulse;
+        external.torque_impulse += self.torque_at(impulse, point);
+    }
+}
+
+impl ReadVelocity for Velocity {
+    fn velocity(&self) -> crate::integration::Velocity {
+        crate::integration::Velocity {
+            linear: extend(self.linvel),
+            angular: extend_torque(self.angvel),
+        }
+    }
+}
+
+/// Only the principal inertia is read, the orientation of the principal axes
+/// is ignored. Fixed and kinematic bodies are immovable, like in
+/// [`RapierParticleQuery::mass`].
+impl ReadInertia for ReadMassProperties {
+    type Data = (&'static ReadMassProperties, Option<&'static RigidBody>);
+
+    fn inertia((mass, rigid_body): QueryItem<'_, Self::Data>) -> Inertia {
+        match rigid_body {
+            Some(
+                RigidBody::KinematicVelocityBased
+                | RigidBody::KinematicPositionBased
+                | RigidBody::Fixed,
+            ) => Inertia::INFINITY,
+            _ => Inertia {
+                linear: mass.get().mass,
+                angular: principal_inertia(mass.get()),
+            },
+        }
+    }
+}
+
+impl AccumulateImpulse for ExternalImpulse {
+    fn impulse(&self) -> Impulse {
+        Impulse {
+            linear: extend(self.impulse),
+            angular: extend_torque(self.torque_impulse),
+        }
+    }
+
+    fn accumulate(&mut self, linear: Vec3, angular: Vec3) {
+        self.impulse += unit(linear);
+        self.torque_impulse += torque(angular);
+    }
+}
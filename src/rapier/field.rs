@@ -0,0 +1,64 @@
+use super::*;
+use crate::field::SpringField;
+use crate::timestep::springs_running;
+
+/// Applies every [`SpringField`] to the dynamic rigid bodies within its
+/// radius, pulling or pushing their center of mass.
+///
+/// The body the field is attached to is left alone.
+pub struct SpringFieldPlugin;
+
+impl Plugin for SpringFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringField>().add_systems(
+            PostUpdate,
+            rapier_spring_field
+                .run_if(springs_running)
+                .after(clear_spring_forces)
+                .before(apply_spring_velocities),
+        );
+        add_spring_output(app);
+    }
+}
+
+pub fn rapier_spring_field(
+    time: Res<Time>,
+    fields: Query<(Entity, &SpringField, &GlobalTransform, Option<&OutputMode>)>,
+    bodies: Query<RapierParticleQuery, With<RigidBody>>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (field_entity, field, field_transform, mode) in &fields {
+        let mode = mode.copied().unwrap_or_default();
+        let owner = owners.owner(field_entity);
+        let origin = unit(field_transform.translation());
+        let center = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: extend(origin),
+            velocity: owner
+                .and_then(|owner| bodies.get(owner).ok())
+                .map_or(Vec3::ZERO, |body| extend(body.velocity_at_point(origin))),
+        };
+
+        for body in &bodies {
+            if Some(body.entity) == owner || !matches!(body.rigid_body, Some(RigidBody::Dynamic)) {
+                continue;
+            }
+
+            let point = body.center_of_mass();
+            let particle = TranslationParticle3 {
+                mass: body.output_mass(mode).mass,
+                translation: extend(point),
+                velocity: extend(body.velocity_at_point(point)),
+            };
+            if let Some(impulse) = field.impulse(timestep, &particle, &center) {
+                output.apply_at(mode, &body, unit(impulse), point, timestep);
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::reflect::Reflect;
+
+use super::*;
+use crate::timestep::springs_running;
+
+/// Grappling hook fired from the translation of this entity, the rigid body
+/// or one of its children.
+///
+/// Send [`FireGrapple`] and [`ReleaseGrapple`] from input handling, and set
+/// `reel` to reel the rope in or out. The rope only pulls, like a real rope,
+/// and hit dynamic bodies are pulled back.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct GrappleSpring {
+    pub spring: Spring,
+    /// Furthest distance the hook reaches and the rope can be reeled out to.
+    pub max_distance: f32,
+    /// Shortest length the rope can be reeled in to.
+    pub min_distance: f32,
+    /// Fastest the rope is reeled in or out, in units per second.
+    pub reel_speed: f32,
+    /// Reeling this frame from -1 (in) to 1 (out), scaled by `reel_speed`.
+    pub reel: f32,
+    /// Stretch past the rest distance at which the rope snaps, never when `None`.
+    pub break_stretch: Option<f32>,
+    /// Length of the rope the spring rests at.
+    pub rest_distance: f32,
+    pub attachment: Option<GrappleAttachment>,
+}
+
+/// What a [`GrappleSpring`] hooked onto.
+#[derive(Debug, Copy, Clone, PartialEq, Reflect)]
+pub struct GrappleAttachment {
+    /// Rigid body that was hit, `None` when hooked onto a fixed point of the world.
+    pub body: Option<Entity>,
+    /// Hit point in the local space of `body`, or in world space without one.
+    pub anchor: Vec3,
+}
+
+impl GrappleSpring {
+    pub fn new(max_distance: f32, spring: Spring) -> Self {
+        Self {
+            spring,
+            max_distance,
+            min_distance: 0.5,
+            reel_speed: 5.0,
+            reel: 0.0,
+            break_stretch: None,
+            rest_distance: 0.0,
+            attachment: None,
+        }
+    }
+
+    pub fn with_reel_speed(mut self, reel_speed: f32) -> Self {
+        self.reel_speed = reel_speed;
+        self
+    }
+
+    pub fn with_break_stretch(mut self, break_stretch: f32) -> Self {
+        self.break_stretch = Some(break_stretch);
+        self
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.attachment.is_some()
+    }
+
+    /// Change the rest distance by the reel input over `timestep` seconds.
+    pub fn reel_by(&mut self, timestep: f32) {
+        let reeled = self.rest_distance + self.reel.clamp(-1.0, 1.0) * self.reel_speed * timestep;
+        self.rest_distance = reeled.clamp(self.min_distance, self.max_distance);
+    }
+}
+
+/// Fire the hook of the `grapple` entity in a world space direction.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct FireGrapple {
+    pub grapple: Entity,
+    pub direction: Vec3,
+}
+
+/// Let go of whatever the `grapple` entity is hooked onto.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct ReleaseGrapple {
+    pub grapple: Entity,
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct GrappleAttached {
+    pub grapple: Entity,
+    pub attachment: GrappleAttachment,
+}
+
+/// Sent when a grapple lets go, including when it breaks.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct GrappleDetached {
+    pub grapple: Entity,
+}
+
+/// Sent when the rope snaps from being stretched past its `break_stretch`,
+/// or the body it was hooked onto was despawned.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct GrappleBroken {
+    pub grapple: Entity,
+}
+
+pub struct GrapplePlugin;
+
+impl Plugin for GrapplePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GrappleSpring>()
+            .add_event::<FireGrapple>()
+            .add_event::<ReleaseGrapple>()
+            .add_event::<GrappleAttached>()
+            .add_event::<GrappleDetached>()
+            .add_event::<GrappleBroken>()
+            .add_systems(Update, fire_grapples)
+            .add_systems(
+                PostUpdate,
+                grapple_spring
+                    .run_if(springs_running)
+                    .after(clear_spring_forces)
+                    .before(apply_spring_velocities),
+            );
+        add_spring_output(app);
+    }
+}
+
+pub fn fire_grapples(
+    rapier_context: Res<RapierContext>,
+    mut fire: EventReader<FireGrapple>,
+    mut release: EventReader<ReleaseGrapple>,
+    mut grapples: Query<(&GlobalTransform, &mut GrappleSpring)>,
+    transforms: Query<&GlobalTransform>,
+    rigid_bodies: Query<&RigidBody>,
+    owners: RigidBodyOwners,
+    mut attached_events: EventWriter<GrappleAttached>,
+    mut detached_events: EventWriter<GrappleDetached>,
+) {
+    for release in release.read() {
+        let Ok((_, mut grapple)) = grapples.get_mut(release.grapple) else {
+            continue;
+        };
+        if grapple.attachment.take().is_some() {
+            detached_events.send(GrappleDetached {
+                grapple: release.grapple,
+            });
+        }
+    }
+
+    for fire in fire.read() {
+        let Ok((transform, mut grapple)) = grapples.get_mut(fire.grapple) else {
+            continue;
+        };
+        let Some(owner) = owners.owner(fire.grapple) else {
+            continue;
+        };
+
+        let origin = unit(transform.translation());
+        let direction = unit(fire.direction).normalize_or_zero();
+        let filter = QueryFilter::default().exclude_rigid_body(owner);
+        let Some((hit, distance)) =
+            rapier_context.cast_ray(origin, direction, grapple.max_distance, true, filter)
+        else {
+            continue;
+        };
+
+        if grapple.attachment.take().is_some() {
+            detached_events.send(GrappleDetached {
+                grapple: fire.grapple,
+            });
+        }
+
+        // Hook onto bodies that can move, everything else is part of the world.
+        let point = extend(origin + direction * distance);
+        let body = owners.owner(hit).filter(|body| {
+            matches!(
+                rigid_bodies.get(*body),
+                Ok(RigidBody::Dynamic
+                    | RigidBody::KinematicPositionBased
+                    | RigidBody::KinematicVelocityBased)
+            )
+        });
+        let anchor = match body.and_then(|body| transforms.get(body).ok()) {
+            Some(body_transform) => body_transform.affine().inverse().transform_point3(point),
+            None => point,
+        };
+
+        let attachment = GrappleAttachment { body, anchor };
+        grapple.attachment = Some(attachment);
+        grapple.rest_distance = distance.clamp(grapple.min_distance, grapple.max_distance);
+        attached_events.send(GrappleAttached {
+            grapple: fire.grapple,
+            attachment,
+        });
+    }
+}
+
+pub fn grapple_spring(
+    time: Res<Time>,
+    mut grapples: Query<(Entity, &mut GrappleSpring, Option<&OutputMode>)>,
+    bodies: Query<RapierParticleQuery>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+    mut detached_events: EventWriter<GrappleDetached>,
+    mut broken_events: EventWriter<GrappleBroken>,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (entity, mut grapple, mode) in &mut grapples {
+        let Some(attachment) = grapple.attachment else {
+            continue;
+        };
+        let mode = mode.copied().unwrap_or_default();
+        let Some(owner) = owners.owner(entity) else {
+            continue;
+        };
+        let (Ok(hook), Ok(body)) = (bodies.get(entity), bodies.get(owner)) else {
+            continue;
+        };
+
+        let target = match attachment.body {
+            Some(target) => match bodies.get(target) {
+                Ok(target) => Some(target),
+                Err(_) => {
+                    grapple.attachment = None;
+                    detached_events.send(GrappleDetached { grapple: entity });
+                    broken_events.send(GrappleBroken { grapple: entity });
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        grapple.reel_by(timestep);
+
+        let origin = unit(hook.global_transform.translation());
+        let anchor = match &target {
+            Some(target) => unit(target.global_transform.transform_point(attachment.anchor)),
+            None => unit(attachment.anchor),
+        };
+        let offset = anchor - origin;
+        let distance = offset.length();
+        let direction = offset.normalize_or_zero();
+
+        if let Some(break_stretch) = grapple.break_stretch {
+            if distance - grapple.rest_distance > break_stretch {
+                grapple.attachment = None;
+                detached_events.send(GrappleDetached { grapple: entity });
+                broken_events.send(GrappleBroken { grapple: entity });
+                continue;
+            }
+        }
+
+        // A rope goes slack when shorter than its rest distance.
+        if distance <= grapple.rest_distance {
+            continue;
+        }
+
+        let target_velocity = target
+            .as_ref()
+            .map(|target| target.velocity_at_point(anchor))
+            .unwrap_or_default();
+        let target_mass = target
+            .as_ref()
+            .map(|target| target.output_mass(mode).mass)
+            .unwrap_or(f32::INFINITY);
+
+        let hook_particle = Particle1 {
+            inertia: body.output_mass(mode).mass,
+            position: distance,
+            velocity: -(body.velocity_at_point(origin) - target_velocity).dot(direction),
+        };
+        let anchor_particle = Particle1 {
+            inertia: target_mass,
+            position: grapple.rest_distance,
+            velocity: 0.0,
+        };
+
+        // Only ever pull the hook towards the anchor.
+        let pull = -grapple
+            .spring
+            .impulse(timestep, hook_particle.instant(&anchor_particle))
+            .min(0.0);
+        let impulse = direction * pull;
+        output.apply_at(mode, &body, impulse, origin, timestep);
+        if let Some(target) = target {
+            output.apply_at(mode, &target, -impulse, anchor, timestep);
+        }
+    }
+}
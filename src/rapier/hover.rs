@@ -0,0 +1,123 @@
+use bevy::reflect::Reflect;
+
+use super::*;
+use crate::timestep::springs_running;
+
+/// Keeps a rigid body floating at a ride height above the ground with a 1D
+/// spring along a downward raycast, the "floating capsule" character controller.
+///
+/// The ground receives the opposite impulse at the point that was hit, so
+/// standing on dynamic bodies pushes them down.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component)]
+pub struct HoverSpring {
+    /// Desired distance between the origin of the body and the ground.
+    pub ride_height: f32,
+    /// Furthest distance from the origin of the body the ground is detected.
+    pub max_distance: f32,
+    pub spring: Spring,
+    /// Ground below the body, `None` when airborne.
+    pub ground: Option<GroundHit>,
+}
+
+impl HoverSpring {
+    pub fn new(ride_height: f32, spring: Spring) -> Self {
+        Self {
+            ride_height,
+            max_distance: ride_height * 1.5,
+            spring,
+            ground: None,
+        }
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.ground.is_some()
+    }
+}
+
+pub struct HoverPlugin;
+
+impl Plugin for HoverPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<HoverSpring>().add_systems(
+            PostUpdate,
+            hover_spring
+                .run_if(springs_running)
+                .after(clear_spring_forces)
+                .before(apply_spring_velocities),
+        );
+        add_spring_output(app);
+    }
+}
+
+pub fn hover_spring(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut hovering: Query<(Entity, &mut HoverSpring, Option<&OutputMode>)>,
+    bodies: Query<RapierParticleQuery>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (entity, mut hover, mode) in &mut hovering {
+        let mode = mode.copied().unwrap_or_default();
+        let Some(owner) = owners.owner(entity) else {
+            continue;
+        };
+        let (Ok(anchor), Ok(body)) = (bodies.get(entity), bodies.get(owner)) else {
+            continue;
+        };
+
+        let origin = unit(anchor.global_transform.translation());
+        let down = unit(Vec3::NEG_Y);
+
+        hover.ground = cast_ground(&rapier_context, origin, down, hover.max_distance, owner);
+        let Some(ground) = hover.ground else {
+            continue;
+        };
+
+        let ground_body = owners
+            .owner(ground.entity)
+            .and_then(|ground_owner| bodies.get(ground_owner).ok());
+        let ground_velocity = ground_body
+            .as_ref()
+            .map(|ground_body| ground_body.velocity_at_point(ground.point))
+            .unwrap_or_default();
+        let ground_mass = ground_body
+            .as_ref()
+            .map(|ground_body| ground_body.output_mass(mode).mass)
+            .unwrap_or(f32::INFINITY);
+
+        let hovering_particle = Particle1 {
+            inertia: body.output_mass(mode).mass,
+            position: ground.distance,
+            velocity: -(body.velocity_at_point(origin) - ground_velocity).dot(down),
+        };
+        let ground_particle = Particle1 {
+            inertia: ground_mass,
+            position: hover.ride_height,
+            velocity: 0.0,
+        };
+
+        let push = hover
+            .spring
+            .impulse(timestep, hovering_particle.instant(&ground_particle));
+        let impulse = -down * push;
+        // A child collider pushes its body off-center, the body itself is kept upright.
+        let point = if owner == entity {
+            body.center_of_mass()
+        } else {
+            origin
+        };
+        output.apply_at(mode, &body, impulse, point, timestep);
+
+        // Push the ground back at the point we are standing on.
+        if let Some(ground_body) = ground_body {
+            output.apply_at(mode, &ground_body, -impulse, ground.point, timestep);
+        }
+    }
+}
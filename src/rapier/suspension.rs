@@ -0,0 +1,109 @@
+use bevy::ecs::entity::{EntityMapper, MapEntities};
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::reflect::Reflect;
+
+use super::*;
+use crate::timestep::springs_running;
+
+/// Raycast suspension pushing the chassis up from the ground at the
+/// translation of this entity, usually a child of the chassis.
+///
+/// The ray is cast along the down axis of the chassis.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SuspensionWheel {
+    /// Rigid body the suspension pushes on, or one of its child colliders.
+    pub chassis: Entity,
+    /// Length of the suspension at rest, measured from the anchor.
+    pub rest_length: f32,
+    /// Furthest distance from the anchor the ground is detected.
+    pub max_length: f32,
+    pub spring: Spring,
+    /// Distance from the anchor to the ground, `None` when the wheel is in the air.
+    pub contact: Option<f32>,
+}
+
+impl MapEntities for SuspensionWheel {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.chassis = entity_mapper.map_entity(self.chassis);
+    }
+}
+
+impl SuspensionWheel {
+    pub fn new(chassis: Entity, rest_length: f32, spring: Spring) -> Self {
+        Self {
+            chassis,
+            rest_length,
+            max_length: rest_length * 1.5,
+            spring,
+            contact: None,
+        }
+    }
+}
+
+pub struct SuspensionPlugin;
+
+impl Plugin for SuspensionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SuspensionWheel>().add_systems(
+            PostUpdate,
+            suspension
+                .run_if(springs_running)
+                .after(clear_spring_forces)
+                .before(apply_spring_velocities),
+        );
+        add_spring_output(app);
+    }
+}
+
+pub fn suspension(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut wheels: Query<(&GlobalTransform, &mut SuspensionWheel, Option<&OutputMode>)>,
+    chassis: Query<RapierParticleQuery>,
+    owners: RigidBodyOwners,
+    mut output: RapierOutput,
+) {
+    let timestep = time.delta_seconds();
+    if timestep == 0.0 {
+        return;
+    }
+
+    for (anchor_transform, mut wheel, mode) in &mut wheels {
+        let mode = mode.copied().unwrap_or_default();
+        let Some(owner) = owners.owner(wheel.chassis) else {
+            continue;
+        };
+        let Ok(body) = chassis.get(owner) else {
+            continue;
+        };
+
+        let anchor = unit(anchor_transform.translation());
+        let down = unit(body.global_transform.compute_transform().rotation * Vec3::NEG_Y);
+
+        wheel.contact = cast_ground(&rapier_context, anchor, down, wheel.max_length, owner)
+            .map(|hit| hit.distance);
+
+        let Some(distance) = wheel.contact else {
+            continue;
+        };
+
+        let wheel_particle = Particle1 {
+            inertia: body.output_mass(mode).mass,
+            position: distance,
+            velocity: -body.velocity_at_point(anchor).dot(down),
+        };
+        let ground = Particle1 {
+            inertia: f32::INFINITY,
+            position: wheel.rest_length,
+            velocity: 0.0,
+        };
+
+        // Suspension can only push the chassis away from the ground.
+        let push = wheel
+            .spring
+            .impulse(timestep, wheel_particle.instant(&ground))
+            .max(0.0);
+        output.apply_at(mode, &body, -down * push, anchor, timestep);
+    }
+}
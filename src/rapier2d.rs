@@ -0,0 +1,152 @@
+//! Springs on `bevy_rapier2d` rigid bodies.
+use bevy::ecs::query::QueryData;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::parry::{
+    self,
+    math::{Isometry, Real},
+};
+
+use crate::*;
+
+#[path = "rapier/buoyancy.rs"]
+pub mod buoyancy;
+#[path = "rapier/closest.rs"]
+pub mod closest;
+#[path = "rapier/common.rs"]
+mod common;
+#[path = "rapier/field.rs"]
+pub mod field;
+#[path = "rapier/grapple.rs"]
+pub mod grapple;
+#[path = "rapier/hover.rs"]
+pub mod hover;
+#[path = "rapier/suspension.rs"]
+pub mod suspension;
+
+pub use buoyancy::BuoyancyPlugin;
+pub use closest::{ClosestPointSpring, ClosestPointSpringPlugin};
+pub use common::*;
+pub use field::SpringFieldPlugin;
+pub use grapple::{
+    FireGrapple, GrappleAttached, GrappleAttachment, GrappleBroken, GrappleDetached, GrapplePlugin,
+    GrappleSpring, ReleaseGrapple,
+};
+pub use hover::{HoverPlugin, HoverSpring};
+pub use suspension::{SuspensionPlugin, SuspensionWheel};
+
+#[derive(QueryData)]
+pub struct RapierParticleQuery2<'a> {
+    pub entity: Entity,
+    pub global_transform: &'a GlobalTransform,
+    pub rigid_body: Option<&'a RigidBody>,
+    pub velocity: Option<&'a Velocity>,
+    pub mass: Option<&'a ReadMassProperties>,
+    pub name: Option<&'a Name>,
+}
+
+pub type RapierParticleQuery<'a> = RapierParticleQuery2<'a>;
+pub type RapierParticleQueryItem<'w, 's> = RapierParticleQuery2Item<'w, 's>;
+
+pub type Unit = Vec2;
+pub type Torque = f32;
+
+/// Project a world space vector onto the plane rapier simulates in.
+pub fn unit(vector: Vec3) -> Unit {
+    vector.xy()
+}
+
+/// World space vector of a rapier vector.
+pub fn extend(unit: Unit) -> Vec3 {
+    unit.extend(0.0)
+}
+
+/// World space axis and magnitude of a rapier torque, around Z in 2D.
+pub fn extend_torque(torque: Torque) -> Vec3 {
+    Vec3::Z * torque
+}
+
+/// Rapier torque of a world space axis and magnitude.
+pub fn torque(vector: Vec3) -> Torque {
+    vector.z
+}
+
+/// Angular velocity of a rapier body.
+pub fn angvel(body: &bevy_rapier2d::rapier::dynamics::RigidBody) -> Torque {
+    body.angvel()
+}
+
+/// Rapier isometry of a transform, rotated around Z only.
+pub fn transform_to_iso(transform: &Transform) -> Isometry<Real> {
+    Isometry::new(
+        transform.translation.xy().into(),
+        transform.rotation.to_scaled_axis().z,
+    )
+}
+
+/// Principal inertia of the body as a vector, 2D bodies only rotate around Z.
+pub fn principal_inertia(mass: &MassProperties) -> Vec3 {
+    Vec3::splat(mass.principal_inertia)
+}
+
+impl<'w, 's> RapierParticleQuery2Item<'w, 's> {
+    /// Center of mass of the body in world space.
+    pub fn center_of_mass(&self) -> Unit {
+        let local = self.mass().local_center_of_mass;
+        self.global_transform
+            .transform_point(local.extend(0.0))
+            .xy()
+    }
+
+    /// Velocity of the body at a point in world space.
+    pub fn velocity_at_point(&self, point: Unit) -> Unit {
+        let velocity = self.velocity();
+        let lever = point - self.center_of_mass();
+        velocity.linvel + lever.perp() * velocity.angvel
+    }
+
+    /// Torque around the center of mass from a force or impulse applied at a
+    /// point in world space.
+    pub fn torque_at(&self, vector: Unit, point: Unit) -> Torque {
+        (point - self.center_of_mass()).perp_dot(vector)
+    }
+
+    /// Change in angular velocity from an angular impulse for the given mass properties.
+    pub fn angular_velocity_change(&self, mass: &MassProperties, torque: Torque) -> Torque {
+        torque * mass.principal_inertia.inverse()
+    }
+
+    pub fn translation(&self) -> TranslationParticle2 {
+        self.anchor(Vec2::ZERO)
+    }
+
+    /// Particle at a point in the local space of the body, scaled along with
+    /// the body and any scaled parents.
+    pub fn anchor(&self, local_anchor: Vec2) -> TranslationParticle2 {
+        let translation = self
+            .global_transform
+            .transform_point(local_anchor.extend(0.0))
+            .xy();
+        // Rapier reports the linear velocity at the center of mass, so sample
+        // it at the anchor instead.
+        TranslationParticle2 {
+            translation,
+            velocity: self.velocity_at_point(translation),
+            mass: self.mass().mass,
+        }
+    }
+
+    pub fn angular(&self) -> AngularParticle2 {
+        let velocity = self.velocity();
+        let mass = self.mass();
+        // Transform the axis as a vector so mirrored bodies keep their angle.
+        let vector = self.global_transform.affine().transform_vector3(Vec3::X);
+        let angle = vector.y.atan2(vector.x);
+        AngularParticle2 {
+            rotation: angle,
+            velocity: velocity.angvel,
+            inertia: mass.principal_inertia,
+        }
+    }
+}
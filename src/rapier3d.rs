@@ -0,0 +1,206 @@
+//! Springs on `bevy_rapier3d` rigid bodies.
+use bevy::ecs::query::QueryData;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use bevy_rapier3d::parry::{
+    self,
+    math::{Isometry, Real},
+};
+
+use crate::*;
+
+#[path = "rapier/buoyancy.rs"]
+pub mod buoyancy;
+#[path = "rapier/closest.rs"]
+pub mod closest;
+#[path = "rapier/common.rs"]
+mod common;
+#[path = "rapier/field.rs"]
+pub mod field;
+#[path = "rapier/grapple.rs"]
+pub mod grapple;
+#[path = "rapier/hover.rs"]
+pub mod hover;
+#[path = "rapier/suspension.rs"]
+pub mod suspension;
+
+pub use buoyancy::BuoyancyPlugin;
+pub use closest::{ClosestPointSpring, ClosestPointSpringPlugin};
+pub use common::*;
+pub use field::SpringFieldPlugin;
+pub use grapple::{
+    FireGrapple, GrappleAttached, GrappleAttachment, GrappleBroken, GrappleDetached, GrapplePlugin,
+    GrappleSpring, ReleaseGrapple,
+};
+pub use hover::{HoverPlugin, HoverSpring};
+pub use suspension::{SuspensionPlugin, SuspensionWheel};
+
+#[derive(QueryData)]
+pub struct RapierParticleQuery3<'a> {
+    pub entity: Entity,
+    pub global_transform: &'a GlobalTransform,
+    pub rigid_body: Option<&'a RigidBody>,
+    pub velocity: Option<&'a Velocity>,
+    pub mass: Option<&'a ReadMassProperties>,
+    pub name: Option<&'a Name>,
+}
+
+pub type RapierParticleQuery<'a> = RapierParticleQuery3<'a>;
+pub type RapierParticleQueryItem<'w, 's> = RapierParticleQuery3Item<'w, 's>;
+
+pub type Unit = Vec3;
+pub type Torque = Vec3;
+
+/// Project a world space vector onto the space rapier simulates in.
+pub fn unit(vector: Vec3) -> Unit {
+    vector
+}
+
+/// World space vector of a rapier vector.
+pub fn extend(unit: Unit) -> Vec3 {
+    unit
+}
+
+/// World space axis and magnitude of a rapier torque.
+pub fn extend_torque(torque: Torque) -> Vec3 {
+    torque
+}
+
+/// Rapier torque of a world space axis and magnitude.
+pub fn torque(vector: Vec3) -> Torque {
+    vector
+}
+
+/// Angular velocity of a rapier body.
+pub fn angvel(body: &bevy_rapier3d::rapier::dynamics::RigidBody) -> Torque {
+    (*body.angvel()).into()
+}
+
+/// Rapier isometry of a transform.
+pub fn transform_to_iso(transform: &Transform) -> Isometry<Real> {
+    Isometry::from_parts(transform.translation.into(), transform.rotation.into())
+}
+
+/// Principal inertia of the body along its principal axes.
+pub fn principal_inertia(mass: &MassProperties) -> Vec3 {
+    mass.principal_inertia
+}
+
+impl<'w, 's> RapierParticleQuery3Item<'w, 's> {
+    /// Center of mass of the body in world space.
+    pub fn center_of_mass(&self) -> Unit {
+        let local = self.mass().local_center_of_mass;
+        self.global_transform.transform_point(local)
+    }
+
+    /// Velocity of the body at a point in world space.
+    pub fn velocity_at_point(&self, point: Unit) -> Unit {
+        let velocity = self.velocity();
+        let lever = point - self.center_of_mass();
+        velocity.linvel + velocity.angvel.cross(lever)
+    }
+
+    /// Torque around the center of mass from a force or impulse applied at a
+    /// point in world space.
+    pub fn torque_at(&self, vector: Unit, point: Unit) -> Torque {
+        (point - self.center_of_mass()).cross(vector)
+    }
+
+    pub fn translation(&self) -> TranslationParticle3 {
+        self.anchor(Vec3::ZERO)
+    }
+
+    /// Particle at a point in the local space of the body, scaled along with
+    /// the body and any scaled parents.
+    pub fn anchor(&self, local_anchor: Vec3) -> TranslationParticle3 {
+        let translation = self.global_transform.transform_point(local_anchor);
+        // Rapier reports the linear velocity at the center of mass, so sample
+        // it at the anchor instead.
+        TranslationParticle3 {
+            translation,
+            velocity: self.velocity_at_point(translation),
+            mass: self.mass().mass,
+        }
+    }
+
+    /// Inverse of the inertia tensor in world space, rotating the principal
+    /// inertia by its local frame and the rotation of the body.
+    ///
+    /// Fixed and kinematic bodies have an infinite inertia, so this is zero.
+    pub fn inverse_inertia_tensor(&self) -> Mat3 {
+        self.inverse_inertia_tensor_of(&self.mass())
+    }
+
+    /// Inverse of the inertia tensor in world space for the given mass properties.
+    pub fn inverse_inertia_tensor_of(&self, mass: &MassProperties) -> Mat3 {
+        let rotation =
+            self.global_transform.compute_transform().rotation * mass.principal_inertia_local_frame;
+        let frame = Mat3::from_quat(rotation);
+        frame * Mat3::from_diagonal(mass.principal_inertia.inverse()) * frame.transpose()
+    }
+
+    /// Change in angular velocity from an angular impulse for the given mass properties.
+    pub fn angular_velocity_change(&self, mass: &MassProperties, torque: Torque) -> Torque {
+        self.inverse_inertia_tensor_of(mass) * torque
+    }
+
+    /// Inertia of the body resisting rotation around a world space axis.
+    pub fn inertia_along(&self, axis: Vec3) -> f32 {
+        let axis = axis.normalize_or_zero();
+        axis.dot(self.inverse_inertia_tensor() * axis).inverse()
+    }
+
+    /// Angular particle aligning the local `axis` of the body.
+    ///
+    /// The inertia is the resistance to rotation around each world axis,
+    /// use [`Self::angular_instant`] for the exact inertia around the axis
+    /// the spring rotates on.
+    pub fn angular(&self, axis: Vec3) -> AngularParticle3 {
+        let velocity = self.velocity();
+        AngularParticle3 {
+            // Transform the axis as a vector so mirrored and non-uniformly
+            // scaled bodies point it the way it is rendered.
+            direction: self
+                .global_transform
+                .affine()
+                .transform_vector3(axis)
+                .normalize_or_zero(),
+            velocity: velocity.angvel,
+            inertia: Vec3::new(
+                self.inertia_along(Vec3::X),
+                self.inertia_along(Vec3::Y),
+                self.inertia_along(Vec3::Z),
+            ),
+        }
+    }
+
+    /// Spring instant aligning the local `axis` of both bodies, with the
+    /// reduced inertia around the axis of rotation from the full inertia tensors.
+    pub fn angular_instant(&self, other: &Self, axis: Vec3) -> SpringInstant<Vec3> {
+        let mut instant = self.angular(axis).instant(&other.angular(axis));
+
+        let rotation_axis = instant.displacement.normalize_or_zero();
+        let rotation_axis = if rotation_axis == Vec3::ZERO {
+            instant.velocity.normalize_or_zero()
+        } else {
+            rotation_axis
+        };
+
+        let inverse_inertia = rotation_axis
+            .dot((self.inverse_inertia_tensor() + other.inverse_inertia_tensor()) * rotation_axis);
+        instant.reduced_inertia = Vec3::splat(inverse_inertia.inverse());
+        instant
+    }
+
+    pub fn angular_x(&self) -> AngularParticle3 {
+        self.angular(Vec3::X)
+    }
+
+    pub fn angular_y(&self) -> AngularParticle3 {
+        self.angular(Vec3::Y)
+    }
+
+    pub fn angular_z(&self) -> AngularParticle3 {
+        self.angular(Vec3::Z)
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::{prelude::*, reflect::Reflect, transform::TransformSystem};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Recoil of a weapon viewmodel or turret, kicking the `Transform` of this
+/// entity away from its rest pose with [`RecoilSpring::fire`] and springing
+/// it back.
+///
+/// The kick is in the local space of the rest pose, e.g. `Vec3::Z` pushes a
+/// viewmodel back towards the camera and an angular kick around `Vec3::X`
+/// pitches it up.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct RecoilSpring {
+    pub translation: Spring,
+    pub rotation: Spring,
+    /// Change in velocity of the translation per shot.
+    pub kick: Vec3,
+    /// Change in angular velocity per shot, as a scaled axis in radians per second.
+    pub angular_kick: Vec3,
+    /// Pose the recoil springs back to, taken from the `Transform` the first
+    /// time it runs when `None`.
+    pub rest: Option<Transform>,
+    /// Current offset of the translation from the rest pose.
+    pub offset: Vec3,
+    pub velocity: Vec3,
+    /// Current offset of the rotation from the rest pose, as a scaled axis.
+    pub angular_offset: Vec3,
+    pub angular_velocity: Vec3,
+}
+
+impl RecoilSpring {
+    pub fn new(kick: Vec3, angular_kick: Vec3) -> Self {
+        Self {
+            translation: Spring::snappy(),
+            rotation: Spring::snappy(),
+            kick,
+            angular_kick,
+            rest: None,
+            offset: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            angular_offset: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_translation_spring(mut self, spring: Spring) -> Self {
+        self.translation = spring;
+        self
+    }
+
+    pub fn with_rotation_spring(mut self, spring: Spring) -> Self {
+        self.rotation = spring;
+        self
+    }
+
+    pub fn with_rest(mut self, rest: Transform) -> Self {
+        self.rest = Some(rest);
+        self
+    }
+
+    /// Kick the recoil for one shot.
+    pub fn fire(&mut self) {
+        self.fire_scaled(1.0);
+    }
+
+    /// Kick the recoil scaled by `scale`, e.g. lower while aiming down sights.
+    pub fn fire_scaled(&mut self, scale: f32) {
+        self.velocity += self.kick * scale;
+        self.angular_velocity += self.angular_kick * scale;
+    }
+
+    /// Advance the recoil back towards rest by one timestep.
+    pub fn step(&mut self, timestep: f32) {
+        let instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: self.offset,
+            velocity: self.velocity,
+        };
+        self.velocity += self.translation.impulse(timestep, instant);
+        self.offset += self.velocity * timestep;
+
+        let angular_instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: self.angular_offset,
+            velocity: self.angular_velocity,
+        };
+        self.angular_velocity += self.rotation.impulse(timestep, angular_instant);
+        self.angular_offset += self.angular_velocity * timestep;
+    }
+
+    /// Rest pose offset by the current recoil.
+    pub fn transform(&self, rest: &Transform) -> Transform {
+        Transform {
+            translation: rest.translation + rest.rotation * self.offset,
+            rotation: rest.rotation * Quat::from_scaled_axis(self.angular_offset),
+            scale: rest.scale,
+        }
+    }
+}
+
+pub struct RecoilPlugin;
+
+impl Plugin for RecoilPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RecoilSpring>()
+            .register_type::<SpringClock>()
+            .add_systems(
+                PostUpdate,
+                recoil_spring
+                    .run_if(springs_running)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn recoil_spring(
+    clocks: SpringClocks,
+    mut recoils: Query<(&mut Transform, &mut RecoilSpring, Option<&SpringClock>)>,
+) {
+    for (mut transform, mut recoil, clock) in &mut recoils {
+        let timestep = clocks.delta(clock);
+        if timestep == 0.0 {
+            continue;
+        }
+
+        let rest = *recoil.rest.get_or_insert(*transform);
+        recoil.step(timestep);
+        *transform = recoil.transform(&rest);
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::dangling::SprungBy;
+use crate::gear::GearSpring;
+use crate::group::SpringGroups;
+use crate::integration::{Impulse, PreviousTranslation, Velocity};
+use crate::joint::{SpringJoint, SpringState};
+use crate::lifetime::SpringLifetime;
+use crate::sleep::SpringSleep;
+use crate::tear::Tearable;
+use crate::snap::SnapSlot;
+use crate::timestep::{SpringTimestep, SpringsPaused};
+use crate::torsion::WindUp;
+use crate::winch::Winch;
+use crate::*;
+
+/// Receives every type holding state the springs mutate while simulating,
+/// implement it to register the types with a rollback library.
+///
+/// With bevy_ggrs this is a wrapper around the `App` calling
+/// `rollback_component_with_clone::<C>()` and `rollback_resource_with_clone::<R>()`,
+/// then [`rollback_types`] registers everything the springs need restored:
+///
+/// ```ignore
+/// struct GgrsTypes<'a>(&'a mut App);
+///
+/// impl RollbackTypes for GgrsTypes<'_> {
+///     fn component<C: Component + Clone>(&mut self) {
+///         self.0.rollback_component_with_clone::<C>();
+///     }
+///
+///     fn resource<R: Resource + Clone>(&mut self) {
+///         self.0.rollback_resource_with_clone::<R>();
+///     }
+/// }
+///
+/// rollback_types(&mut GgrsTypes(&mut app));
+/// ```
+pub trait RollbackTypes {
+    fn component<C: Component + Clone>(&mut self);
+    fn resource<R: Resource + Clone>(&mut self);
+}
+
+/// Visit the state of the springs that has to be rolled back for the
+/// simulation to be deterministic.
+///
+/// Settings like [`Spring`] or [`Inertia`](crate::integration::Inertia) are
+/// left out unless the game changes them during a rollback window, and the
+/// solver keeps no warm-start data besides the last impulse in [`SpringState`].
+/// [`SpringValue`](crate::value::SpringValue) is generic, so register the
+/// `SpringValue<T>` the game uses next to these.
+pub fn rollback_types<V: RollbackTypes>(visitor: &mut V) {
+    visitor.component::<Transform>();
+    visitor.component::<GlobalTransform>();
+    visitor.component::<Velocity>();
+    visitor.component::<Impulse>();
+    visitor.component::<PreviousTranslation>();
+    visitor.component::<SpringJoint>();
+    visitor.component::<SpringState>();
+    visitor.component::<SpringSleep>();
+    visitor.component::<SpringLifetime>();
+    visitor.component::<SprungBy>();
+    visitor.component::<WindUp>();
+    visitor.component::<GearSpring>();
+    visitor.component::<SnapSlot>();
+    visitor.component::<Winch>();
+    visitor.component::<Tearable>();
+    visitor.component::<follow::SpringFollow>();
+    visitor.component::<transform_spring::TransformSpring>();
+    #[cfg(feature = "jiggle")]
+    visitor.component::<jiggle::JiggleBone>();
+    #[cfg(feature = "spring_arm")]
+    visitor.component::<spring_arm::SpringArm>();
+    #[cfg(feature = "ui")]
+    visitor.component::<ui::UiSpring>();
+
+    visitor.resource::<SpringTimestep>();
+    visitor.resource::<SpringsPaused>();
+    visitor.resource::<SpringGroups>();
+}
+
+/// Copy of the rollback state of the springs at one point in time, for
+/// rollback without a library or saving the state for a replay.
+///
+/// Restoring a snapshot also removes the components from entities that did
+/// not have them when it was saved, entities spawned or despawned since are
+/// left to the caller.
+#[derive(Default)]
+pub struct SpringSnapshot {
+    entries: Vec<Box<dyn SnapshotEntry>>,
+}
+
+impl SpringSnapshot {
+    pub fn save(world: &mut World) -> Self {
+        let mut saver = SnapshotSaver {
+            world,
+            entries: Vec::new(),
+        };
+        rollback_types(&mut saver);
+        Self {
+            entries: saver.entries,
+        }
+    }
+
+    pub fn restore(&self, world: &mut World) {
+        for entry in &self.entries {
+            entry.restore(world);
+        }
+    }
+}
+
+trait SnapshotEntry: Send + Sync {
+    fn restore(&self, world: &mut World);
+}
+
+struct ComponentSnapshot<C>(EntityHashMap<C>);
+
+impl<C: Component + Clone> SnapshotEntry for ComponentSnapshot<C> {
+    fn restore(&self, world: &mut World) {
+        let stale = world
+            .query_filtered::<Entity, With<C>>()
+            .iter(world)
+            .filter(|entity| !self.0.contains_key(entity))
+            .collect::<Vec<_>>();
+        for entity in stale {
+            world.entity_mut(entity).remove::<C>();
+        }
+
+        for (entity, component) in &self.0 {
+            if let Some(mut entity) = world.get_entity_mut(*entity) {
+                entity.insert(component.clone());
+            }
+        }
+    }
+}
+
+struct ResourceSnapshot<R>(Option<R>);
+
+impl<R: Resource + Clone> SnapshotEntry for ResourceSnapshot<R> {
+    fn restore(&self, world: &mut World) {
+        match &self.0 {
+            Some(resource) => world.insert_resource(resource.clone()),
+            None => {
+                world.remove_resource::<R>();
+            }
+        }
+    }
+}
+
+struct SnapshotSaver<'w> {
+    world: &'w mut World,
+    entries: Vec<Box<dyn SnapshotEntry>>,
+}
+
+impl RollbackTypes for SnapshotSaver<'_> {
+    fn component<C: Component + Clone>(&mut self) {
+        let components = self
+            .world
+            .query::<(Entity, &C)>()
+            .iter(self.world)
+            .map(|(entity, component)| (entity, component.clone()))
+            .collect();
+        self.entries
+            .push(Box::new(ComponentSnapshot::<C>(components)));
+    }
+
+    fn resource<R: Resource + Clone>(&mut self) {
+        let resource = self.world.get_resource::<R>().cloned();
+        self.entries.push(Box::new(ResourceSnapshot(resource)));
+    }
+}
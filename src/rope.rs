@@ -0,0 +1,379 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+        system::SystemParam,
+        world::Command,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::joint::{SpringJoint, SpringState};
+
+/// Chain of springs making up a rope, for cutting it with [`RopeCutter`].
+///
+/// Every link is an entity with a [`SpringJoint`] whose `containing` entity
+/// is the previous link, or the root of the rope for the first one.
+#[derive(Default, Debug, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct Rope {
+    /// Entities with the joints of the rope, from the root to the tip.
+    pub links: Vec<Entity>,
+}
+
+impl MapEntities for Rope {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for link in &mut self.links {
+            *link = entity_mapper.map_entity(*link);
+        }
+    }
+}
+
+impl Rope {
+    pub fn new(links: impl IntoIterator<Item = Entity>) -> Self {
+        Self {
+            links: links.into_iter().collect(),
+        }
+    }
+}
+
+/// Length of a [`Rope`] compared to its rest length, for switching swinging
+/// mechanics between ballistic flight while slack and a pendulum while taut.
+///
+/// Inserted on every rope, [`RopeTaut`] and [`RopeSlack`] are sent when the
+/// rope transitions between the two.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct RopeState {
+    /// Sum of the current lengths of the links.
+    pub length: f32,
+    /// Sum of the rest lengths of the links.
+    pub rest_length: f32,
+    /// Fraction of the rest length above which the rope becomes taut.
+    pub taut_ratio: f32,
+    /// Fraction of the rest length below which the rope becomes slack again,
+    /// lower than `taut_ratio` so the rope doesn't flicker between the two.
+    pub slack_ratio: f32,
+    taut: bool,
+}
+
+impl Default for RopeState {
+    fn default() -> Self {
+        Self {
+            length: 0.0,
+            rest_length: 0.0,
+            taut_ratio: 1.0,
+            slack_ratio: 0.98,
+            taut: false,
+        }
+    }
+}
+
+impl RopeState {
+    pub fn with_ratios(mut self, taut_ratio: f32, slack_ratio: f32) -> Self {
+        self.taut_ratio = taut_ratio;
+        self.slack_ratio = slack_ratio;
+        self
+    }
+
+    pub fn is_taut(&self) -> bool {
+        self.taut
+    }
+
+    /// Current length relative to the rest length, 1 when at rest and 0 for
+    /// a rope without a rest length.
+    pub fn ratio(&self) -> f32 {
+        if self.rest_length <= 0.0 {
+            return 0.0;
+        }
+
+        self.length / self.rest_length
+    }
+}
+
+/// Sent when a rope stretches past its `taut_ratio`.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct RopeTaut {
+    pub rope: Entity,
+    pub length: f32,
+    pub rest_length: f32,
+}
+
+/// Sent when a taut rope shortens below its `slack_ratio`.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct RopeSlack {
+    pub rope: Entity,
+    pub length: f32,
+    pub rest_length: f32,
+}
+
+pub fn insert_rope_state(
+    mut commands: Commands,
+    ropes: Query<Entity, (With<Rope>, Without<RopeState>)>,
+) {
+    for entity in &ropes {
+        commands.entity(entity).insert(RopeState::default());
+    }
+}
+
+/// Sum up the lengths of the links of every rope and send the transitions
+/// between taut and slack.
+pub fn rope_state(
+    mut ropes: Query<(Entity, &Rope, &mut RopeState)>,
+    links: Query<(&SpringJoint, &SpringState)>,
+    mut taut_events: EventWriter<RopeTaut>,
+    mut slack_events: EventWriter<RopeSlack>,
+) {
+    for (rope, rope_links, mut state) in &mut ropes {
+        let (length, rest_length) = links.iter_many(&rope_links.links).fold(
+            (0.0, 0.0),
+            |(length, rest_length), (joint, link)| {
+                (length + link.length, rest_length + joint.rest_length)
+            },
+        );
+        state.length = length;
+        state.rest_length = rest_length;
+
+        let ratio = state.ratio();
+        if !state.taut && rest_length > 0.0 && ratio > state.taut_ratio {
+            state.taut = true;
+            taut_events.send(RopeTaut {
+                rope,
+                length,
+                rest_length,
+            });
+        } else if state.taut && ratio < state.slack_ratio {
+            state.taut = false;
+            slack_events.send(RopeSlack {
+                rope,
+                length,
+                rest_length,
+            });
+        }
+    }
+}
+
+/// Link of a rope found by [`RopeCutter`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RopeHit {
+    pub rope: Entity,
+    /// Entity with the joint of the link.
+    pub link: Entity,
+    /// Distance between the link and what was cut with.
+    pub distance: f32,
+}
+
+/// Sent when a rope was cut, `end` is the new tip of the `rope` and
+/// `new_end` the root of the `new_rope` holding the links past the cut.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct RopeCut {
+    pub rope: Entity,
+    /// Rope of the links past the cut, `None` when the last link was cut.
+    pub new_rope: Option<Entity>,
+    pub end: Entity,
+    pub new_end: Entity,
+}
+
+/// Finds the links of [`Rope`]s closest to a blade, see [`CutRope`] for
+/// severing them.
+#[derive(SystemParam)]
+pub struct RopeCutter<'w, 's> {
+    ropes: Query<'w, 's, (Entity, &'static Rope)>,
+    joints: Query<'w, 's, &'static SpringJoint>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+}
+
+impl<'w, 's> RopeCutter<'w, 's> {
+    /// Link of any rope closest to the world space segment from `start` to
+    /// `end`, e.g. the swing of a blade, within `max_distance`.
+    pub fn nearest_link(&self, start: Vec3, end: Vec3, max_distance: f32) -> Option<RopeHit> {
+        let mut nearest: Option<RopeHit> = None;
+        for (rope, links) in &self.ropes {
+            for link in &links.links {
+                let Ok(joint) = self.joints.get(*link) else {
+                    continue;
+                };
+                let (Ok(a), Ok(b)) = (
+                    self.transforms.get(*link),
+                    self.transforms.get(joint.containing),
+                ) else {
+                    continue;
+                };
+
+                let distance = segment_distance(start, end, a.translation(), b.translation());
+                if distance <= max_distance && nearest.is_none_or(|hit| distance < hit.distance) {
+                    nearest = Some(RopeHit {
+                        rope,
+                        link: *link,
+                        distance,
+                    });
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Link of any rope closest to a point in world space within `max_distance`.
+    pub fn nearest_link_to_point(&self, point: Vec3, max_distance: f32) -> Option<RopeHit> {
+        self.nearest_link(point, point, max_distance)
+    }
+
+    /// Cut the link of any rope closest to the segment, returning the link that was cut.
+    pub fn cut(
+        &self,
+        commands: &mut Commands,
+        start: Vec3,
+        end: Vec3,
+        max_distance: f32,
+    ) -> Option<RopeHit> {
+        let hit = self.nearest_link(start, end, max_distance)?;
+        commands.add(CutRope {
+            rope: hit.rope,
+            link: hit.link,
+        });
+        Some(hit)
+    }
+}
+
+/// Shortest distance between the segments from `a0` to `a1` and from `b0` to `b1`.
+pub fn segment_distance(a0: Vec3, a1: Vec3, b0: Vec3, b1: Vec3) -> f32 {
+    let (u, v, w) = (a1 - a0, b1 - b0, a0 - b0);
+    let (a, b, c) = (u.dot(u), u.dot(v), v.dot(v));
+    let (d, e) = (u.dot(w), v.dot(w));
+
+    let (s, t) = if a <= f32::EPSILON && c <= f32::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f32::EPSILON {
+        (0.0, (e / c).clamp(0.0, 1.0))
+    } else if c <= f32::EPSILON {
+        ((-d / a).clamp(0.0, 1.0), 0.0)
+    } else {
+        let denominator = a * c - b * b;
+        let s = if denominator > f32::EPSILON {
+            ((b * e - c * d) / denominator).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        // Closest point on the second segment to the first, clamped back onto
+        // the first segment when it falls outside of the second.
+        let t = (b * s + e) / c;
+        if t < 0.0 {
+            ((-d / a).clamp(0.0, 1.0), 0.0)
+        } else if t > 1.0 {
+            (((b - d) / a).clamp(0.0, 1.0), 1.0)
+        } else {
+            (s, t)
+        }
+    };
+
+    (a0 + u * s).distance(b0 + v * t)
+}
+
+/// Sever the `link` of a `rope`, moving the links past it to a new rope and
+/// sending a [`RopeCut`].
+pub struct CutRope {
+    pub rope: Entity,
+    pub link: Entity,
+}
+
+impl Command for CutRope {
+    fn apply(self, world: &mut World) {
+        let Some(joint) = world.get::<SpringJoint>(self.link).copied() else {
+            return;
+        };
+        let Some(mut rope) = world.get_mut::<Rope>(self.rope) else {
+            return;
+        };
+        let Some(index) = rope.links.iter().position(|link| *link == self.link) else {
+            return;
+        };
+        let past_cut = rope.links.split_off(index + 1);
+        rope.links.truncate(index);
+
+        world
+            .entity_mut(self.link)
+            .remove::<(SpringJoint, SpringState)>();
+
+        let new_rope = (!past_cut.is_empty()).then(|| {
+            world
+                .spawn((Rope { links: past_cut }, Name::new("Rope")))
+                .id()
+        });
+        world.send_event(RopeCut {
+            rope: self.rope,
+            new_rope,
+            end: joint.containing,
+            new_end: self.link,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::Spring;
+
+    fn rope(app: &mut App, links: usize) -> (Entity, Vec<Entity>) {
+        let chain = chain(app, links, 1.0, Spring::snappy());
+        let rope = app.world_mut().spawn(Rope::new(chain[1..].to_vec())).id();
+        (rope, chain)
+    }
+
+    fn move_to(app: &mut App, entity: Entity, translation: Vec3) {
+        app.world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = translation;
+    }
+
+    #[test]
+    fn cut_moves_links_past_the_cut_to_a_new_rope() {
+        let mut app = app(plugin());
+        let (rope, chain) = rope(&mut app, 4);
+
+        CutRope {
+            rope,
+            link: chain[2],
+        }
+        .apply(app.world_mut());
+
+        assert_eq!(app.world().get::<Rope>(rope).unwrap().links, [chain[1]]);
+        assert!(app.world().get::<SpringJoint>(chain[2]).is_none());
+        let events = app.world().resource::<Events<RopeCut>>();
+        let cut: Vec<_> = events.iter_current_update_events().collect();
+        assert_eq!(cut.len(), 1);
+        assert_eq!((cut[0].end, cut[0].new_end), (chain[1], chain[2]));
+        let new_rope = cut[0].new_rope.unwrap();
+        assert_eq!(
+            app.world().get::<Rope>(new_rope).unwrap().links,
+            [chain[3], chain[4]]
+        );
+    }
+
+    #[test]
+    fn rope_turns_taut_and_slack() {
+        let mut app = app(plugin());
+        let (rope, chain) = rope(&mut app, 2);
+        let state = |app: &App| *app.world().get::<RopeState>(rope).unwrap();
+
+        step(&mut app, 1);
+        assert!(!state(&app).is_taut());
+
+        move_to(&mut app, chain[2], Vec3::X * 4.0);
+        step(&mut app, 1);
+        assert!(state(&app).is_taut());
+        assert!(state(&app).ratio() > 1.0);
+        let taut = app.world().resource::<Events<RopeTaut>>();
+        assert_eq!(taut.iter_current_update_events().count(), 1);
+
+        move_to(&mut app, chain[2], Vec3::X);
+        step(&mut app, 1);
+        assert!(!state(&app).is_taut());
+        let slack = app.world().resource::<Events<RopeSlack>>();
+        assert_eq!(slack.iter_current_update_events().count(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::dangling::SpringTargets;
+use crate::integration::{Impulse, PreviousTranslation, Velocity};
+use crate::joint::SpringJoint;
+use crate::plugin::{SpringSchedule, SpringSet};
+
+/// Watches the particles for NaN or infinite values produced by the solver,
+/// zeroing them and disabling the springs attached to the particle before
+/// they spread through the rest of the scene.
+///
+/// A [`SpringQuarantined`] event is sent for every particle that was fixed.
+#[derive(Default)]
+pub struct SpringSanitizerPlugin;
+
+impl Plugin for SpringSanitizerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SpringQuarantined>().add_systems(
+            SpringSchedule,
+            (
+                sanitize_impulses
+                    .after(SpringSet::Solve)
+                    .before(SpringSet::Integrate),
+                sanitize_particles.after(SpringSet::Integrate),
+            ),
+        );
+    }
+}
+
+/// Which value of a particle was not finite.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Reflect)]
+pub enum NonFinite {
+    Impulse,
+    Velocity,
+    Translation,
+    Rotation,
+}
+
+/// Sent when a particle had a NaN or infinite value, after it was reset and
+/// the `springs` attached to it were disabled.
+#[derive(Debug, Clone, Event)]
+pub struct SpringQuarantined {
+    pub particle: Entity,
+    pub value: NonFinite,
+    pub springs: Vec<Entity>,
+}
+
+/// Disable every enabled spring attached to `particle`.
+fn quarantine(
+    particle: Entity,
+    value: NonFinite,
+    targets: &SpringTargets,
+    joints: &mut Query<&mut SpringJoint>,
+) -> SpringQuarantined {
+    let mut springs = Vec::new();
+    let attached = std::iter::once(particle).chain(targets.springs_of(particle).iter().copied());
+    for spring in attached {
+        let Ok(mut joint) = joints.get_mut(spring) else {
+            continue;
+        };
+        if joint.enabled {
+            joint.enabled = false;
+            springs.push(spring);
+        }
+    }
+
+    SpringQuarantined {
+        particle,
+        value,
+        springs,
+    }
+}
+
+pub fn sanitize_impulses(
+    targets: Res<SpringTargets>,
+    mut particles: Query<(Entity, &mut Impulse)>,
+    mut joints: Query<&mut SpringJoint>,
+    mut quarantined: EventWriter<SpringQuarantined>,
+) {
+    for (entity, mut impulse) in &mut particles {
+        if impulse.linear.is_finite() && impulse.angular.is_finite() {
+            continue;
+        }
+
+        *impulse = Impulse::default();
+        quarantined.send(quarantine(
+            entity,
+            NonFinite::Impulse,
+            &targets,
+            &mut joints,
+        ));
+    }
+}
+
+pub fn sanitize_particles(
+    targets: Res<SpringTargets>,
+    mut particles: Query<(
+        Entity,
+        &mut Transform,
+        &mut Velocity,
+        Option<&mut PreviousTranslation>,
+    )>,
+    mut joints: Query<&mut SpringJoint>,
+    mut quarantined: EventWriter<SpringQuarantined>,
+) {
+    for (entity, mut transform, mut velocity, previous) in &mut particles {
+        let value = if !transform.translation.is_finite() {
+            NonFinite::Translation
+        } else if !transform.rotation.is_finite() {
+            NonFinite::Rotation
+        } else if !velocity.linear.is_finite() || !velocity.angular.is_finite() {
+            NonFinite::Velocity
+        } else {
+            continue;
+        };
+
+        // Put the particle back where it was before this step when possible.
+        let previous = previous.and_then(|mut previous| previous.0.take());
+        if !transform.translation.is_finite() {
+            transform.translation = previous
+                .filter(|previous| previous.is_finite())
+                .unwrap_or(Vec3::ZERO);
+        }
+        if !transform.rotation.is_finite() {
+            transform.rotation = Quat::IDENTITY;
+        }
+        *velocity = Velocity::default();
+
+        quarantined.send(quarantine(entity, value, &targets, &mut joints));
+    }
+}
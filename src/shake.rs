@@ -0,0 +1,143 @@
+use bevy::{prelude::*, reflect::Reflect, transform::TransformSystem};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Screen shake for cameras, kicking an internal spring with
+/// [`SpringShake::add_trauma`] and adding its decaying oscillation on top of
+/// the `Transform` of this entity.
+///
+/// The offset is removed again in `PreUpdate`, so systems moving the camera
+/// keep seeing its unshaken transform. For 2D cameras leave the Z axis of
+/// the trauma at zero and only rotate around Z.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringShake {
+    pub translation: Spring,
+    pub rotation: Spring,
+    /// Current offset of the translation from the unshaken transform.
+    pub offset: Vec3,
+    pub velocity: Vec3,
+    /// Current offset of the rotation from the unshaken transform, as a
+    /// scaled axis in radians.
+    pub angular_offset: Vec3,
+    pub angular_velocity: Vec3,
+    /// Offset added to the transform this frame, to be removed again.
+    applied: Option<(Vec3, Quat)>,
+}
+
+impl Default for SpringShake {
+    fn default() -> Self {
+        Self::new(Spring::new(0.2, 0.15))
+    }
+}
+
+impl SpringShake {
+    /// Use the same spring for translation and rotation, a strong and weakly
+    /// damped one gives a rattling shake, a weaker one a sway.
+    pub fn new(spring: Spring) -> Self {
+        Self {
+            translation: spring,
+            rotation: spring,
+            offset: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            angular_offset: Vec3::ZERO,
+            angular_velocity: Vec3::ZERO,
+            applied: None,
+        }
+    }
+
+    /// Kick the translation of the shake, e.g. by the direction and strength
+    /// of an explosion relative to the camera.
+    pub fn add_trauma(&mut self, impulse: Vec3) {
+        self.velocity += impulse;
+    }
+
+    /// Kick the rotation of the shake by an impulse around an axis, scaled
+    /// by its length in radians per second.
+    pub fn add_angular_trauma(&mut self, impulse: Vec3) {
+        self.angular_velocity += impulse;
+    }
+
+    /// Advance the oscillation by one timestep.
+    pub fn step(&mut self, timestep: f32) {
+        let instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: self.offset,
+            velocity: self.velocity,
+        };
+        self.velocity += self.translation.impulse(timestep, instant);
+        self.offset += self.velocity * timestep;
+
+        let angular_instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: self.angular_offset,
+            velocity: self.angular_velocity,
+        };
+        self.angular_velocity += self.rotation.impulse(timestep, angular_instant);
+        self.angular_offset += self.angular_velocity * timestep;
+    }
+
+    /// Whether the shake is within `threshold` of rest and barely moving.
+    pub fn is_settled(&self, threshold: f32) -> bool {
+        [
+            self.offset,
+            self.velocity,
+            self.angular_offset,
+            self.angular_velocity,
+        ]
+        .iter()
+        .all(|vector| vector.length() <= threshold)
+    }
+}
+
+pub struct SpringShakePlugin;
+
+impl Plugin for SpringShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringShake>()
+            .register_type::<SpringClock>()
+            .add_systems(PreUpdate, remove_spring_shake)
+            .add_systems(
+                PostUpdate,
+                spring_shake
+                    .run_if(springs_running)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Take the shake applied last frame back out of the transform.
+pub fn remove_spring_shake(mut shakes: Query<(&mut Transform, &mut SpringShake)>) {
+    for (mut transform, mut shake) in &mut shakes {
+        let Some((translation, rotation)) = shake.applied.take() else {
+            continue;
+        };
+
+        transform.translation -= translation;
+        transform.rotation *= rotation.inverse();
+    }
+}
+
+pub fn spring_shake(
+    clocks: SpringClocks,
+    mut shakes: Query<(&mut Transform, &mut SpringShake, Option<&SpringClock>)>,
+) {
+    for (mut transform, mut shake, clock) in &mut shakes {
+        let timestep = clocks.delta(clock);
+        if timestep > 0.0 && !shake.is_settled(1e-4) {
+            shake.step(timestep);
+        }
+
+        if let Some((translation, rotation)) = shake.applied.take() {
+            transform.translation -= translation;
+            transform.rotation *= rotation.inverse();
+        }
+
+        let rotation = Quat::from_scaled_axis(shake.angular_offset);
+        transform.translation += shake.offset;
+        transform.rotation *= rotation;
+        shake.applied = Some((shake.offset, rotation));
+    }
+}
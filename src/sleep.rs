@@ -0,0 +1,147 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::joint::SpringJoint;
+use crate::plugin::SpringConfig;
+use crate::timestep::SpringTimestep;
+
+/// Thresholds for putting springs to sleep.
+#[derive(Debug, Copy, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SleepSettings {
+    /// Maximum displacement (linear and angular) of a spring at rest.
+    pub displacement: f32,
+    /// Maximum relative velocity of a spring at rest, and maximum velocity of
+    /// either endpoint before a sleeping spring is woken up.
+    pub velocity: f32,
+    /// Number of consecutive steps a spring has to be at rest before sleeping.
+    pub steps: u32,
+}
+
+impl Default for SleepSettings {
+    fn default() -> Self {
+        Self {
+            displacement: 0.001,
+            velocity: 0.01,
+            steps: 60,
+        }
+    }
+}
+
+/// Sleep state of a spring, added to every [`SpringJoint`] when sleeping is
+/// enabled in the [`SpringConfig`].
+///
+/// Sleeping springs are skipped by the solver.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringSleep {
+    /// Consecutive steps the spring has been at rest.
+    pub steps_at_rest: u32,
+    pub asleep: bool,
+}
+
+impl SpringSleep {
+    pub fn wake(&mut self) {
+        self.steps_at_rest = 0;
+        self.asleep = false;
+    }
+}
+
+pub fn insert_spring_sleep(
+    mut commands: Commands,
+    config: Res<SpringConfig>,
+    springs: Query<Entity, (With<SpringJoint>, Without<SpringSleep>)>,
+) {
+    if config.sleep.is_none() {
+        return;
+    }
+
+    for entity in &springs {
+        commands.entity(entity).insert(SpringSleep::default());
+    }
+}
+
+pub fn update_spring_sleep(
+    config: Res<SpringConfig>,
+    timestep: Res<SpringTimestep>,
+    mut springs: Query<(Entity, &SpringJoint, &mut SpringSleep)>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+) {
+    let Some(settings) = config.sleep else {
+        return;
+    };
+
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (spring_entity, joint, mut sleep) in &mut springs {
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) = (
+            particles.get(spring_entity),
+            particles.get(joint.containing),
+        ) else {
+            sleep.wake();
+            continue;
+        };
+
+        let endpoints_moving = velocity_a.linear.length() > settings.velocity
+            || velocity_a.angular.length() > settings.velocity
+            || velocity_b.linear.length() > settings.velocity
+            || velocity_b.angular.length() > settings.velocity;
+
+        let (a, b) = joint.particles(
+            (transform_a, velocity_a, inertia_a),
+            &Impulse::default(),
+            (transform_b, velocity_b, inertia_b),
+            &Impulse::default(),
+        );
+        let instant = joint.linear_instant(&a, &b);
+        let angular_instant = a.angular.instant(&b.angular);
+
+        let at_rest = !endpoints_moving
+            && instant.displacement.length() <= settings.displacement
+            && angular_instant.displacement.length() <= settings.displacement
+            && instant.velocity.length() <= settings.velocity
+            && angular_instant.velocity.length() <= settings.velocity;
+
+        if at_rest {
+            sleep.steps_at_rest = sleep.steps_at_rest.saturating_add(1);
+            if sleep.steps_at_rest >= settings.steps {
+                sleep.asleep = true;
+            }
+        } else if sleep.asleep || sleep.steps_at_rest > 0 {
+            sleep.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::Spring;
+
+    fn asleep(app: &App, entity: Entity) -> bool {
+        app.world().get::<SpringSleep>(entity).unwrap().asleep
+    }
+
+    #[test]
+    fn sleeps_at_rest_and_wakes_when_moved() {
+        let settings = SleepSettings::default();
+        let mut app = app(plugin().with_sleep(settings));
+        let chain = chain(&mut app, 2, 1.0, Spring::snappy());
+
+        // The sleep state is inserted on the first step.
+        step(&mut app, settings.steps as usize + 1);
+        assert!(chain[1..].iter().all(|link| asleep(&app, *link)));
+
+        app.world_mut()
+            .get_mut::<Velocity>(chain[2])
+            .unwrap()
+            .linear = Vec3::Y;
+        step(&mut app, 1);
+        assert!(!asleep(&app, chain[2]));
+        assert_ne!(translation(&app, chain[2]).y, 0.0);
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::joint::{SpringJoint, SpringState};
+use crate::*;
+
+/// Socket that springs the closest [`SnapKey`] entering its `capture_radius`
+/// into place, for inventory sockets and docking ports.
+///
+/// The slot holds the [`SpringJoint`] to its occupant, so it needs to be a
+/// particle itself, usually with an infinite [`Inertia`](crate::integration::Inertia).
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SnapSlot {
+    /// Distance from the slot a key is captured at.
+    pub capture_radius: f32,
+    /// Distance from the slot the occupant is released at, when pulled out.
+    pub release_radius: f32,
+    pub spring: Spring,
+    /// Bitmask of the [`SnapKey`]s the slot accepts.
+    pub filter: u32,
+    /// Entity currently held by the slot.
+    pub occupant: Option<Entity>,
+}
+
+impl SnapSlot {
+    pub fn new(capture_radius: f32, spring: Spring) -> Self {
+        Self {
+            capture_radius,
+            release_radius: capture_radius * 2.0,
+            spring,
+            filter: u32::MAX,
+            occupant: None,
+        }
+    }
+
+    pub fn with_release_radius(mut self, release_radius: f32) -> Self {
+        self.release_radius = release_radius;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: u32) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn accepts(&self, key: SnapKey) -> bool {
+        self.filter & key.0 != 0
+    }
+}
+
+impl MapEntities for SnapSlot {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.occupant = self
+            .occupant
+            .map(|occupant| entity_mapper.map_entity(occupant));
+    }
+}
+
+/// Bitmask of the kinds of [`SnapSlot`]s this entity can be captured by.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SnapKey(pub u32);
+
+impl Default for SnapKey {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SnapAttached {
+    pub slot: Entity,
+    pub occupant: Entity,
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SnapDetached {
+    pub slot: Entity,
+    pub occupant: Entity,
+}
+
+pub struct SnapSlotPlugin;
+
+impl Plugin for SnapSlotPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SnapSlot>()
+            .register_type::<SnapKey>()
+            .add_event::<SnapAttached>()
+            .add_event::<SnapDetached>()
+            .add_systems(Update, snap_slots);
+    }
+}
+
+pub fn snap_slots(
+    mut commands: Commands,
+    mut slots: Query<(Entity, &GlobalTransform, &mut SnapSlot)>,
+    keys: Query<(Entity, &GlobalTransform, &SnapKey)>,
+    mut attached_events: EventWriter<SnapAttached>,
+    mut detached_events: EventWriter<SnapDetached>,
+) {
+    let mut occupied = slots
+        .iter()
+        .filter_map(|(_, _, slot)| slot.occupant)
+        .collect::<Vec<_>>();
+
+    for (entity, transform, mut slot) in &mut slots {
+        let origin = transform.translation();
+
+        if let Some(occupant) = slot.occupant {
+            let released = keys.get(occupant).map_or(true, |(_, key_transform, key)| {
+                !slot.accepts(*key)
+                    || key_transform.translation().distance(origin) > slot.release_radius
+            });
+            if !released {
+                continue;
+            }
+
+            commands
+                .entity(entity)
+                .remove::<(SpringJoint, SpringState)>();
+            slot.occupant = None;
+            occupied.retain(|other| *other != occupant);
+            detached_events.send(SnapDetached {
+                slot: entity,
+                occupant,
+            });
+        }
+
+        let closest = keys
+            .iter()
+            .filter(|(key_entity, _, key)| {
+                *key_entity != entity && slot.accepts(**key) && !occupied.contains(key_entity)
+            })
+            .map(|(key_entity, key_transform, _)| {
+                (key_entity, key_transform.translation().distance(origin))
+            })
+            .filter(|(_, distance)| *distance <= slot.capture_radius)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((occupant, _)) = closest {
+            commands.entity(entity).insert((
+                SpringJoint::linear(occupant),
+                slot.spring,
+                SpringState::default(),
+            ));
+            slot.occupant = Some(occupant);
+            occupied.push(occupant);
+            attached_events.send(SnapAttached {
+                slot: entity,
+                occupant,
+            });
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    transform::TransformSystem,
+};
+#[cfg(feature = "rapier3d")]
+use bevy_rapier3d::prelude::*;
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Keeps the entity (usually a camera) at an offset from a target entity
+/// using a translational spring.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct SpringArm {
+    pub target: Entity,
+    /// Desired offset from the target.
+    pub offset: Vec3,
+    /// Rotate the offset by the rotation of the target.
+    pub follow_rotation: bool,
+    /// Rotate the entity to look at the target every frame.
+    pub look_at_target: bool,
+    /// Distance kept from obstacles when the arm is shortened by a raycast.
+    #[cfg(feature = "rapier3d")]
+    pub collision_margin: f32,
+    pub spring: Spring,
+    /// Current velocity of the end of the arm.
+    pub velocity: Vec3,
+}
+
+impl MapEntities for SpringArm {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.map_entity(self.target);
+    }
+}
+
+impl SpringArm {
+    pub fn new(target: Entity, offset: Vec3) -> Self {
+        Self {
+            target,
+            offset,
+            follow_rotation: false,
+            look_at_target: true,
+            #[cfg(feature = "rapier3d")]
+            collision_margin: 0.2,
+            spring: Spring::critically_damped(0.1),
+            velocity: Vec3::ZERO,
+        }
+    }
+
+    pub fn with_spring(mut self, spring: Spring) -> Self {
+        self.spring = spring;
+        self
+    }
+
+    /// Desired position of the end of the arm for a given target transform.
+    pub fn desired_translation(&self, target: &GlobalTransform) -> Vec3 {
+        let offset = if self.follow_rotation {
+            target.compute_transform().rotation * self.offset
+        } else {
+            self.offset
+        };
+
+        target.translation() + offset
+    }
+}
+
+pub struct SpringArmPlugin;
+
+impl Plugin for SpringArmPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringArm>()
+            .register_type::<SpringClock>()
+            .add_systems(
+                PostUpdate,
+                spring_arm
+                    .run_if(springs_running)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn spring_arm(
+    clocks: SpringClocks,
+    mut arms: Query<(&mut Transform, &mut SpringArm, Option<&SpringClock>)>,
+    targets: Query<&GlobalTransform>,
+    #[cfg(feature = "rapier3d")] rapier_context: Option<Res<RapierContext>>,
+) {
+    for (mut transform, mut arm, clock) in &mut arms {
+        let timestep = clocks.delta(clock);
+        if timestep == 0.0 {
+            continue;
+        }
+
+        let Ok(target) = targets.get(arm.target) else {
+            continue;
+        };
+
+        let desired = arm.desired_translation(target);
+        let end = TranslationParticle3 {
+            mass: 1.0,
+            translation: transform.translation,
+            velocity: arm.velocity,
+        };
+        let anchor = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: desired,
+            velocity: Vec3::ZERO,
+        };
+
+        let impulse = arm.spring.impulse(timestep, end.instant(&anchor));
+        arm.velocity += impulse;
+        transform.translation += arm.velocity * timestep;
+
+        // Pull the arm in front of anything between the target and the end of the arm.
+        #[cfg(feature = "rapier3d")]
+        if let Some(rapier_context) = &rapier_context {
+            let origin = target.translation();
+            let arm_vector = transform.translation - origin;
+            let length = arm_vector.length();
+            let direction = arm_vector.normalize_or_zero();
+            let filter = QueryFilter::default().exclude_rigid_body(arm.target);
+            if let Some((_, toi)) = rapier_context.cast_ray(origin, direction, length, true, filter)
+            {
+                let shortened = (toi - arm.collision_margin).max(0.0);
+                transform.translation = origin + direction * shortened;
+                arm.velocity = arm.velocity.reject_from_normalized(direction);
+            }
+        }
+
+        if arm.look_at_target {
+            transform.look_at(target.translation(), Vec3::Y);
+        }
+    }
+}
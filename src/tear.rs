@@ -0,0 +1,183 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::joint::{SpringJoint, SpringState};
+use crate::multi::Springs;
+use crate::rope::{CutRope, Rope};
+
+/// Tears the [`SpringJoint`] on this entity once it is stretched too far,
+/// so capes, nets and ropes can rip under load.
+///
+/// Strain is the stretch past the rest length relative to the rest length,
+/// so springs with a rest length of zero never tear. Torn joints are
+/// removed from the entity and a [`SpringTorn`] event is sent, links of a
+/// [`Rope`] are cut with [`CutRope`] so the rope is split too.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Tearable {
+    /// Strain above which the spring tears, e.g. 0.5 for half again its rest length.
+    pub max_strain: f32,
+    /// Strain of the spring after the last step.
+    pub strain: f32,
+    /// Highest strain the spring has been under, for e.g. fraying visuals.
+    pub peak_strain: f32,
+}
+
+impl Tearable {
+    pub fn new(max_strain: f32) -> Self {
+        Self {
+            max_strain,
+            strain: 0.0,
+            peak_strain: 0.0,
+        }
+    }
+
+    /// Record the strain of a spring, returning whether it tears.
+    pub fn update(&mut self, length: f32, rest_length: f32) -> bool {
+        self.strain = strain(length, rest_length);
+        self.peak_strain = self.peak_strain.max(self.strain);
+        self.strain > self.max_strain
+    }
+}
+
+/// Stretch past `rest_length` relative to it, zero for a rest length of zero.
+pub fn strain(length: f32, rest_length: f32) -> f32 {
+    if rest_length <= 0.0 {
+        return 0.0;
+    }
+
+    (length - rest_length) / rest_length
+}
+
+/// Sent when a spring from `entity` to `containing` tore.
+#[derive(Debug, Copy, Clone, Event)]
+pub struct SpringTorn {
+    pub entity: Entity,
+    pub containing: Entity,
+    pub strain: f32,
+}
+
+pub fn tear_springs(
+    mut commands: Commands,
+    mut springs: Query<(Entity, &SpringJoint, &SpringState, &mut Tearable)>,
+    ropes: Query<(Entity, &Rope)>,
+    mut torn_events: EventWriter<SpringTorn>,
+) {
+    for (entity, joint, state, mut tearable) in &mut springs {
+        if !joint.enabled || !tearable.update(state.length, joint.rest_length) {
+            continue;
+        }
+
+        let rope = ropes
+            .iter()
+            .find_map(|(rope, links)| links.links.contains(&entity).then_some(rope));
+        if let Some(rope) = rope {
+            commands.add(CutRope { rope, link: entity });
+            commands.entity(entity).remove::<Tearable>();
+        } else {
+            commands
+                .entity(entity)
+                .remove::<(SpringJoint, SpringState, Tearable)>();
+        }
+        torn_events.send(SpringTorn {
+            entity,
+            containing: joint.containing,
+            strain: tearable.strain,
+        });
+    }
+}
+
+/// Remove the springs of [`Springs`] hubs stretched past their `max_strain`.
+pub fn tear_multi_springs(
+    mut hubs: Query<(Entity, &mut Springs)>,
+    transforms: Query<&Transform>,
+    mut torn_events: EventWriter<SpringTorn>,
+) {
+    for (entity, mut springs) in &mut hubs {
+        let Ok(hub) = transforms.get(entity) else {
+            continue;
+        };
+
+        let mut torn = Vec::new();
+        for spring_to in springs.0.iter().filter(|spring_to| spring_to.enabled) {
+            let (Some(max_strain), Ok(target)) =
+                (spring_to.max_strain, transforms.get(spring_to.target))
+            else {
+                continue;
+            };
+
+            let length = hub.translation.distance(target.translation);
+            let strain = strain(length, spring_to.rest_length);
+            if strain > max_strain {
+                torn.push((spring_to.target, strain));
+            }
+        }
+
+        for (target, strain) in torn {
+            springs.remove(target);
+            torn_events.send(SpringTorn {
+                entity,
+                containing: target,
+                strain,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rope::RopeCut;
+    use crate::testing::*;
+    use crate::Spring;
+
+    fn stretch(app: &mut App, entity: Entity, translation: Vec3) {
+        app.world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = translation;
+    }
+
+    #[test]
+    fn overstretched_spring_tears() {
+        let mut app = app(plugin());
+        let chain = chain(&mut app, 1, 1.0, Spring::snappy());
+        app.world_mut()
+            .entity_mut(chain[1])
+            .insert(Tearable::new(0.5));
+
+        step(&mut app, 1);
+        assert!(app.world().get::<SpringJoint>(chain[1]).is_some());
+
+        stretch(&mut app, chain[1], Vec3::X * 3.0);
+        step(&mut app, 1);
+        assert!(app.world().get::<SpringJoint>(chain[1]).is_none());
+        let torn = app.world().resource::<Events<SpringTorn>>();
+        let torn: Vec<_> = torn.iter_current_update_events().collect();
+        assert_eq!(torn.len(), 1);
+        assert_eq!(torn[0].containing, chain[0]);
+    }
+
+    #[test]
+    fn torn_rope_link_splits_the_rope() {
+        let mut app = app(plugin());
+        let chain = chain(&mut app, 3, 1.0, Spring::snappy());
+        let rope = app.world_mut().spawn(Rope::new(chain[1..].to_vec())).id();
+        app.world_mut()
+            .entity_mut(chain[2])
+            .insert(Tearable::new(0.5));
+
+        stretch(&mut app, chain[2], Vec3::new(2.0, 3.0, 0.0));
+        step(&mut app, 1);
+
+        assert!(app.world().get::<SpringJoint>(chain[2]).is_none());
+        assert_eq!(app.world().get::<Rope>(rope).unwrap().links, [chain[1]]);
+        let cut = app.world().resource::<Events<RopeCut>>();
+        let cut: Vec<_> = cut.iter_current_update_events().collect();
+        assert_eq!(cut.len(), 1);
+        let new_rope = cut[0]
+            .new_rope
+            .expect("links past the tear make a new rope");
+        assert_eq!(app.world().get::<Rope>(new_rope).unwrap().links, [chain[3]]);
+    }
+}
@@ -0,0 +1,87 @@
+//! Headless app stepping the springs at a fixed timestep, shared by the tests
+//! of the solver modules.
+use std::time::Duration;
+
+use bevy::{core::TaskPoolPlugin, prelude::*};
+
+use crate::force::GlobalGravity;
+use crate::integration::{Inertia, ParticleBundle, Velocity};
+use crate::joint::SpringJoint;
+use crate::plugin::SpringPlugin;
+use crate::timestep::SpringTimestep;
+use crate::Spring;
+
+pub const TIMESTEP: f32 = 1.0 / 60.0;
+
+/// App running the given [`SpringPlugin`] in `Update`, without gravity.
+pub fn app(plugin: SpringPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins((TaskPoolPlugin::default(), plugin))
+        .init_resource::<Time>()
+        .insert_resource(SpringTimestep::from_seconds(TIMESTEP))
+        .insert_resource(GlobalGravity(Vec3::ZERO));
+    app
+}
+
+pub fn plugin() -> SpringPlugin {
+    SpringPlugin::new(Update)
+}
+
+/// Run `steps` updates, each advancing `Time` by [`TIMESTEP`].
+pub fn step(app: &mut App, steps: usize) {
+    for _ in 0..steps {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(TIMESTEP));
+        app.update();
+    }
+}
+
+pub fn particle(app: &mut App, translation: Vec3, mass: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            TransformBundle::from_transform(Transform::from_translation(translation)),
+            ParticleBundle {
+                inertia: Inertia {
+                    linear: mass,
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id()
+}
+
+/// Particle at `translation` with a spring to `containing`.
+pub fn spring(
+    app: &mut App,
+    translation: Vec3,
+    mass: f32,
+    joint: SpringJoint,
+    spring: Spring,
+) -> Entity {
+    let entity = particle(app, translation, mass);
+    app.world_mut().entity_mut(entity).insert((joint, spring));
+    entity
+}
+
+/// Particles spaced `length` apart along X, each with a linear spring of
+/// that rest length to the previous one, the first one anchored.
+pub fn chain(app: &mut App, links: usize, length: f32, spring: Spring) -> Vec<Entity> {
+    let mut particles = vec![particle(app, Vec3::ZERO, f32::INFINITY)];
+    for index in 1..=links {
+        let previous = particles[index - 1];
+        let joint = SpringJoint::linear(previous).with_rest_length(length);
+        let translation = Vec3::X * length * index as f32;
+        particles.push(self::spring(app, translation, 1.0, joint, spring));
+    }
+    particles
+}
+
+pub fn translation(app: &App, entity: Entity) -> Vec3 {
+    app.world().get::<Transform>(entity).unwrap().translation
+}
+
+pub fn velocity(app: &App, entity: Entity) -> Velocity {
+    *app.world().get::<Velocity>(entity).unwrap()
+}
@@ -0,0 +1,162 @@
+use bevy::{ecs::system::SystemParam, prelude::*, reflect::Reflect};
+
+use crate::kinematic::Kinematic;
+use crate::plugin::SpringConfig;
+
+/// Timestep used by the spring systems, for computing the impulses and
+/// integrating them alike.
+///
+/// By default this is read from `Time` every run, which is `Time<Fixed>` when the
+/// systems run in `FixedUpdate`. Pausing `Time<Virtual>` or [`SpringsPaused`]
+/// stops the springs, `time_scale` slows them down or speeds them up.
+#[derive(Debug, Copy, Clone, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct SpringTimestep {
+    /// Explicit timestep in seconds to use instead of the one read from `Time`.
+    pub explicit: Option<f32>,
+    /// Multiplier on the timestep, for slow motion of the springs only.
+    pub time_scale: f32,
+    delta: f32,
+    substeps: u32,
+}
+
+impl Default for SpringTimestep {
+    fn default() -> Self {
+        Self {
+            explicit: None,
+            time_scale: 1.0,
+            delta: 0.0,
+            substeps: 1,
+        }
+    }
+}
+
+impl SpringTimestep {
+    pub fn from_seconds(seconds: f32) -> Self {
+        Self {
+            explicit: Some(seconds),
+            delta: seconds,
+            ..default()
+        }
+    }
+
+    /// Timestep of the current substep in seconds.
+    pub fn delta(&self) -> f32 {
+        self.delta / self.substeps as f32
+    }
+
+    /// Timestep of the whole step in seconds, before being divided into substeps.
+    pub fn step_delta(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn substeps(&self) -> u32 {
+        self.substeps
+    }
+
+    pub fn inverse(&self) -> f32 {
+        self.delta().inverse()
+    }
+
+    /// Whether the springs should be stepped at all this run.
+    pub fn is_running(&self) -> bool {
+        self.delta > 0.0
+    }
+
+    pub fn set_delta(&mut self, delta: f32) {
+        self.delta = delta;
+    }
+}
+
+/// Where the timestep of a spring on this entity comes from, for springs
+/// that should step differently from the schedule their system runs in, e.g.
+/// a camera spring ticking with `Time<Virtual>` next to fixed physics.
+///
+/// Read by the per-entity springs: [`SpringFollow`](crate::follow::SpringFollow),
+/// [`TransformSpring`](crate::transform_spring::TransformSpring),
+/// [`SpringValue`](crate::value::SpringValue), [`SpringShake`](crate::shake::SpringShake),
+/// [`RecoilSpring`](crate::recoil::RecoilSpring), `UiSpring`, `SpringArm` and `JiggleBone`.
+/// Joints are integrated together, so they share the [`SpringTimestep`] instead.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub enum SpringClock {
+    /// `Time` of the schedule the spring system runs in.
+    #[default]
+    Schedule,
+    /// Timestep of `Time<Fixed>`, once per run of the system.
+    Fixed,
+    /// Elapsed `Time<Virtual>`, scaled and paused with the rest of the app.
+    Virtual,
+    /// Explicit timestep in seconds, once per run of the system.
+    Explicit(f32),
+}
+
+/// Clocks a [`SpringClock`] can read from.
+#[derive(SystemParam)]
+pub struct SpringClocks<'w> {
+    time: Res<'w, Time>,
+    fixed: Option<Res<'w, Time<Fixed>>>,
+    virtual_time: Option<Res<'w, Time<Virtual>>>,
+}
+
+impl SpringClocks<'_> {
+    /// Timestep in seconds of a spring with the given clock, the `Time` of
+    /// the schedule without one.
+    ///
+    /// Zero while no time passed in the schedule, so every clock stops along
+    /// with the spring systems.
+    pub fn delta(&self, clock: Option<&SpringClock>) -> f32 {
+        if self.time.delta_seconds() == 0.0 {
+            return 0.0;
+        }
+
+        match clock.copied().unwrap_or_default() {
+            SpringClock::Schedule => self.time.delta_seconds(),
+            SpringClock::Fixed => self
+                .fixed
+                .as_ref()
+                .map_or(0.0, |fixed| fixed.timestep().as_secs_f32()),
+            SpringClock::Virtual => self
+                .virtual_time
+                .as_ref()
+                .map_or(0.0, |virtual_time| virtual_time.delta_seconds()),
+            SpringClock::Explicit(seconds) => seconds.max(0.0),
+        }
+    }
+}
+
+/// Stops every spring system of the crate while `true`, without pausing the
+/// rest of the app.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Resource, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Resource)]
+pub struct SpringsPaused(pub bool);
+
+/// Run condition of the spring systems, false while [`SpringsPaused`] or
+/// `Time<Virtual>` is paused.
+pub fn springs_running(
+    paused: Option<Res<SpringsPaused>>,
+    virtual_time: Option<Res<Time<Virtual>>>,
+) -> bool {
+    !paused.is_some_and(|paused| paused.0) && !virtual_time.is_some_and(|time| time.is_paused())
+}
+
+pub fn update_timestep(
+    time: Res<Time>,
+    config: Res<SpringConfig>,
+    paused: Option<Res<SpringsPaused>>,
+    virtual_time: Option<Res<Time<Virtual>>>,
+    mut timestep: ResMut<SpringTimestep>,
+) {
+    // Keep the springs still when no time has passed, even with an explicit timestep.
+    let delta = if time.delta_seconds() == 0.0 || !springs_running(paused, virtual_time) {
+        0.0
+    } else {
+        timestep.explicit.unwrap_or(time.delta_seconds()) * timestep.time_scale.max(0.0)
+    };
+
+    timestep.set_delta(delta);
+    timestep.substeps = config.substeps.max(1);
+}
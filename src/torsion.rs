@@ -0,0 +1,210 @@
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+};
+
+use crate::dangling::SpringLink;
+use crate::group::{SpringGroup, SpringGroups};
+use crate::integration::{Impulse, Inertia, Velocity};
+use crate::timestep::SpringTimestep;
+use crate::*;
+
+/// Angular-only spring between this entity and the `containing` entity, for
+/// doors, levers and turrets swinging back to a neutral pose.
+///
+/// Drives the full orientation of this entity towards the orientation of the
+/// `containing` entity offset by `rest_rotation`.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component, MapEntities)]
+pub struct TorsionSpring {
+    pub containing: Entity,
+    /// Disabled springs are skipped while keeping their settings.
+    pub enabled: bool,
+    /// Rotation relative to the `containing` entity the spring rests at.
+    pub rest_rotation: Quat,
+    pub spring: Spring,
+}
+
+impl MapEntities for TorsionSpring {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.containing = entity_mapper.map_entity(self.containing);
+    }
+}
+
+impl SpringLink for TorsionSpring {
+    fn containing(&self) -> Entity {
+        self.containing
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl TorsionSpring {
+    pub fn new(containing: Entity, spring: Spring) -> Self {
+        Self {
+            containing,
+            enabled: true,
+            rest_rotation: Quat::IDENTITY,
+            spring,
+        }
+    }
+
+    pub fn with_rest_rotation(mut self, rest_rotation: Quat) -> Self {
+        self.rest_rotation = rest_rotation;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Rest angle in radians around the Z axis, for 2D.
+    pub fn with_rest_angle(self, rest_angle: f32) -> Self {
+        self.with_rest_rotation(Quat::from_rotation_z(rest_angle))
+    }
+
+    /// Angular impulse applied to this entity, the `containing` entity receives the opposite.
+    pub fn impulse(
+        &self,
+        timestep: f32,
+        rotation: Quat,
+        containing_rotation: Quat,
+        relative_velocity: Vec3,
+        reduced_inertia: Vec3,
+    ) -> Vec3 {
+        let mut error = rotation * (containing_rotation * self.rest_rotation).inverse();
+        if error.w < 0.0 {
+            error = -error;
+        }
+
+        let instant = SpringInstant {
+            reduced_inertia,
+            displacement: error.to_scaled_axis(),
+            velocity: relative_velocity,
+        };
+        self.spring.impulse(timestep, instant)
+    }
+
+    /// Angular impulse around the Z axis from the multi-turn angle tracked by
+    /// a [`WindUp`], instead of the shortest arc to the rest rotation.
+    pub fn wound_impulse(
+        &self,
+        timestep: f32,
+        wind_up: &WindUp,
+        relative_velocity: Vec3,
+        reduced_inertia: Vec3,
+    ) -> Vec3 {
+        let instant = SpringInstant {
+            reduced_inertia,
+            displacement: Vec3::Z * wind_up.angle,
+            velocity: relative_velocity * Vec3::Z,
+        };
+        self.spring.impulse(timestep, instant)
+    }
+}
+
+/// Tracks the accumulated rotation of a 2D [`TorsionSpring`] around the Z
+/// axis, so it can be wound several turns and springs back through all of them.
+///
+/// For cranks, dials and music box mechanics.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct WindUp {
+    /// Accumulated angle away from the rest rotation in radians, positive counter-clockwise.
+    pub angle: f32,
+    previous: Option<f32>,
+}
+
+impl WindUp {
+    /// Start already wound by `angle` radians.
+    pub fn wound(angle: f32) -> Self {
+        Self {
+            angle,
+            previous: None,
+        }
+    }
+
+    /// Number of full turns away from the rest rotation.
+    pub fn turns(&self) -> f32 {
+        self.angle / std::f32::consts::TAU
+    }
+
+    /// Accumulate the change from the previously tracked angle, taking the
+    /// shortest way around between calls.
+    pub fn track(&mut self, angle: f32) -> f32 {
+        match self.previous {
+            Some(previous) => self.angle += wrap_angle(angle - previous),
+            None => self.angle += wrap_angle(angle - wrap_angle(self.angle)),
+        }
+        self.previous = Some(angle);
+        self.angle
+    }
+}
+
+pub fn torsion_spring(
+    timestep: Res<SpringTimestep>,
+    groups: Res<SpringGroups>,
+    mut springs: Query<(
+        Entity,
+        &TorsionSpring,
+        Option<&mut WindUp>,
+        Option<&SpringGroup>,
+    )>,
+    particles: Query<(&Transform, &Velocity, &Inertia)>,
+    mut impulses: Query<&mut Impulse>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+    let timestep = timestep.delta();
+
+    for (entity, torsion, wind_up, group) in &mut springs {
+        if !torsion.enabled || !groups.is_active(group) || torsion.containing == entity {
+            continue;
+        }
+
+        let (Ok((transform_a, velocity_a, inertia_a)), Ok((transform_b, velocity_b, inertia_b))) =
+            (particles.get(entity), particles.get(torsion.containing))
+        else {
+            continue;
+        };
+        let Ok([mut impulse_a, mut impulse_b]) =
+            impulses.get_many_mut([entity, torsion.containing])
+        else {
+            continue;
+        };
+
+        // Include the impulses of the springs solved before this one.
+        let velocity_a = velocity_a.with_impulse(&impulse_a, inertia_a);
+        let velocity_b = velocity_b.with_impulse(&impulse_b, inertia_b);
+
+        let relative_velocity = velocity_a.angular - velocity_b.angular;
+        let reduced_inertia = (inertia_a.angular.inverse() + inertia_b.angular.inverse()).inverse();
+        let impulse = match wind_up {
+            Some(mut wind_up) => {
+                let relative =
+                    transform_a.rotation * (transform_b.rotation * torsion.rest_rotation).inverse();
+                wind_up.track(2.0 * relative.z.atan2(relative.w));
+                torsion.wound_impulse(timestep, &wind_up, relative_velocity, reduced_inertia)
+            }
+            None => torsion.impulse(
+                timestep,
+                transform_a.rotation,
+                transform_b.rotation,
+                relative_velocity,
+                reduced_inertia,
+            ),
+        };
+        impulse_a.angular += impulse;
+        impulse_b.angular -= impulse;
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::{prelude::*, reflect::Reflect, transform::TransformSystem};
+
+use crate::integration::integrate_rotation;
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// Target the [`TransformSpring`] on the same entity springs the `Transform` towards.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct TransformTarget(pub Transform);
+
+/// Springs the translation, rotation and scale of the `Transform` towards the
+/// [`TransformTarget`], without needing any physics components.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct TransformSpring {
+    pub translation: Spring,
+    pub rotation: Spring,
+    pub scale: Spring,
+    pub velocity: Vec3,
+    pub angular_velocity: Vec3,
+    pub scale_velocity: Vec3,
+}
+
+impl TransformSpring {
+    /// Use the same spring for translation, rotation and scale.
+    pub fn new(spring: Spring) -> Self {
+        Self {
+            translation: spring,
+            rotation: spring,
+            scale: spring,
+            ..default()
+        }
+    }
+
+    /// Advance `transform` towards `target` by one timestep.
+    pub fn step(&mut self, transform: &mut Transform, target: &Transform, timestep: f32) {
+        let current = TranslationParticle3 {
+            mass: 1.0,
+            translation: transform.translation,
+            velocity: self.velocity,
+        };
+        let goal = TranslationParticle3 {
+            mass: f32::INFINITY,
+            translation: target.translation,
+            velocity: Vec3::ZERO,
+        };
+        self.velocity += self.translation.impulse(timestep, current.instant(&goal));
+        transform.translation += self.velocity * timestep;
+
+        // Take the short way around to the target rotation.
+        let mut difference = transform.rotation * target.rotation.inverse();
+        if difference.w < 0.0 {
+            difference = -difference;
+        }
+        let angular_instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: difference.to_scaled_axis(),
+            velocity: self.angular_velocity,
+        };
+        self.angular_velocity += self.rotation.impulse(timestep, angular_instant);
+        integrate_rotation(&mut transform.rotation, self.angular_velocity, timestep);
+
+        let scale_instant = SpringInstant {
+            reduced_inertia: Vec3::ONE,
+            displacement: transform.scale - target.scale,
+            velocity: self.scale_velocity,
+        };
+        self.scale_velocity += self.scale.impulse(timestep, scale_instant);
+        transform.scale += self.scale_velocity * timestep;
+    }
+}
+
+pub struct TransformSpringPlugin;
+
+impl Plugin for TransformSpringPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformTarget>()
+            .register_type::<TransformSpring>()
+            .register_type::<SpringClock>()
+            .add_systems(
+                PostUpdate,
+                transform_spring
+                    .run_if(springs_running)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub fn transform_spring(
+    clocks: SpringClocks,
+    mut springs: Query<(
+        &mut Transform,
+        &mut TransformSpring,
+        &TransformTarget,
+        Option<&SpringClock>,
+    )>,
+) {
+    for (mut transform, mut spring, target, clock) in &mut springs {
+        let timestep = clocks.delta(clock);
+        if timestep == 0.0 {
+            continue;
+        }
+
+        spring.step(&mut transform, &target.0, timestep);
+    }
+}
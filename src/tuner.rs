@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+use bevy_inspector_egui::bevy_egui::{EguiContexts, EguiPlugin};
+use bevy_inspector_egui::egui;
+
+use crate::history::SpringHistory;
+use crate::joint::{SpringJoint, SpringPair};
+use crate::*;
+
+/// Window listing every spring, with sliders to tune the selected one live,
+/// a plot of its displacement and a button copying its settings as code.
+pub struct SpringTunerPlugin;
+
+impl Plugin for SpringTunerPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.init_resource::<SpringTuner>()
+            .add_systems(Update, spring_tuner_window);
+    }
+}
+
+/// State of the window of the [`SpringTunerPlugin`].
+#[derive(Debug, Clone, Resource)]
+pub struct SpringTuner {
+    pub open: bool,
+    pub selected: Option<Entity>,
+}
+
+impl Default for SpringTuner {
+    fn default() -> Self {
+        Self {
+            open: true,
+            selected: None,
+        }
+    }
+}
+
+pub fn spring_tuner_window(
+    mut commands: Commands,
+    mut contexts: EguiContexts,
+    mut tuner: ResMut<SpringTuner>,
+    mut springs: Query<(
+        Entity,
+        Option<&Name>,
+        &mut SpringJoint,
+        AnyOf<(&mut Spring, &mut SpringPair)>,
+        Option<&SpringHistory>,
+    )>,
+) {
+    let mut open = tuner.open;
+    egui::Window::new("Springs")
+        .open(&mut open)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for (entity, name, ..) in &springs {
+                        let label = name.map_or_else(|| format!("{entity:?}"), Name::to_string);
+                        let selected = tuner.selected == Some(entity);
+                        if ui.selectable_label(selected, label).clicked() {
+                            tuner.selected = Some(entity);
+                        }
+                    }
+                });
+            ui.separator();
+
+            let Some(selected) = tuner.selected else {
+                ui.label("Select a spring to tune it.");
+                return;
+            };
+            let Ok((entity, _, mut joint, (spring, pair), history)) = springs.get_mut(selected)
+            else {
+                tuner.selected = None;
+                return;
+            };
+
+            let mut rest_length = joint.rest_length;
+            let rest_changed = ui
+                .horizontal(|ui| {
+                    ui.label("rest distance");
+                    ui.add(egui::DragValue::new(&mut rest_length).speed(0.01))
+                        .changed()
+                })
+                .inner;
+            if rest_changed {
+                joint.rest_length = rest_length.max(0.0);
+            }
+
+            let code = match (spring, pair) {
+                (_, Some(mut pair)) => {
+                    let mut edited = *pair;
+                    ui.label("linear");
+                    let linear = spring_sliders(ui, &mut edited.linear);
+                    ui.label("angular");
+                    let angular = spring_sliders(ui, &mut edited.angular);
+                    if linear || angular {
+                        *pair = edited;
+                    }
+                    format!(
+                        "SpringPair::new({}, {})",
+                        spring_code(&edited.linear),
+                        spring_code(&edited.angular)
+                    )
+                }
+                (Some(mut spring), None) => {
+                    let mut edited = *spring;
+                    if spring_sliders(ui, &mut edited) {
+                        *spring = edited;
+                    }
+                    spring_code(&edited)
+                }
+                (None, None) => return,
+            };
+
+            ui.separator();
+            match history {
+                Some(history) => plot_displacement(ui, history),
+                None => {
+                    if ui.button("Record displacement").clicked() {
+                        commands.entity(entity).insert(SpringHistory::default());
+                    }
+                }
+            }
+
+            ui.separator();
+            let code = format!(
+                "{code}\nSpringJoint::new(containing).with_rest_length({:?})",
+                joint.rest_length
+            );
+            if ui.button("Copy as code").clicked() {
+                ui.output_mut(|output| output.copied_text = code);
+            }
+        });
+    tuner.open = open;
+}
+
+/// Sliders for the settings of a spring, returning whether any changed.
+fn spring_sliders(ui: &mut egui::Ui, spring: &mut Spring) -> bool {
+    let strength = ui.add(egui::Slider::new(&mut spring.strength, 0.0..=1.0).text("strength"));
+    let damp_ratio =
+        ui.add(egui::Slider::new(&mut spring.damp_ratio, 0.0..=4.0).text("damp ratio"));
+    strength.changed() || damp_ratio.changed()
+}
+
+fn spring_code(spring: &Spring) -> String {
+    let code = format!(
+        "Spring::new({:?}, {:?})",
+        spring.strength, spring.damp_ratio
+    );
+    match spring.damp_mode {
+        DampMode::Full => code,
+        DampMode::Axial => format!("{code}.with_damp_mode(DampMode::Axial)"),
+    }
+}
+
+/// Line plot of the displacement recorded by a [`SpringHistory`], scaled to
+/// the largest displacement.
+fn plot_displacement(ui: &mut egui::Ui, history: &SpringHistory) {
+    let size = egui::vec2(ui.available_width(), 80.0);
+    let (response, painter) = ui.allocate_painter(size, egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+    let largest = history
+        .samples
+        .iter()
+        .map(|sample| sample.displacement.abs())
+        .fold(f32::EPSILON, f32::max);
+    let last = history.samples.len().max(2) - 1;
+    let points = history
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            egui::pos2(
+                rect.left() + rect.width() * index as f32 / last as f32,
+                rect.center().y - sample.displacement / largest * rect.height() * 0.5,
+            )
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, ui.visuals().text_color()),
+    ));
+    ui.label(format!("displacement, up to {largest:.3}"));
+}
@@ -0,0 +1,72 @@
+use bevy::{prelude::*, reflect::Reflect, ui::UiSystem};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::value::SpringValue;
+use crate::*;
+
+/// Springs the `left`/`top` offsets of a UI node's `Style` towards target
+/// offsets in logical pixels, for sliding menus and popups into place.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct UiSpring {
+    pub left: SpringValue<f32>,
+    pub top: SpringValue<f32>,
+}
+
+impl UiSpring {
+    /// Node at rest at the given offset.
+    pub fn new(left: f32, top: f32, spring: Spring) -> Self {
+        Self {
+            left: SpringValue::new(left, spring),
+            top: SpringValue::new(top, spring),
+        }
+    }
+
+    pub fn set_target(&mut self, left: f32, top: f32) {
+        self.left.set_target(left);
+        self.top.set_target(top);
+    }
+
+    pub fn snap(&mut self, left: f32, top: f32) {
+        self.left.snap(left);
+        self.top.snap(top);
+    }
+
+    pub fn is_settled(&self, threshold: f32) -> bool {
+        self.left.is_settled(threshold) && self.top.is_settled(threshold)
+    }
+}
+
+pub struct UiSpringPlugin;
+
+impl Plugin for UiSpringPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<UiSpring>()
+            .register_type::<SpringClock>()
+            .add_systems(
+                PostUpdate,
+                ui_spring.run_if(springs_running).before(UiSystem::Layout),
+            );
+    }
+}
+
+pub fn ui_spring(
+    clocks: SpringClocks,
+    mut nodes: Query<(&mut Style, &mut UiSpring, Option<&SpringClock>)>,
+) {
+    for (mut style, mut spring, clock) in &mut nodes {
+        let timestep = clocks.delta(clock);
+        if timestep > 0.0 && !spring.is_settled(0.01) {
+            spring.left.tick(timestep);
+            spring.top.tick(timestep);
+        }
+
+        // Settled springs still have to reach the node, e.g. after a snap.
+        let (left, top) = (Val::Px(spring.left.value), Val::Px(spring.top.value));
+        if style.left != left || style.top != top {
+            style.left = left;
+            style.top = top;
+        }
+    }
+}
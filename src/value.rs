@@ -0,0 +1,97 @@
+use std::marker::PhantomData;
+
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::timestep::{springs_running, SpringClock, SpringClocks};
+use crate::*;
+
+/// A value springing towards a target, for tweening arbitrary scalars and
+/// vectors (FOV, volume, opacity, zoom) with the same damping as the springs.
+#[derive(Default, Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct SpringValue<T: Kinematic> {
+    pub target: T,
+    pub value: T,
+    pub velocity: T,
+    pub spring: Spring,
+}
+
+impl<T: Kinematic> SpringValue<T> {
+    /// Value at rest at `value`.
+    pub fn new(value: T, spring: Spring) -> Self {
+        Self {
+            target: value,
+            value,
+            velocity: T::splat(0.0),
+            spring,
+        }
+    }
+
+    pub fn set_target(&mut self, target: T) {
+        self.target = target;
+    }
+
+    /// Move the value and reset the velocity, skipping any springing.
+    pub fn snap(&mut self, value: T) {
+        self.target = value;
+        self.value = value;
+        self.velocity = T::splat(0.0);
+    }
+
+    /// Advance the value towards the target by one timestep.
+    pub fn tick(&mut self, timestep: f32) -> T {
+        if timestep > 0.0 {
+            let instant = SpringInstant {
+                reduced_inertia: T::splat(1.0),
+                displacement: self.value - self.target,
+                velocity: self.velocity,
+            };
+
+            self.velocity = self.velocity + self.spring.impulse(timestep, instant);
+            self.value = self.value + self.velocity * timestep;
+        }
+
+        self.value
+    }
+
+    /// Whether the value is within `threshold` of the target and barely moving.
+    pub fn is_settled(&self, threshold: f32) -> bool {
+        (self.value - self.target).length().abs() <= threshold
+            && self.velocity.length().abs() <= threshold
+    }
+}
+
+pub fn tick_spring_values<T: Kinematic>(
+    clocks: SpringClocks,
+    mut values: Query<(&mut SpringValue<T>, Option<&SpringClock>)>,
+) {
+    for (mut value, clock) in &mut values {
+        let timestep = clocks.delta(clock);
+        if timestep == 0.0 {
+            continue;
+        }
+
+        value.tick(timestep);
+    }
+}
+
+/// Ticks every [`SpringValue<T>`] component in `Update`.
+pub struct SpringValuePlugin<T: Kinematic>(PhantomData<T>);
+
+impl<T: Kinematic> Default for SpringValuePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Kinematic> Plugin for SpringValuePlugin<T>
+where
+    SpringValue<T>: bevy::reflect::GetTypeRegistration,
+{
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringValue<T>>()
+            .register_type::<SpringClock>()
+            .add_systems(Update, tick_spring_values::<T>.run_if(springs_running));
+    }
+}
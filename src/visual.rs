@@ -0,0 +1,287 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    ecs::{
+        entity::{EntityMapper, MapEntities},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    reflect::Reflect,
+    render::{mesh::Indices, render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
+    transform::TransformSystem,
+};
+
+use crate::joint::{SpringJoint, SpringState};
+
+/// Stretches this entity between the two particles of a [`SpringJoint`],
+/// for ropes and bungees without custom rendering code.
+///
+/// The local Y axis of the entity spans the spring and X and Z are scaled by
+/// the `width`, so use a 1x1 sprite or a unit mesh centered on the origin.
+/// The sprite color or the [`StandardMaterial`] base color is tinted from
+/// `slack_color` towards `taut_color` as the spring stretches, give every
+/// visual its own material when tinting meshes.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SpringVisual {
+    /// Entity with the [`SpringJoint`].
+    pub spring: Entity,
+    pub width: f32,
+    pub slack_color: Color,
+    pub taut_color: Color,
+    /// Stretch past the rest length at which the visual is fully `taut_color`.
+    pub taut_stretch: f32,
+}
+
+impl SpringVisual {
+    pub fn new(spring: Entity, width: f32) -> Self {
+        Self {
+            spring,
+            width,
+            slack_color: Color::WHITE,
+            taut_color: Color::srgb(1.0, 0.2, 0.2),
+            taut_stretch: 1.0,
+        }
+    }
+
+    pub fn with_colors(mut self, slack_color: Color, taut_color: Color) -> Self {
+        self.slack_color = slack_color;
+        self.taut_color = taut_color;
+        self
+    }
+
+    pub fn with_taut_stretch(mut self, taut_stretch: f32) -> Self {
+        self.taut_stretch = taut_stretch;
+        self
+    }
+
+    /// Color of the visual for a spring stretched `stretch` past its rest length.
+    pub fn color(&self, stretch: f32) -> Color {
+        let tension = (stretch / self.taut_stretch.max(f32::EPSILON)).clamp(0.0, 1.0);
+        self.slack_color.mix(&self.taut_color, tension)
+    }
+}
+
+impl MapEntities for SpringVisual {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.spring = entity_mapper.map_entity(self.spring);
+    }
+}
+
+pub struct SpringVisualPlugin;
+
+impl Plugin for SpringVisualPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpringVisual>()
+            .register_type::<SpringCoil>()
+            .add_systems(
+                PostUpdate,
+                (spring_visuals, spring_coils).before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Transform of an entity spanning `a` to `b` along its local Y axis.
+pub fn span_transform(a: Vec3, b: Vec3, width: f32) -> Transform {
+    let span = b - a;
+    Transform {
+        translation: (a + b) * 0.5,
+        rotation: Quat::from_rotation_arc(Vec3::Y, span.try_normalize().unwrap_or(Vec3::Y)),
+        scale: Vec3::new(width, span.length(), width),
+    }
+}
+
+pub fn spring_visuals(
+    mut visuals: Query<
+        (
+            &SpringVisual,
+            &mut Transform,
+            Option<&mut Sprite>,
+            Option<&Handle<StandardMaterial>>,
+        ),
+        Without<SpringJoint>,
+    >,
+    springs: Query<(&SpringJoint, Option<&SpringState>)>,
+    transforms: Query<&GlobalTransform>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (visual, mut transform, sprite, material) in &mut visuals {
+        let Ok((joint, state)) = springs.get(visual.spring) else {
+            continue;
+        };
+        let (Ok(a), Ok(b)) = (
+            transforms.get(visual.spring),
+            transforms.get(joint.containing),
+        ) else {
+            continue;
+        };
+
+        *transform = span_transform(a.translation(), b.translation(), visual.width);
+
+        let stretch = state.map_or(0.0, |state| state.length - joint.rest_length);
+        let color = visual.color(stretch);
+        if let Some(mut sprite) = sprite {
+            if sprite.color != color {
+                sprite.color = color;
+            }
+        }
+
+        // Only touch materials whose color changed, to avoid re-uploading them every frame.
+        if let Some(material) = material {
+            if materials
+                .get(material)
+                .is_some_and(|material| material.base_color != color)
+            {
+                if let Some(material) = materials.get_mut(material) {
+                    material.base_color = color;
+                }
+            }
+        }
+    }
+}
+
+/// Helix mesh between the two particles of a 3D [`SpringJoint`], regenerated
+/// whenever the length of the spring changes.
+///
+/// The coil starts at the spring entity and runs along the local Y axis of
+/// this entity to the `containing` entity.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct SpringCoil {
+    /// Entity with the [`SpringJoint`].
+    pub spring: Entity,
+    /// Number of turns of the helix.
+    pub coils: u32,
+    /// Radius of the helix.
+    pub radius: f32,
+    /// Radius of the wire the helix is made of.
+    pub wire_radius: f32,
+    /// Rings of vertices per turn.
+    pub segments: u32,
+    /// Vertices around each ring of the wire.
+    pub sides: u32,
+    /// Length the mesh was last generated for.
+    pub length: f32,
+}
+
+impl SpringCoil {
+    pub fn new(spring: Entity, coils: u32, radius: f32) -> Self {
+        Self {
+            spring,
+            coils,
+            radius,
+            wire_radius: radius * 0.1,
+            segments: 16,
+            sides: 6,
+            length: 0.0,
+        }
+    }
+
+    pub fn with_wire_radius(mut self, wire_radius: f32) -> Self {
+        self.wire_radius = wire_radius;
+        self
+    }
+
+    /// Tube along a helix from the origin to `length` along the Y axis.
+    pub fn mesh(&self, length: f32) -> Mesh {
+        let rings = (self.coils * self.segments).max(1);
+        let sides = self.sides.max(3);
+        let turns = self.coils as f32 * TAU;
+
+        let mut positions = Vec::with_capacity(((rings + 1) * sides) as usize);
+        let mut normals = Vec::with_capacity(positions.capacity());
+        for ring in 0..=rings {
+            let t = ring as f32 / rings as f32;
+            let angle = t * turns;
+            let radial = Vec3::new(angle.cos(), 0.0, angle.sin());
+            let center = radial * self.radius + Vec3::Y * length * t;
+            let tangent = Vec3::new(
+                -angle.sin() * self.radius * turns,
+                length,
+                angle.cos() * self.radius * turns,
+            )
+            .normalize_or_zero();
+            let binormal = tangent.cross(radial);
+
+            for side in 0..sides {
+                let around = side as f32 / sides as f32 * TAU;
+                let normal = radial * around.cos() + binormal * around.sin();
+                positions.push(center + normal * self.wire_radius);
+                normals.push(normal);
+            }
+        }
+
+        let mut indices = Vec::with_capacity((rings * sides * 6) as usize);
+        for ring in 0..rings {
+            for side in 0..sides {
+                let next_side = (side + 1) % sides;
+                let (a, b) = (ring * sides + side, ring * sides + next_side);
+                let (c, d) = (a + sides, b + sides);
+                indices.extend([a, c, b, b, c, d]);
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+impl MapEntities for SpringCoil {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.spring = entity_mapper.map_entity(self.spring);
+    }
+}
+
+pub fn spring_coils(
+    mut commands: Commands,
+    mut coils: Query<
+        (
+            Entity,
+            &mut SpringCoil,
+            &mut Transform,
+            Option<&Handle<Mesh>>,
+        ),
+        Without<SpringJoint>,
+    >,
+    springs: Query<&SpringJoint>,
+    transforms: Query<&GlobalTransform>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (entity, mut coil, mut transform, mesh) in &mut coils {
+        let Ok(joint) = springs.get(coil.spring) else {
+            continue;
+        };
+        let (Ok(a), Ok(b)) = (
+            transforms.get(coil.spring),
+            transforms.get(joint.containing),
+        ) else {
+            continue;
+        };
+
+        let (a, b) = (a.translation(), b.translation());
+        *transform = Transform {
+            scale: Vec3::ONE,
+            translation: a,
+            ..span_transform(a, b, 1.0)
+        };
+
+        let length = a.distance(b);
+        if mesh.is_some() && (length - coil.length).abs() <= 0.001 {
+            continue;
+        }
+        coil.length = length;
+
+        let new_mesh = coil.mesh(length);
+        match mesh.and_then(|mesh| meshes.get_mut(mesh)) {
+            Some(mesh) => *mesh = new_mesh,
+            None => {
+                commands.entity(entity).insert(meshes.add(new_mesh));
+            }
+        }
+    }
+}
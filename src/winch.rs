@@ -0,0 +1,118 @@
+use bevy::{prelude::*, reflect::Reflect};
+
+use crate::joint::SpringJoint;
+use crate::timestep::SpringTimestep;
+
+/// Reels the rest length of the [`SpringJoint`] on this entity towards
+/// `target_rest` at a limited speed, for cranes, fishing lines and tow cables.
+///
+/// A [`WinchReeled`] event is sent once the target is reached, and again
+/// for every new `target_rest` it is reeled to.
+#[derive(Debug, Copy, Clone, Component, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[reflect(Component)]
+pub struct Winch {
+    /// Rest length to reel towards.
+    pub target_rest: f32,
+    /// Fastest the rest length changes, in units per second.
+    pub speed: f32,
+    /// Target the last [`WinchReeled`] was sent for.
+    reeled_to: Option<f32>,
+}
+
+impl Winch {
+    pub fn new(target_rest: f32, speed: f32) -> Self {
+        Self {
+            target_rest,
+            speed,
+            reeled_to: None,
+        }
+    }
+
+    /// Reel towards a new rest length, same as setting `target_rest`.
+    pub fn reel_to(&mut self, target_rest: f32) {
+        self.target_rest = target_rest;
+    }
+
+    /// Whether a joint at `rest_length` has reached the current `target_rest`.
+    pub fn is_reeled(&self, rest_length: f32) -> bool {
+        rest_length == self.target_rest
+    }
+
+    /// Rest length after reeling from `rest_length` for `timestep` seconds.
+    pub fn step(&self, rest_length: f32, timestep: f32) -> f32 {
+        let max_change = self.speed.max(0.0) * timestep;
+        rest_length + (self.target_rest - rest_length).clamp(-max_change, max_change)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Event)]
+pub struct WinchReeled {
+    pub entity: Entity,
+    pub rest_length: f32,
+}
+
+pub fn winch(
+    timestep: Res<SpringTimestep>,
+    mut winches: Query<(Entity, &mut Winch, &mut SpringJoint)>,
+    mut reeled_events: EventWriter<WinchReeled>,
+) {
+    if !timestep.is_running() {
+        return;
+    }
+
+    for (entity, mut winch, mut joint) in &mut winches {
+        // Already reeled in and reported, a stale `reeled_to` is harmless as
+        // the joint has to be at the target as well.
+        if winch.is_reeled(joint.rest_length) && winch.reeled_to == Some(winch.target_rest) {
+            continue;
+        }
+
+        let rest_length = winch.step(joint.rest_length, timestep.step_delta());
+        if rest_length != joint.rest_length {
+            joint.rest_length = rest_length;
+        }
+
+        if winch.is_reeled(rest_length) {
+            winch.reeled_to = Some(rest_length);
+            reeled_events.send(WinchReeled {
+                entity,
+                rest_length,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::*;
+    use crate::Spring;
+
+    #[test]
+    fn reels_rest_length_at_speed() {
+        let mut app = app(plugin());
+        let chain = chain(&mut app, 1, 1.0, Spring::snappy());
+        app.world_mut()
+            .entity_mut(chain[1])
+            .insert(Winch::new(2.0, 1.0));
+        let rest_length = |app: &App| {
+            app.world()
+                .get::<SpringJoint>(chain[1])
+                .unwrap()
+                .rest_length
+        };
+
+        step(&mut app, 30);
+        assert!((rest_length(&app) - 1.5).abs() < 1e-3);
+
+        let mut reeled = 0;
+        for _ in 0..60 {
+            step(&mut app, 1);
+            let events = app.world().resource::<Events<WinchReeled>>();
+            reeled += events.iter_current_update_events().count();
+        }
+        assert_eq!(rest_length(&app), 2.0);
+        assert_eq!(reeled, 1);
+    }
+}